@@ -27,6 +27,10 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
                     continue;
                 };
 
+                if *attr_ident != "terminal" {
+                    continue;
+                }
+
                 let Ok(Expr::Lit(ExprLit {
                     lit: Lit::Str(literal),
                     ..
@@ -35,17 +39,15 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
                     panic!("missing matcher for #[terminal] {ident}");
                 };
 
-                if *attr_ident == "terminal" {
-                    return Some((
-                        Variant {
-                            attrs: vec![],
-                            ident,
-                            fields: syn::Fields::Unit,
-                            discriminant,
-                        },
-                        literal,
-                    ));
-                }
+                return Some((
+                    Variant {
+                        attrs: vec![],
+                        ident,
+                        fields: syn::Fields::Unit,
+                        discriminant,
+                    },
+                    literal,
+                ));
             }
 
             None
@@ -122,6 +124,15 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
         }
     });
 
+    let matches_text = terminal_variants_tuples.iter().map(|(variant, literal)| {
+        let Variant {
+            ident: var_ident, ..
+        } = variant;
+        quote! {
+            Terminal::#var_ident => #literal,
+        }
+    });
+
     let matches_get_position = variants.iter().map(|variant| {
         let Variant {
             ident: var_ident, ..
@@ -131,6 +142,19 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
         }
     });
 
+    let matches_keyword = terminal_variants_tuples
+        .iter()
+        .filter(|(_, literal)| literal.value().bytes().all(|byte| byte.is_ascii_alphabetic()))
+        .map(|(variant, literal)| {
+            let Variant {
+                ident: var_ident, ..
+            } = variant;
+
+            quote! {
+                #ident::#var_ident { .. } => Some(#literal),
+            }
+        });
+
     let terminal_insertions = terminal_variants_tuples.iter().map(|(variant, literal)| {
         let Variant {
             ident: var_ident, ..
@@ -139,7 +163,7 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
         let literal = literal.value();
 
         quote! {
-            terminal!(entries, #var_ident, #literal);
+            terminal!(lexikon, #var_ident, #literal);
         }
     });
 
@@ -151,7 +175,7 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
         let literal = literal.value();
 
         quote! {
-            literal!(entries, #var_ident, #literal);
+            literal!(lexikon, #var_ident, #literal);
         }
     });
 
@@ -192,6 +216,13 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
                     #(#matches_to_token)*
                 }
             }
+
+            /// The source text matched by this terminal.
+            pub fn text(&self) -> &'static str {
+                match self {
+                    #(#matches_text)*
+                }
+            }
         }
 
         impl #ident {
@@ -200,76 +231,136 @@ pub fn impl_token_macro(ast: syn::DeriveInput) -> TokenStream {
                     #(#matches_get_position)*
                 }
             }
+
+            /// The keyword this token stands for (if it is a reserved keyword like `let`).
+            pub fn keyword(&self) -> Option<&'static str> {
+                match self {
+                    #(#matches_keyword)*
+                    _ => None
+                }
+            }
         }
 
         macro_rules! terminal {
-            ($entries:ident, $name:ident, $value:expr) => {
-                Self::insert(
-                    &mut $entries,
-                    Regex::new(&$value.escape_unicode().to_string()).unwrap(),
-                    |_, position| Token::$name { position },
-                );
+            ($lexikon:ident, $name:ident, $value:expr) => {
+                $lexikon.insert_terminal($value, Terminal::$name);
             };
         }
 
         macro_rules! literal {
-            ($entries:ident, $name:ident, $value:expr) => {
-                Self::insert(
-                    &mut $entries,
-                    Regex::new($value).unwrap(),
-                    |matched, position| Token::$name {
+            ($lexikon:ident, $name:ident, $value:expr) => {
+                $lexikon.insert_literal(
+                    Regex::new(concat!("^(?:", $value, ")")).unwrap(),
+                    |matched, position, interner| Token::$name {
                         position,
-                        value: matched.as_str().parse().unwrap(),
+                        value: FromLexeme::from_lexeme(matched, interner),
                     },
                 );
             };
         }
 
-        type Entries = Vec<(Regex, Box<dyn Fn(Match, usize) -> Token>)>;
-
+        /// Lexikon for finding the longest token at the start of some input.
+        ///
+        /// All terminals are stored in a byte-level trie (i.e., a table driven state machine), so
+        /// they can be matched in a single pass over the input. Literals are matched with
+        /// anchored regexes, which never scan past the token they match.
         pub struct Lexikon {
-            entries: Entries,
+            /// `transitions[state][byte]` is the state after reading `byte` (0 means no
+            /// transition, since the root is never a target).
+            transitions: Vec<[u16; 256]>,
+            /// The terminal which is accepted in every state (if any).
+            accepting: Vec<Option<Terminal>>,
+            literals: Vec<(Regex, fn(&str, Position, &mut Interner) -> Token)>,
         }
 
         impl<'a> Lexikon {
             pub fn new() -> Lexikon {
-                let mut entries = vec![];
+                let mut lexikon = Lexikon {
+                    transitions: vec![[0; 256]],
+                    accepting: vec![None],
+                    literals: vec![],
+                };
 
                 #(#terminal_insertions)*
 
                 #(#literal_insertions)*
 
-                Lexikon { entries }
+                lexikon
+            }
+
+            fn insert_terminal(&mut self, value: &str, terminal: Terminal) {
+                let mut state = 0;
+
+                for byte in value.bytes() {
+                    let next = self.transitions[state][byte as usize] as usize;
+
+                    state = if next == 0 {
+                        let next = self.transitions.len();
+                        self.transitions.push([0; 256]);
+                        self.accepting.push(None);
+                        self.transitions[state][byte as usize] =
+                            u16::try_from(next).expect("too many terminals for lexikon");
+                        next
+                    } else {
+                        next
+                    };
+                }
+
+                self.accepting[state] = Some(terminal);
             }
 
-            fn insert<F: Fn(Match, usize) -> Token + 'static>(entries: &mut Entries, reg: Regex, f: F) {
-                entries.push((reg, Box::new(f)))
+            fn insert_literal(
+                &mut self,
+                reg: Regex,
+                f: fn(&str, Position, &mut Interner) -> Token,
+            ) {
+                self.literals.push((reg, f))
             }
 
+            /// Find the longest match at the start of `pattern`. On a tie, terminals take
+            /// precedence over literals. The values of literals are created with the given
+            /// interner.
             pub fn find_longest_match(
                 &self,
                 pattern: &'a str,
                 position: usize,
+                interner: &mut Interner,
             ) -> (usize, Option<Token>) {
-                let mut longest = (0, None);
-
-                for (reg, mapper) in &self.entries {
-                    let Some(res) = reg.captures_at(pattern, 0).and_then(|res| res.get(0)) else {
-                        continue;
-                    };
-
-                    let len = res.len();
+                // walk the trie as far as possible and remember the last accepting state
+                let mut state = 0;
+                let mut terminal = None;
+                for (index, byte) in pattern.bytes().enumerate() {
+                    state = self.transitions[state][byte as usize] as usize;
+                    if state == 0 {
+                        break;
+                    }
+                    if let Some(accepted) = &self.accepting[state] {
+                        terminal = Some((index + 1, accepted));
+                    }
+                }
 
-                    if len > longest.0 && res.start() == 0 {
-                        longest = (len, Some(mapper(res, position)));
+                let mut longest = terminal.map_or(0, |(len, _)| len);
+                let mut literal = None;
+                for (reg, mapper) in &self.literals {
+                    if let Some(res) = reg.find(pattern) {
+                        if res.len() > longest {
+                            longest = res.len();
+                            literal = Some((res.as_str(), mapper));
+                        }
                     }
                 }
 
-                longest
+                match (literal, terminal) {
+                    (Some((matched, mapper)), _) => {
+                        (longest, Some(mapper(matched, Position::new(position, longest), interner)))
+                    }
+                    (None, Some((len, accepted))) => {
+                        (len, Some(accepted.to_token(Position::new(position, len))))
+                    }
+                    (None, None) => (0, None),
+                }
             }
         }
-
-
     };
 
     gen.into()
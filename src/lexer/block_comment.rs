@@ -0,0 +1,79 @@
+use super::{LexError, Position, Token};
+
+/// Lex a block comment (`/* ... */`) at the start of `input` (which has to start with `/*`).
+/// Block comments can be nested, so every `/*` within the comment needs its own `*/`.
+///
+/// Returns the length of the comment (in bytes) and either the token or an error. An
+/// unterminated comment is consumed up to the end of the input and reported at its opening `/*`.
+pub(crate) fn lex_block_comment(input: &str, offset: usize) -> (usize, Result<Token, LexError>) {
+    let bytes = input.as_bytes();
+
+    let mut index = 2;
+    let mut depth = 1;
+
+    while depth > 0 {
+        match (bytes.get(index), bytes.get(index + 1)) {
+            (None, _) => {
+                return (
+                    input.len(),
+                    Err(LexError::new(
+                        "unterminated block comment",
+                        Position::new(offset, 2),
+                    )),
+                );
+            }
+            (Some(b'/'), Some(b'*')) => {
+                depth += 1;
+                index += 2;
+            }
+            (Some(b'*'), Some(b'/')) => {
+                depth -= 1;
+                index += 2;
+            }
+            _ => index += 1,
+        }
+    }
+
+    (
+        index,
+        Ok(Token::Comment {
+            value: input[..index].to_string(),
+            position: Position::new(offset, index),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_comment() {
+        let (len, Ok(Token::Comment { value, position })) =
+            lex_block_comment("/* a\n * b */ let", 4)
+        else {
+            panic!("expected comment");
+        };
+        assert_eq!(12, len);
+        assert_eq!("/* a\n * b */", value);
+        assert_eq!(Position::new(4, 12), position);
+    }
+
+    #[test]
+    fn test_nested_block_comment() {
+        let (_, Ok(Token::Comment { value, .. })) = lex_block_comment("/* a /* b */ c */ d */", 0)
+        else {
+            panic!("expected comment");
+        };
+        assert_eq!("/* a /* b */ c */", value);
+    }
+
+    #[test]
+    fn test_unterminated() {
+        let (len, Err(error)) = lex_block_comment("/* a /* b */ c", 7) else {
+            panic!("expected error");
+        };
+        assert_eq!(14, len);
+        assert_eq!(Position::new(7, 2), error.position);
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use super::{LexError, LexResult, Position};
+
+const PREFIX: &str = "pesca:";
+
+/// A structured comment configuring the tooling for the following code, e.g.,
+/// `// pesca:allow(unused_variable)` or `// pesca:off`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Directive {
+    pub name: String,
+    pub arguments: Vec<String>,
+}
+
+impl Directive {
+    /// Whether this directive disables all checks (until a `pesca:on`).
+    pub fn is_off(&self) -> bool {
+        self.name == "off"
+    }
+
+    /// Whether this directive re-enables all checks.
+    pub fn is_on(&self) -> bool {
+        self.name == "on"
+    }
+
+    /// Whether this directive allows the given lint (e.g., `allow(unused_variable)`).
+    pub fn allows(&self, lint: &str) -> bool {
+        self.name == "allow" && self.arguments.iter().any(|argument| argument == lint)
+    }
+}
+
+/// Parse a comment (including the leading `//`) as a directive. Returns `None` for ordinary
+/// comments and an error for a comment which starts like a directive, but is malformed.
+pub(crate) fn parse_directive(comment: &str, position: Position) -> Option<LexResult<Directive>> {
+    let content = comment.strip_prefix("//")?.trim();
+    let content = content.strip_prefix(PREFIX)?;
+
+    let error = || {
+        LexError::new(
+            format!("malformed directive `{}`", comment.trim()),
+            position,
+        )
+    };
+
+    let is_name = |name: &str| {
+        !name.is_empty()
+            && name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-')
+    };
+
+    let (name, arguments) = match content.split_once('(') {
+        Some((name, rest)) => {
+            let Some(arguments) = rest.trim_end().strip_suffix(')') else {
+                return Some(Err(error()));
+            };
+
+            let arguments = arguments
+                .split(',')
+                .map(|argument| argument.trim().to_string())
+                .collect::<Vec<_>>();
+
+            (name.trim(), arguments)
+        }
+        None => (content.trim(), vec![]),
+    };
+
+    if !is_name(name) || !arguments.iter().all(|argument| is_name(argument)) {
+        return Some(Err(error()));
+    }
+
+    Some(Ok(Directive {
+        name: name.to_string(),
+        arguments,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(comment: &str) -> Option<LexResult<Directive>> {
+        parse_directive(comment, Position::default())
+    }
+
+    #[test]
+    fn test_ordinary_comment() {
+        assert_eq!(None, parse("// just a comment"));
+    }
+
+    #[test]
+    fn test_directive_without_arguments() {
+        let Some(Ok(directive)) = parse("// pesca:off") else {
+            panic!("expected directive");
+        };
+        assert!(directive.is_off());
+        assert!(directive.arguments.is_empty());
+    }
+
+    #[test]
+    fn test_directive_with_arguments() {
+        let Some(Ok(directive)) = parse("//pesca:allow(unused_variable, dead_code)") else {
+            panic!("expected directive");
+        };
+        assert!(directive.allows("unused_variable"));
+        assert!(directive.allows("dead_code"));
+        assert!(!directive.allows("shadowing"));
+    }
+
+    #[test]
+    fn test_malformed_directive() {
+        assert!(matches!(parse("// pesca:allow(foo"), Some(Err(_))));
+        assert!(matches!(parse("// pesca:allow(foo,)"), Some(Err(_))));
+        assert!(matches!(parse("// pesca:"), Some(Err(_))));
+    }
+}
@@ -0,0 +1,177 @@
+use super::{LexError, LexResult, Position};
+
+/// All supported escape sequences (besides unicode escapes) and the characters they stand for.
+const ESCAPES: [(char, char); 9] = [
+    ('n', '\n'),
+    ('t', '\t'),
+    ('r', '\r'),
+    ('0', '\0'),
+    ('\\', '\\'),
+    ('\'', '\''),
+    ('"', '"'),
+    // braces only need to be escaped in strings, where they would start an interpolation
+    ('{', '{'),
+    ('}', '}'),
+];
+
+const VALID_ESCAPES: &str = r#"\n, \t, \r, \0, \\, \', \", \{, \} and \u{XXXX}"#;
+
+/// Validate all escape sequences of the given (raw) string or character literal starting at the
+/// byte `offset` of the input. The error points at the invalid escape sequence itself.
+pub(crate) fn validate_escapes(raw: &str, offset: usize) -> LexResult<()> {
+    let mut chars = raw.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+
+        match chars.next() {
+            Some((_, c)) if ESCAPES.iter().any(|(escape, _)| *escape == c) => {}
+            Some((_, 'u')) => {
+                let mut end = start + 2;
+                let mut digits = String::new();
+                let mut closed = false;
+
+                if let Some((index, '{')) = chars.peek().copied() {
+                    chars.next();
+                    end = index + 1;
+                    while let Some((index, c)) = chars.next_if(|(_, c)| *c != '"' && *c != '\'') {
+                        end = index + c.len_utf8();
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        digits.push(c);
+                    }
+                }
+
+                let escape = &raw[start..end];
+                let error = |message: String| {
+                    LexError::new(message, Position::new(offset + start, end - start))
+                };
+
+                if !closed {
+                    return Err(error(format!(
+                        "invalid unicode escape `{escape}`, expected `\\u{{XXXX}}`"
+                    )));
+                }
+                if digits.is_empty()
+                    || digits.len() > 6
+                    || !digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return Err(error(format!(
+                        "invalid unicode escape `{escape}`, expected 1 to 6 hexadecimal digits"
+                    )));
+                }
+                if u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .is_none()
+                {
+                    return Err(error(format!(
+                        "invalid unicode escape `{escape}`, {digits} is not a valid code point"
+                    )));
+                }
+            }
+            Some((index, c)) => {
+                return Err(LexError::new(
+                    format!("invalid escape sequence `\\{c}`, valid escapes are {VALID_ESCAPES}"),
+                    Position::new(offset + start, index + c.len_utf8() - start),
+                ))
+            }
+            None => {
+                return Err(LexError::new(
+                    format!("incomplete escape sequence, valid escapes are {VALID_ESCAPES}"),
+                    Position::new(offset + start, 1),
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Decode all escape sequences of the given (already validated) string.
+pub fn unescape(raw: &str) -> String {
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('u') => {
+                let digits = chars
+                    .by_ref()
+                    .skip(1)
+                    .take_while(|c| *c != '}')
+                    .collect::<String>();
+                if let Some(c) = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    result.push(c);
+                }
+            }
+            Some(c) => {
+                if let Some((_, value)) = ESCAPES.iter().find(|(escape, _)| *escape == c) {
+                    result.push(*value);
+                }
+            }
+            None => {}
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_escapes() {
+        assert_eq!(Ok(()), validate_escapes(r#""a\n\t\\\"\u{1F600}""#, 0));
+    }
+
+    #[test]
+    fn test_invalid_escape() {
+        let error = validate_escapes(r#""ab\q""#, 10).expect_err("should fail");
+
+        assert_eq!(Position::new(13, 2), error.position);
+        assert!(error.message.contains(r"`\q`"));
+        assert!(error.message.contains(VALID_ESCAPES));
+    }
+
+    #[test]
+    fn test_empty_unicode_escape() {
+        let error = validate_escapes(r"'\u{}'", 0).expect_err("should fail");
+
+        assert_eq!(Position::new(1, 4), error.position);
+        assert!(error.message.contains("hexadecimal digits"));
+    }
+
+    #[test]
+    fn test_invalid_code_point() {
+        let error = validate_escapes(r#""\u{D800}""#, 0).expect_err("should fail");
+
+        assert_eq!(Position::new(1, 8), error.position);
+        assert!(error.message.contains("not a valid code point"));
+    }
+
+    #[test]
+    fn test_unclosed_unicode_escape() {
+        let error = validate_escapes(r#""\u{41""#, 0).expect_err("should fail");
+
+        assert_eq!(Position::new(1, 5), error.position);
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!("a\n\"\u{41}\\", unescape(r#"a\n\"\u{41}\\"#));
+    }
+}
@@ -0,0 +1,162 @@
+use std::ops::Range;
+
+use super::block_comment;
+
+/// Why a string literal could not be scanned for interpolations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScanError {
+    /// The literal itself has no closing `"`.
+    Unterminated,
+    /// The `{` at the given offset has no matching `}`.
+    UnclosedInterpolation(usize),
+}
+
+/// Scan the string literal at the start of `input` (which has to start with `"`) for interpolated
+/// expressions (`{...}`). Braces which are escaped (`\{`) are not interpolated.
+///
+/// Returns the length of the literal (in bytes) and the ranges of all interpolated expressions
+/// (without their braces).
+pub(crate) fn scan_string(input: &str) -> Result<(usize, Vec<Range<usize>>), ScanError> {
+    let bytes = input.as_bytes();
+    let mut interpolations = vec![];
+    let mut index = 1;
+
+    loop {
+        match bytes.get(index).ok_or(ScanError::Unterminated)? {
+            // the braces of a unicode escape (`\u{...}`) do not start an interpolation
+            b'\\' if input[index..].starts_with("\\u{") => {
+                index += 3;
+                while !matches!(bytes.get(index), None | Some(b'}' | b'"')) {
+                    index += 1;
+                }
+                if bytes.get(index) == Some(&b'}') {
+                    index += 1;
+                }
+            }
+            b'\\' => index += 2,
+            b'"' => return Ok((index + 1, interpolations)),
+            b'{' => {
+                let start = index + 1;
+                let end = start
+                    + scan_interpolation(&input[start..])
+                        .ok_or(ScanError::UnclosedInterpolation(index))?;
+                interpolations.push(start..end);
+                index = end + 1;
+            }
+            _ => index += 1,
+        }
+    }
+}
+
+/// The length of the interpolated expression at the start of `input` (up to its closing `}`).
+/// Nested blocks, string and character literals and comments are skipped as a whole.
+fn scan_interpolation(input: &str) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut depth = 0;
+    let mut index = 0;
+
+    loop {
+        match bytes.get(index)? {
+            b'"' => {
+                let (len, _) = scan_string(&input[index..]).ok()?;
+                index += len;
+            }
+            b'\'' => index += scan_char(&input[index..]).unwrap_or(1),
+            b'/' if input[index..].starts_with("//") => {
+                index += input[index..].find('\n')?;
+            }
+            b'/' if input[index..].starts_with("/*") => {
+                let (len, _) = block_comment::lex_block_comment(&input[index..], 0);
+                index += len;
+            }
+            b'{' => {
+                depth += 1;
+                index += 1;
+            }
+            b'}' if depth == 0 => return Some(index),
+            b'}' => {
+                depth -= 1;
+                index += 1;
+            }
+            _ => index += 1,
+        }
+    }
+}
+
+/// The length of the character literal at the start of `input` (which has to start with `'`), if
+/// there is one.
+fn scan_char(input: &str) -> Option<usize> {
+    let rest = &input[1..];
+    let len = if rest.starts_with("\\u{") {
+        rest.find('}')? + 1
+    } else if let Some(escaped) = rest.strip_prefix('\\') {
+        1 + escaped.chars().next()?.len_utf8()
+    } else {
+        rest.chars().next().filter(|c| *c != '\'')?.len_utf8()
+    };
+
+    rest[len..].starts_with('\'').then_some(len + 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The length of the literal and the (start, end) offsets of its interpolations.
+    fn scan(input: &str) -> Option<(usize, Vec<(usize, usize)>)> {
+        let (len, interpolations) = scan_string(input).ok()?;
+
+        Some((
+            len,
+            interpolations
+                .into_iter()
+                .map(|range| (range.start, range.end))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_scan_plain_string() {
+        assert_eq!(Some((7, vec![])), scan(r#""hello" + 1"#));
+    }
+
+    #[test]
+    fn test_scan_interpolations() {
+        assert_eq!(
+            Some((18, vec![(2, 6), (13, 16)])),
+            scan(r#""{name} and {a+1}""#)
+        );
+    }
+
+    #[test]
+    fn test_scan_escaped_braces() {
+        assert_eq!(Some((9, vec![])), scan(r#""\{a\}\"""#));
+        assert_eq!(Some((15, vec![(12, 13)])), scan(r#""\u{1F600} {a}""#));
+    }
+
+    #[test]
+    fn test_scan_nested_strings_and_blocks() {
+        assert_eq!(Some((17, vec![(2, 15)])), scan(r#""{f("}", { x })}""#));
+    }
+
+    #[test]
+    fn test_scan_chars_and_comments() {
+        assert_eq!(Some((14, vec![(4, 10)])), scan(r#""a {f('}')} b""#));
+        assert_eq!(Some((14, vec![(2, 12)])), scan(r#""{f('\'', x)}""#));
+        assert_eq!(Some((17, vec![(2, 15)])), scan(r#""{a /* } */ + 1}""#));
+        assert_eq!(Some((14, vec![(2, 12)])), scan("\"{a // }\n+ 1}\""));
+    }
+
+    #[test]
+    fn test_scan_unterminated() {
+        assert_eq!(Err(ScanError::Unterminated), scan_string(r#""abc"#));
+        assert_eq!(
+            Err(ScanError::UnclosedInterpolation(1)),
+            scan_string(r#""{abc""#)
+        );
+        assert_eq!(
+            Err(ScanError::UnclosedInterpolation(3)),
+            scan_string(r#""a {b" + c"#)
+        );
+    }
+}
@@ -1,29 +1,89 @@
+mod block_comment;
+mod directive;
+mod escape;
+mod format_string;
 mod lexmap;
+mod number;
+mod position;
+mod regex_literal;
+mod symbol;
 mod token;
 mod tokens;
 
+pub use directive::Directive;
+pub use escape::unescape;
 pub use lexmap::*;
+pub use position::*;
+pub use symbol::*;
 pub use token::*;
 pub use tokens::*;
 
-use std::{error::Error, fmt::Display};
+use std::{error::Error, fmt::Display, sync::OnceLock};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct LexError(String);
+pub struct LexError {
+    pub message: String,
+    pub position: Position,
+    /// The character at the start of the error (or `'\0'` at the end of the input).
+    pub character: char,
+    /// The line of the error (starting at 1).
+    pub line: usize,
+    /// The column of the error in characters (starting at 1).
+    pub column: usize,
+}
 
 pub type LexResult<T> = Result<T, LexError>;
 
+impl LexError {
+    /// Create an error at the given position. The character, line and column are only filled in
+    /// by the [`Lexer`], which knows the whole input.
+    pub fn new(message: impl Into<String>, position: Position) -> Self {
+        Self {
+            message: message.into(),
+            position,
+            character: '\0',
+            line: 0,
+            column: 0,
+        }
+    }
+}
+
+/// Fail for inputs which are too large to be covered by a [`Position`] (i.e., above 4 GiB),
+/// instead of producing wrong positions.
+pub fn check_input_len(len: usize) -> LexResult<()> {
+    match len > Position::MAX_OFFSET {
+        true => Err(LexError {
+            line: 1,
+            column: 1,
+            ..LexError::new(
+                format!("input of {len} bytes is too large (at most 4 GiB are supported)"),
+                Position::default(),
+            )
+        }),
+        false => Ok(()),
+    }
+}
+
 impl Display for LexError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.0.as_str())
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
     }
 }
 
 impl Error for LexError {}
 
+/// The lexikon is only built once and shared between all lexers (and threads).
+fn lexikon() -> &'static Lexikon {
+    static LEXIKON: OnceLock<Lexikon> = OnceLock::new();
+    LEXIKON.get_or_init(Lexikon::new)
+}
+
 pub struct Lexer<'a> {
     tokens: Vec<Token>,
-    lexikon: Lexikon,
+    errors: Vec<LexError>,
+    lexikon: &'static Lexikon,
+    /// Interner of all identifiers of this input, so equal names share a single symbol.
+    interner: Interner,
     position: usize,
     input: &'a str,
 }
@@ -32,7 +92,9 @@ impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         Self {
             tokens: vec![],
-            lexikon: Lexikon::new(),
+            errors: vec![],
+            lexikon: lexikon(),
+            interner: Interner::new(),
             position: 0,
             input,
         }
@@ -50,33 +112,306 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    pub fn lex(mut self) -> LexResult<Vec<Token>> {
-        while self.position != self.input.len() {
+    /// Lex the whole input and fail on the first error.
+    pub fn lex(self) -> LexResult<Vec<Token>> {
+        let (tokens, mut errors) = self.lex_recovering();
+
+        match errors.is_empty() {
+            true => Ok(tokens),
+            false => Err(errors.swap_remove(0)),
+        }
+    }
+
+    /// Lex the whole input and collect all errors on the way. Malformed number literals are
+    /// emitted as [`Token::InvalidNumber`], so the tokens can still be parsed.
+    pub fn lex_recovering(mut self) -> (Vec<Token>, Vec<LexError>) {
+        if let Err(error) = check_input_len(self.input.len()) {
+            return (vec![], vec![error]);
+        }
+
+        self.lex_tokens();
+        self.finish()
+    }
+
+    fn lex_tokens(&mut self) {
+        while self.position < self.input.len() {
             self.eat_whitespace();
-            let (len, res) = self
-                .lexikon
-                .find_longest_match(&self.input[self.position..], self.position)
-                .clone();
+
+            if self.input[self.position..].starts_with('"')
+                && !self.expects_asm_template()
+                && self.lex_format_string()
+            {
+                continue;
+            }
+
+            if self
+                .input
+                .as_bytes()
+                .get(self.position)
+                .is_some_and(u8::is_ascii_digit)
+            {
+                self.lex_number();
+                continue;
+            }
+
+            if self.input[self.position..].starts_with("/*") {
+                self.lex_block_comment();
+                continue;
+            }
+
+            if self.input[self.position..].starts_with('/')
+                && !self.input[self.position..].starts_with("//")
+                && self.expects_operand()
+            {
+                self.lex_regex();
+                continue;
+            }
+
+            let (len, res) = self.lexikon.find_longest_match(
+                &self.input[self.position..],
+                self.position,
+                &mut self.interner,
+            );
 
             match res {
-                Some(t) => self.tokens.push(t),
-                None => {
-                    if self.position == self.input.len() {
-                        return Ok(self.tokens);
-                    } else {
-                        panic!(
-                            "Failed to lex '{}' at position {}; remaining '{}'",
-                            self.input,
-                            self.position,
-                            &self.input[self.position..]
-                        );
+                Some(mut t) => {
+                    match &mut t {
+                        Token::StringLiteral { value, position }
+                        | Token::CharLiteral { value, position } => {
+                            if let Err(error) =
+                                escape::validate_escapes(value, position.offset as usize)
+                            {
+                                self.errors.push(error);
+                            }
+                            // the token carries the decoded content, without its quotes
+                            *value = unescape(&value[1..value.len() - 1]);
+                        }
+                        // `////` (or more) is an ordinary comment, e.g., a separator line
+                        Token::Comment { value, position }
+                            if value.starts_with("///") && !value.starts_with("////") =>
+                        {
+                            let value = &value[3..];
+                            self.tokens.push(Token::DocComment {
+                                value: value.strip_prefix(' ').unwrap_or(value).to_string(),
+                                position: *position,
+                            });
+                            self.position += len;
+                            continue;
+                        }
+                        Token::Comment { value, position } => {
+                            match directive::parse_directive(value, *position) {
+                                Some(Ok(directive)) => {
+                                    self.tokens.push(Token::Directive {
+                                        value: directive,
+                                        position: *position,
+                                    });
+                                    self.position += len;
+                                    continue;
+                                }
+                                Some(Err(error)) => self.errors.push(error),
+                                None => {}
+                            }
+                        }
+                        _ => {}
                     }
+                    self.tokens.push(t)
+                }
+                None => {
+                    let Some(character) = self.input[self.position..].chars().next() else {
+                        return;
+                    };
+
+                    // skip the offending character, so lexing can continue after it
+                    let message = match character {
+                        '"' => "unterminated string literal".into(),
+                        character => format!("unexpected character '{}'", character.escape_debug()),
+                    };
+                    self.errors.push(LexError::new(
+                        message,
+                        Position::new(self.position, character.len_utf8()),
+                    ));
+                    self.position += character.len_utf8();
+                    continue;
                 }
             };
             self.position += len;
         }
+    }
+
+    /// Fill in the character, line and column of all errors.
+    fn finish(mut self) -> (Vec<Token>, Vec<LexError>) {
+        for error in &mut self.errors {
+            let mut offset = (error.position.offset as usize).min(self.input.len());
+            while !self.input.is_char_boundary(offset) {
+                offset -= 1;
+            }
+
+            let before = &self.input[..offset];
+            let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+            error.character = self.input[offset..].chars().next().unwrap_or('\0');
+            error.line = before.matches('\n').count() + 1;
+            error.column = before[line_start..].chars().count() + 1;
+        }
+
+        (self.tokens, self.errors)
+    }
+
+    /// Whether the next token starts an operand (instead of continuing one, e.g., with a binary
+    /// operator). This is used to distinguish a regex literal from a division. A `?` is taken as
+    /// the end of an operand (i.e., a try like `f()? / 2`), so a regex can not directly follow the
+    /// `?` of a ternary.
+    fn expects_operand(&self) -> bool {
+        !matches!(
+            self.tokens.iter().rev().find(|token| {
+                !matches!(
+                    token,
+                    Token::Comment { .. } | Token::DocComment { .. } | Token::Directive { .. }
+                )
+            }),
+            Some(
+                Token::Id { .. }
+                    | Token::Integer { .. }
+                    | Token::FloatingPoint { .. }
+                    | Token::InvalidNumber { .. }
+                    | Token::TrueKeyword { .. }
+                    | Token::FalseKeyword { .. }
+                    | Token::StringLiteral { .. }
+                    | Token::CharLiteral { .. }
+                    | Token::RegexLiteral { .. }
+                    | Token::FormatStringEnd { .. }
+                    | Token::RParen { .. }
+                    | Token::RBracket { .. }
+                    | Token::QuestionMark { .. }
+            )
+        )
+    }
+
+    /// Whether the next string is the template of an `asm { ... }` expression, whose braces refer
+    /// to operands (and are therefore not interpolated).
+    fn expects_asm_template(&self) -> bool {
+        matches!(
+            self.tokens.as_slice(),
+            [.., Token::Id { value, .. }, Token::LBrace { .. }] if value.as_str() == "asm"
+        )
+    }
+
+    /// Lex an interpolated string into its text segments and the tokens of the embedded
+    /// expressions, e.g., `"a {b} c"` into `FormatStringStart("a ")`, `Id(b)` and
+    /// `FormatStringEnd(" c")`. Returns `false` (without consuming anything) if the string at the
+    /// current position has no (well-formed) interpolations.
+    fn lex_format_string(&mut self) -> bool {
+        let start = self.position;
+        let (len, interpolations) = match format_string::scan_string(&self.input[start..]) {
+            Ok(result) => result,
+            // the lexikon reports the missing `"`
+            Err(format_string::ScanError::Unterminated) => return false,
+            // report the brace and recover by lexing the literal as a plain string
+            Err(format_string::ScanError::UnclosedInterpolation(offset)) => {
+                self.errors.push(LexError::new(
+                    "unclosed `{` in string literal, escape it as `\\{` for a literal brace",
+                    Position::new(start + offset, 1),
+                ));
+                return false;
+            }
+        };
+        if interpolations.is_empty() {
+            return false;
+        }
+
+        // the current segment of text starts after its opening `"` (or `}`)
+        let mut text_start = start + 1;
+
+        for (index, interpolation) in interpolations.into_iter().enumerate() {
+            let (expr_start, expr_end) = (start + interpolation.start, start + interpolation.end);
+
+            let value = self.decode_text(text_start, expr_start - 1);
+            let position = Position::new(text_start - 1, expr_start - text_start + 1);
+            self.tokens.push(match index {
+                0 => Token::FormatStringStart { value, position },
+                _ => Token::FormatStringMiddle { value, position },
+            });
+
+            // lex the embedded expression on its own, but keep the positions within the input
+            let mut lexer = Lexer {
+                tokens: vec![],
+                errors: vec![],
+                lexikon: self.lexikon,
+                interner: std::mem::take(&mut self.interner),
+                position: expr_start,
+                input: &self.input[..expr_end],
+            };
+            lexer.lex_tokens();
+            self.interner = lexer.interner;
+            self.tokens.extend(lexer.tokens);
+            self.errors.extend(lexer.errors);
+
+            text_start = expr_end + 1;
+        }
+
+        let end = start + len;
+        let value = self.decode_text(text_start, end - 1);
+        self.tokens.push(Token::FormatStringEnd {
+            value,
+            position: Position::new(text_start - 1, end - text_start + 1),
+        });
+        self.position = end;
+
+        true
+    }
+
+    /// Decode the text of a string segment between the given offsets.
+    fn decode_text(&mut self, start: usize, end: usize) -> String {
+        let raw = &self.input[start..end];
+        if let Err(error) = escape::validate_escapes(raw, start) {
+            self.errors.push(error);
+        }
+
+        unescape(raw)
+    }
+
+    fn lex_block_comment(&mut self) {
+        let (len, result) =
+            block_comment::lex_block_comment(&self.input[self.position..], self.position);
+
+        match result {
+            Ok(token) => self.tokens.push(token),
+            Err(error) => self.errors.push(error),
+        }
+
+        self.position += len;
+    }
+
+    fn lex_regex(&mut self) {
+        let (len, result) = regex_literal::lex_regex(&self.input[self.position..], self.position);
+
+        match result {
+            Ok(token) => self.tokens.push(token),
+            Err(error) => self.errors.push(error),
+        }
 
-        Ok(self.tokens)
+        self.position += len;
+    }
+
+    fn lex_number(&mut self) {
+        // a number after a `.` is a tuple index, so `t.0.1` contains the indices 0 and 1 (instead
+        // of the float `0.1`)
+        let allow_float = !matches!(self.tokens.last(), Some(Token::Dot { .. }));
+        let (len, result) =
+            number::lex_number(&self.input[self.position..], self.position, allow_float);
+
+        match result {
+            Ok(token) => self.tokens.push(token),
+            Err(error) => {
+                self.errors.push(error);
+                self.tokens.push(Token::InvalidNumber {
+                    value: self.input[self.position..self.position + len].to_string(),
+                    position: Position::new(self.position, len),
+                });
+            }
+        }
+
+        self.position += len;
     }
 }
 
@@ -84,6 +419,19 @@ impl<'a> Lexer<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_input_len() {
+        assert_eq!(Ok(()), check_input_len(Position::MAX_OFFSET));
+
+        let Err(error) = check_input_len(Position::MAX_OFFSET + 1) else {
+            panic!("expected error for too large input");
+        };
+        assert_eq!(
+            "1:1: input of 4294967296 bytes is too large (at most 4 GiB are supported)",
+            error.to_string()
+        );
+    }
+
     #[test]
     fn test_lex_alphabetic_id() {
         let lexer = Lexer::new("letter");
@@ -91,7 +439,7 @@ mod tests {
         assert_eq!(
             Ok(vec![Token::Id {
                 value: "letter".into(),
-                position: 0
+                position: Position::default()
             }]),
             lexer.lex()
         )
@@ -104,7 +452,7 @@ mod tests {
         assert_eq!(
             Ok(vec![Token::Integer {
                 value: 1337,
-                position: 0
+                position: Position::default()
             }]),
             lexer.lex()
         )
@@ -116,11 +464,21 @@ mod tests {
 
         assert_eq!(
             Ok(vec![
-                Token::FnKeyword { position: 0 },
-                Token::LParen { position: 0 },
-                Token::RParen { position: 0 },
-                Token::LBrace { position: 0 },
-                Token::RBrace { position: 0 }
+                Token::FnKeyword {
+                    position: Position::default()
+                },
+                Token::LParen {
+                    position: Position::default()
+                },
+                Token::RParen {
+                    position: Position::default()
+                },
+                Token::LBrace {
+                    position: Position::default()
+                },
+                Token::RBrace {
+                    position: Position::default()
+                }
             ]),
             lexer.lex()
         );
@@ -132,19 +490,535 @@ mod tests {
 
         assert_eq!(
             Ok(vec![
-                Token::Let { position: 0 },
+                Token::Let {
+                    position: Position::default()
+                },
                 Token::Id {
                     value: "foo".into(),
-                    position: 0
+                    position: Position::default()
+                },
+                Token::Assign {
+                    position: Position::default()
                 },
-                Token::Assign { position: 0 },
                 Token::Integer {
                     value: 42,
-                    position: 0
+                    position: Position::default()
+                },
+                Token::Semicolon {
+                    position: Position::default()
+                }
+            ]),
+            lexer.lex()
+        );
+    }
+
+    #[test]
+    fn test_lex_mut() {
+        let lexer = Lexer::new("let mut mutable");
+
+        assert_eq!(
+            Ok(vec![
+                Token::Let {
+                    position: Position::default()
+                },
+                Token::Mut {
+                    position: Position::default()
+                },
+                Token::Id {
+                    value: "mutable".into(),
+                    position: Position::default()
                 },
-                Token::Semicolon { position: 0 }
             ]),
             lexer.lex()
+        )
+    }
+
+    #[test]
+    fn test_lex_compound_assignment() {
+        let lexer = Lexer::new("x /= 2 %= 3");
+
+        assert!(matches!(
+            lexer.lex().as_deref(),
+            Ok([
+                Token::Id { .. },
+                Token::SlashAssign { .. },
+                Token::Integer { value: 2, .. },
+                Token::PercentAssign { .. },
+                Token::Integer { value: 3, .. },
+            ])
+        ));
+    }
+
+    #[test]
+    fn test_lex_longest_terminal() {
+        let lexer = Lexer::new("a == b::c => d");
+
+        assert_eq!(
+            Ok(vec![
+                Token::Id {
+                    value: "a".into(),
+                    position: Position::default()
+                },
+                Token::Equal {
+                    position: Position::default()
+                },
+                Token::Id {
+                    value: "b".into(),
+                    position: Position::default()
+                },
+                Token::DoubleColon {
+                    position: Position::default()
+                },
+                Token::Id {
+                    value: "c".into(),
+                    position: Position::default()
+                },
+                Token::BigRightArrow {
+                    position: Position::default()
+                },
+                Token::Id {
+                    value: "d".into(),
+                    position: Position::default()
+                }
+            ]),
+            lexer.lex()
+        );
+    }
+
+    #[test]
+    fn test_lex_literal_longer_than_terminal() {
+        let tokens = Lexer::new("iffy if 1.5").lex().expect("should lex");
+
+        let [Token::Id { value, .. }, Token::IfKeyword { .. }, Token::FloatingPoint { value: float, .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("iffy", value.as_str());
+        assert_eq!(1.5, *float);
+    }
+
+    #[test]
+    fn test_lex_positions() {
+        let tokens = Lexer::new("let foo = 42;").lex().expect("should lex");
+
+        assert_eq!(
+            vec![
+                Position::new(0, 3),
+                Position::new(4, 3),
+                Position::new(8, 1),
+                Position::new(10, 2),
+                Position::new(12, 1)
+            ],
+            tokens.iter().map(Token::position).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_lex_match_tokens() {
+        let tokens = Lexer::new(r#"match x { 1..=9 => "a\"b", 'c'..'d' => 0 }"#)
+            .lex()
+            .expect("should lex");
+
+        let [Token::MatchKeyword { .. }, Token::Id { .. }, Token::LBrace { .. }, Token::Integer { .. }, Token::DotDotEqual { .. }, Token::Integer { .. }, Token::BigRightArrow { .. }, Token::StringLiteral { value: string, .. }, Token::Comma { .. }, Token::CharLiteral { value: char, .. }, Token::DotDot { .. }, Token::CharLiteral { .. }, Token::BigRightArrow { .. }, Token::Integer { .. }, Token::RBrace { .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("a\"b", string);
+        assert_eq!("c", char);
+    }
+
+    #[test]
+    fn test_lex_string_with_spaces() {
+        let tokens = Lexer::new(r#"let a = "hello  world";"#)
+            .lex()
+            .expect("should lex");
+
+        let [_, _, _, Token::StringLiteral { value, position }, Token::Semicolon { .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("hello  world", value);
+        assert_eq!(Position::new(8, 14), *position);
+    }
+
+    #[test]
+    fn test_lex_string_escapes() {
+        let tokens = Lexer::new(r#""a\n\t\\\"\u{1F600}""#)
+            .lex()
+            .expect("should lex");
+
+        let [Token::StringLiteral { value, position }] = tokens.as_slice() else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("a\n\t\\\"\u{1F600}", value);
+        assert_eq!(Position::new(0, 20), *position);
+    }
+
+    #[test]
+    fn test_lex_invalid_unicode_escape() {
+        let error = Lexer::new(r#"let a = "\u{110000}";"#)
+            .lex()
+            .expect_err("should fail");
+
+        assert_eq!(Position::new(9, 10), error.position);
+        assert!(error.message.contains("not a valid code point"));
+    }
+
+    #[test]
+    fn test_lex_format_string() {
+        let tokens = Lexer::new(r#""hello {name}, you are {age + 1}\n""#)
+            .lex()
+            .expect("should lex");
+
+        assert_eq!(
+            vec![
+                Token::FormatStringStart {
+                    value: "hello ".into(),
+                    position: Position::new(0, 8)
+                },
+                Token::Id {
+                    value: "name".into(),
+                    position: Position::new(8, 4)
+                },
+                Token::FormatStringMiddle {
+                    value: ", you are ".into(),
+                    position: Position::new(12, 12)
+                },
+                Token::Id {
+                    value: "age".into(),
+                    position: Position::new(24, 3)
+                },
+                Token::Plus {
+                    position: Position::new(28, 1)
+                },
+                Token::Integer {
+                    value: 1,
+                    position: Position::new(30, 1)
+                },
+                Token::FormatStringEnd {
+                    value: "\n".into(),
+                    position: Position::new(31, 4)
+                },
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn test_lex_nested_format_string() {
+        let tokens = Lexer::new(r#""a {"b {c}"} \{d\}""#)
+            .lex()
+            .expect("should lex");
+
+        let [Token::FormatStringStart { value: outer, .. }, Token::FormatStringStart { value: inner, .. }, Token::Id { .. }, Token::FormatStringEnd { .. }, Token::FormatStringEnd { value: rest, .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("a ", outer);
+        assert_eq!("b ", inner);
+        assert_eq!(" {d}", rest);
+    }
+
+    #[test]
+    fn test_lex_asm_template_is_not_interpolated() {
+        let tokens = Lexer::new(r#"asm { "mov {0}, {1}" }"#)
+            .lex()
+            .expect("should lex");
+
+        let [_, _, Token::StringLiteral { value, .. }, _] = tokens.as_slice() else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("mov {0}, {1}", value);
+    }
+
+    #[test]
+    fn test_lex_unclosed_interpolation() {
+        let (tokens, errors) = Lexer::new(r#"let a = "b {c";"#).lex_recovering();
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [_, _, _, Token::StringLiteral { value, .. }, Token::Semicolon { .. }] if value == "b {c"
+        ));
+        assert_eq!(1, errors.len());
+        assert_eq!(Position::new(11, 1), errors[0].position);
+        assert!(errors[0].message.contains("unclosed `{`"));
+    }
+
+    #[test]
+    fn test_lex_slashes() {
+        let tokens = Lexer::new("a / b // c\na /* c */ b /= /d/")
+            .lex()
+            .expect("should lex");
+
+        let [Token::Id { .. }, Token::Slash { .. }, Token::Id { .. }, Token::Comment { value: line, .. }, Token::Id { .. }, Token::Comment { value: block, .. }, Token::Id { .. }, Token::SlashAssign { .. }, Token::RegexLiteral { value: regex, .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("// c", line);
+        assert_eq!("/* c */", block);
+        assert_eq!("/d/", regex);
+    }
+
+    #[test]
+    fn test_lex_shifts() {
+        let tokens = Lexer::new("a << 2 >> b").lex().expect("should lex");
+
+        let [Token::Id { .. }, Token::ShiftLeft { .. }, Token::Integer { .. }, Token::ShiftRight { .. }, Token::Id { .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+    }
+
+    #[test]
+    fn test_lex_doc_comment() {
+        let tokens = Lexer::new("/// Adds.\n//// ---\nfn")
+            .lex()
+            .expect("should lex");
+
+        assert_eq!(
+            vec![
+                Token::DocComment {
+                    value: "Adds.".into(),
+                    position: Position::new(0, 9)
+                },
+                Token::Comment {
+                    value: "//// ---".into(),
+                    position: Position::new(10, 8)
+                },
+                Token::FnKeyword {
+                    position: Position::new(19, 2)
+                },
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn test_lex_block_comment() {
+        let tokens = Lexer::new("a /* b /* c */ */ / d")
+            .lex()
+            .expect("should lex");
+
+        let [Token::Id { .. }, Token::Comment { value, .. }, Token::Slash { .. }, Token::Id { .. }] =
+            tokens.as_slice()
+        else {
+            panic!("unexpected tokens {tokens:?}");
+        };
+        assert_eq!("/* b /* c */ */", value);
+    }
+
+    #[test]
+    fn test_lex_unterminated_block_comment() {
+        let error = Lexer::new("let a = 1; /* /* */")
+            .lex()
+            .expect_err("should fail");
+
+        assert_eq!(Position::new(11, 2), error.position);
+    }
+
+    #[test]
+    fn test_lex_unexpected_character() {
+        let error = Lexer::new("let a = 1;\nlet b = a € 2;")
+            .lex()
+            .expect_err("should fail");
+
+        assert_eq!(
+            LexError {
+                message: "unexpected character '€'".into(),
+                position: Position::new(21, 3),
+                character: '€',
+                line: 2,
+                column: 11,
+            },
+            error
+        );
+    }
+
+    #[test]
+    fn test_lex_never_panics() {
+        let source =
+            "let s = \"a\\u{41} {x / 2}\"; /* é /* */ */ 'ü' 0x1F /\\d+/ € // pesca:off\n\\";
+
+        // every prefix ends the input at a (possibly) unexpected place
+        for (index, _) in source.char_indices() {
+            let (_, errors) = Lexer::new(&source[..index]).lex_recovering();
+
+            for error in errors {
+                assert!(error.line >= 1 && error.column >= 1, "{error:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_lex_unterminated_string() {
+        let (tokens, errors) = Lexer::new("let a = \"foo;").lex_recovering();
+
+        assert_eq!(
+            vec![LexError {
+                message: "unterminated string literal".into(),
+                position: Position::new(8, 1),
+                character: '"',
+                line: 1,
+                column: 9,
+            }],
+            errors
+        );
+        // lexing continues after the quote
+        assert!(matches!(
+            tokens.as_slice(),
+            [
+                Token::Let { .. },
+                Token::Id { .. },
+                Token::Assign { .. },
+                Token::Id { .. },
+                Token::Semicolon { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_lex_unterminated_regex_with_trailing_backslash() {
+        let error = Lexer::new("let a = /\\").lex().expect_err("should fail");
+
+        assert_eq!("unterminated regex literal", error.message);
+        assert_eq!(Position::new(8, 2), error.position);
+    }
+
+    #[test]
+    fn test_lex_invalid_escape() {
+        let error = Lexer::new(r#"let a = "foo\qbar";"#)
+            .lex()
+            .expect_err("should fail");
+
+        assert_eq!(Position::new(12, 2), error.position);
+    }
+
+    #[test]
+    fn test_lex_unicode_char_literal() {
+        let tokens = Lexer::new(r"'\u{41}'").lex().expect("should lex");
+
+        assert!(matches!(tokens.as_slice(), [Token::CharLiteral { value, .. }] if value == "A"));
+    }
+
+    #[test]
+    fn test_lex_recovers_from_invalid_number() {
+        let (tokens, errors) = Lexer::new("let a = 0b12 + 0xZZ;").lex_recovering();
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [
+                Token::Let { .. },
+                Token::Id { .. },
+                Token::Assign { .. },
+                Token::InvalidNumber { .. },
+                Token::Plus { .. },
+                Token::InvalidNumber { .. },
+                Token::Semicolon { .. }
+            ]
+        ));
+        assert_eq!(
+            vec![Position::new(11, 1), Position::new(17, 2)],
+            errors
+                .iter()
+                .map(|error| error.position)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_lex_regex_or_division() {
+        let tokens = Lexer::new("let a = /[0-9]+/g; let b = a / 2;")
+            .lex()
+            .expect("should work");
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [
+                Token::Let { .. },
+                Token::Id { .. },
+                Token::Assign { .. },
+                Token::RegexLiteral { .. },
+                Token::Semicolon { .. },
+                Token::Let { .. },
+                Token::Id { .. },
+                Token::Assign { .. },
+                Token::Id { .. },
+                Token::Slash { .. },
+                Token::Integer { .. },
+                Token::Semicolon { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_lex_tuple_indices() {
+        let tokens = Lexer::new("t.0.1 + 0.1").lex().expect("should work");
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [
+                Token::Id { .. },
+                Token::Dot { .. },
+                Token::Integer { value: 0, .. },
+                Token::Dot { .. },
+                Token::Integer { value: 1, .. },
+                Token::Plus { .. },
+                Token::FloatingPoint { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_lex_bool_and_logical_operators() {
+        let tokens = Lexer::new("true && false || trueish & x")
+            .lex()
+            .expect("should work");
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [
+                Token::TrueKeyword { .. },
+                Token::DoubleAmpersand { .. },
+                Token::FalseKeyword { .. },
+                Token::DoublePipe { .. },
+                Token::Id { .. },
+                Token::Ampersand { .. },
+                Token::Id { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_lex_directive() {
+        let tokens = Lexer::new("// pesca:allow(unused_variable)\nlet a = 1; // a comment")
+            .lex()
+            .expect("should work");
+
+        let [Token::Directive { value, .. }, .., Token::Comment { .. }] = tokens.as_slice() else {
+            panic!("expected directive, got {tokens:?}");
+        };
+        assert!(value.allows("unused_variable"));
+    }
+
+    #[test]
+    fn test_lex_malformed_directive() {
+        let result = Lexer::new("// pesca:allow(").lex();
+
+        assert_eq!(
+            Err(LexError {
+                message: "malformed directive `// pesca:allow(`".into(),
+                position: Position::new(0, 15),
+                character: '/',
+                line: 1,
+                column: 1,
+            }),
+            result
         );
     }
 }
@@ -0,0 +1,385 @@
+use super::{LexError, Position, Token};
+
+/// Lex a number literal at the start of `input` (which has to start with a digit). Besides
+/// decimal integers and floating point numbers (optionally with an exponent, e.g., `1.5e-3`),
+/// hexadecimal (`0x`), octal (`0o`) and binary (`0b`) integers are supported. Digits may be
+/// separated by single underscores (e.g., `1_000`).
+///
+/// If `allow_float` is false, only integers are lexed (e.g., for the tuple indices in `t.0.1`).
+///
+/// Returns the length of the literal (in bytes) and either the token or an error pointing at the
+/// offending digits. Even a malformed literal is consumed completely, so lexing can continue after
+/// it.
+pub(crate) fn lex_number(
+    input: &str,
+    offset: usize,
+    allow_float: bool,
+) -> (usize, Result<Token, LexError>) {
+    let bytes = input.as_bytes();
+
+    let (radix, prefix) = match bytes {
+        [b'0', b'x', ..] => (16, 2),
+        [b'0', b'o', ..] => (8, 2),
+        [b'0', b'b', ..] => (2, 2),
+        _ => (10, 0),
+    };
+
+    // fractions and exponents are only possible for decimal numbers
+    let float = allow_float && radix == 10;
+    let digits_end = prefix + digits_len(&input[prefix..], float);
+
+    // `1..2` is a range, not a float, just like `1.foo` is an access on an integer
+    let has_fraction = float
+        && bytes.get(digits_end) == Some(&b'.')
+        && bytes.get(digits_end + 1).is_some_and(u8::is_ascii_digit);
+    let fraction_end = if has_fraction {
+        digits_end + 1 + digits_len(&input[digits_end + 1..], float)
+    } else {
+        digits_end
+    };
+
+    let exponent_digits = if float {
+        exponent_sign_len(&input[fraction_end..]).map(|sign| fraction_end + 1 + sign)
+    } else {
+        None
+    };
+    let len = match exponent_digits {
+        Some(start) => start + alphanumeric_len(&input[start..]),
+        None => fraction_end,
+    };
+    let is_float = has_fraction || exponent_digits.is_some();
+
+    let lexeme = &input[..len];
+    let position = Position::new(offset, len);
+    let error = |start: usize, end: usize, message: String| {
+        LexError::new(message, Position::new(offset + start, end - start))
+    };
+
+    if prefix > 0 && digits_end == prefix {
+        return (
+            len,
+            Err(error(
+                0,
+                prefix,
+                format!(
+                    "expected {} digits after `{}`",
+                    radix_name(radix),
+                    &lexeme[..2]
+                ),
+            )),
+        );
+    }
+
+    if let Err(error) = validate_digits(&input[prefix..digits_end], radix, offset + prefix) {
+        return (len, Err(error));
+    }
+
+    if is_float {
+        if has_fraction {
+            if let Err(error) = validate_digits(
+                &input[digits_end + 1..fraction_end],
+                10,
+                offset + digits_end + 1,
+            ) {
+                return (len, Err(error));
+            }
+        }
+
+        if let Some(start) = exponent_digits {
+            if let Err(error) = validate_digits(&input[start..len], 10, offset + start) {
+                return (len, Err(error));
+            }
+        }
+
+        let value = lexeme
+            .replace('_', "")
+            .parse()
+            .expect("digits are validated");
+        return (len, Ok(Token::FloatingPoint { value, position }));
+    }
+
+    let digits = input[prefix..digits_end].replace('_', "");
+    match u64::from_str_radix(&digits, radix) {
+        Ok(value) => (len, Ok(Token::Integer { value, position })),
+        Err(_) => (
+            len,
+            Err(error(
+                0,
+                len,
+                format!("integer literal `{lexeme}` is too large"),
+            )),
+        ),
+    }
+}
+
+fn alphanumeric_len(input: &str) -> usize {
+    input
+        .bytes()
+        .take_while(|byte| byte.is_ascii_alphanumeric() || *byte == b'_')
+        .count()
+}
+
+/// Length of the alphanumeric run at the start of `input`. For floating point numbers, the run
+/// ends before an exponent (e.g., the `e9` in `1e9`).
+fn digits_len(input: &str, float: bool) -> usize {
+    let len = alphanumeric_len(input);
+
+    if !float {
+        return len;
+    }
+
+    input[..len]
+        .bytes()
+        .enumerate()
+        .find(|(index, byte)| {
+            matches!(byte, b'e' | b'E') && exponent_sign_len(&input[*index..]).is_some()
+        })
+        .map_or(len, |(index, _)| index)
+}
+
+/// If `input` starts with an exponent (`e` or `E`, an optional sign and a digit), return the
+/// length of the sign.
+fn exponent_sign_len(input: &str) -> Option<usize> {
+    match input.as_bytes() {
+        [b'e' | b'E', b'+' | b'-', digit, ..] if digit.is_ascii_digit() => Some(1),
+        [b'e' | b'E', digit, ..] if digit.is_ascii_digit() => Some(0),
+        _ => None,
+    }
+}
+
+fn radix_name(radix: u32) -> &'static str {
+    match radix {
+        16 => "hexadecimal",
+        8 => "octal",
+        2 => "binary",
+        _ => "decimal",
+    }
+}
+
+/// Check that `digits` only consists of digits of the given radix, which are separated by single
+/// underscores. The error spans exactly the offending characters.
+fn validate_digits(digits: &str, radix: u32, offset: usize) -> Result<(), LexError> {
+    let bytes = digits.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+
+        if bytes[index] == b'_' {
+            while index < bytes.len() && bytes[index] == b'_' {
+                index += 1;
+            }
+
+            let message = if start == 0 {
+                "numeric literal can not start with `_`"
+            } else if index == bytes.len() {
+                "numeric literal can not end with `_`"
+            } else if index - start > 1 {
+                "digits can only be separated by a single `_`"
+            } else {
+                continue;
+            };
+
+            return Err(LexError::new(
+                message,
+                Position::new(offset + start, index - start),
+            ));
+        }
+
+        if (bytes[index] as char).is_digit(radix) {
+            index += 1;
+            continue;
+        }
+
+        while index < bytes.len() && bytes[index] != b'_' && !(bytes[index] as char).is_digit(radix)
+        {
+            index += 1;
+        }
+
+        return Err(LexError::new(
+            format!(
+                "invalid digit{} `{}` in {} literal",
+                if index - start > 1 { "s" } else { "" },
+                &digits[start..index],
+                radix_name(radix)
+            ),
+            Position::new(offset + start, index - start),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(input: &str) -> (usize, Result<Token, LexError>) {
+        lex_number(input, 0, true)
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert!(matches!(
+            lex("1_000;"),
+            (5, Ok(Token::Integer { value: 1000, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_radix() {
+        assert!(matches!(
+            lex("0xFf"),
+            (4, Ok(Token::Integer { value: 255, .. }))
+        ));
+        assert!(matches!(
+            lex("0o17"),
+            (4, Ok(Token::Integer { value: 15, .. }))
+        ));
+        assert!(matches!(
+            lex("0o755"),
+            (5, Ok(Token::Integer { value: 0o755, .. }))
+        ));
+        assert!(matches!(
+            lex("0b1010_1010"),
+            (11, Ok(Token::Integer { value: 170, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_float() {
+        let (len, Ok(Token::FloatingPoint { value, .. })) = lex("1_0.2_5") else {
+            panic!("expected float");
+        };
+        assert_eq!(7, len);
+        assert_eq!(10.25, value);
+    }
+
+    #[test]
+    fn test_exponent() {
+        assert!(matches!(
+            lex("1e9"),
+            (3, Ok(Token::FloatingPoint { value, .. })) if value == 1e9
+        ));
+        assert!(matches!(
+            lex("1.5E-3;"),
+            (6, Ok(Token::FloatingPoint { value, .. })) if value == 1.5e-3
+        ));
+        assert!(matches!(
+            lex("2e+1_0"),
+            (6, Ok(Token::FloatingPoint { value, .. })) if value == 2e10
+        ));
+    }
+
+    #[test]
+    fn test_missing_exponent_digits() {
+        let (len, Err(error)) = lex("1e+") else {
+            panic!("expected error");
+        };
+        assert_eq!(2, len);
+        assert_eq!("invalid digit `e` in decimal literal", error.message);
+    }
+
+    #[test]
+    fn test_access_on_integer_is_not_a_float() {
+        assert!(matches!(
+            lex("1.foo"),
+            (1, Ok(Token::Integer { value: 1, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_integer_only() {
+        assert!(matches!(
+            lex_number("0.1", 0, false),
+            (1, Ok(Token::Integer { value: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_range_is_not_a_float() {
+        assert!(matches!(
+            lex("1..2"),
+            (1, Ok(Token::Integer { value: 1, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_hex_digits() {
+        let (len, Err(error)) = lex("0xZZ + 1") else {
+            panic!("expected error");
+        };
+        assert_eq!(4, len);
+        assert_eq!(Position::new(2, 2), error.position);
+        assert_eq!("invalid digits `ZZ` in hexadecimal literal", error.message);
+    }
+
+    #[test]
+    fn test_invalid_octal_digit() {
+        let (len, Err(error)) = lex("0o755_8") else {
+            panic!("expected error");
+        };
+        assert_eq!(7, len);
+        assert_eq!(Position::new(6, 1), error.position);
+        assert_eq!("invalid digit `8` in octal literal", error.message);
+    }
+
+    #[test]
+    fn test_invalid_binary_digit() {
+        let (_, Err(error)) = lex("0b12") else {
+            panic!("expected error");
+        };
+        assert_eq!(Position::new(3, 1), error.position);
+        assert_eq!("invalid digit `2` in binary literal", error.message);
+    }
+
+    #[test]
+    fn test_double_underscore() {
+        let (_, Err(error)) = lex("1__0") else {
+            panic!("expected error");
+        };
+        assert_eq!(Position::new(1, 2), error.position);
+    }
+
+    #[test]
+    fn test_trailing_underscore() {
+        let (_, Err(error)) = lex("10_") else {
+            panic!("expected error");
+        };
+        assert_eq!(Position::new(2, 1), error.position);
+    }
+
+    #[test]
+    fn test_separated_hex_digits() {
+        assert!(matches!(
+            lex("0xFF_FF"),
+            (7, Ok(Token::Integer { value: 0xFFFF, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_underscore_before_fraction() {
+        let (len, Err(error)) = lex("1_.5") else {
+            panic!("expected error");
+        };
+        assert_eq!(4, len);
+        assert_eq!(Position::new(1, 1), error.position);
+        assert_eq!("numeric literal can not end with `_`", error.message);
+    }
+
+    #[test]
+    fn test_missing_digits_after_prefix() {
+        let (_, Err(error)) = lex("0x") else {
+            panic!("expected error");
+        };
+        assert_eq!(Position::new(0, 2), error.position);
+    }
+
+    #[test]
+    fn test_too_large() {
+        let (_, Err(error)) = lex("0x1_0000_0000_0000_0000") else {
+            panic!("expected error");
+        };
+        assert_eq!(Position::new(0, 23), error.position);
+    }
+}
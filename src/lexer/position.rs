@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+/// Location of a token within the source: its byte offset and its length (in bytes).
+///
+/// Line and column are not stored, but computed on demand (see `SourceFile::line_col`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Position {
+    pub offset: u32,
+    pub length: u32,
+}
+
+impl Position {
+    /// Offsets are stored as `u32`, so positions can only cover inputs of up to 4 GiB. Larger
+    /// inputs are rejected by the lexer (see `check_input_len`).
+    pub const MAX_OFFSET: usize = u32::MAX as usize;
+
+    pub fn new(offset: usize, length: usize) -> Self {
+        let fits = |value: usize| {
+            u32::try_from(value).expect("positions within an input of at most 4 GiB fit into u32")
+        };
+
+        // the end has to fit as well, so `end()` can not overflow
+        fits(offset + length);
+
+        Self {
+            offset: fits(offset),
+            length: fits(length),
+        }
+    }
+
+    pub fn end(&self) -> u32 {
+        self.offset + self.length
+    }
+
+    /// The byte range covered by this position.
+    pub fn span(&self) -> Span {
+        Span {
+            start: self.offset as usize,
+            end: self.end() as usize,
+        }
+    }
+}
+
+/// A range of byte offsets (`start..end`) into the original source, e.g., to slice the exact
+/// source text of a token or of everything consumed while parsing an AST node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The smallest span covering this and the other span.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// The source text covered by this span.
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Position, Span};
+
+    #[test]
+    fn test_span() {
+        let source = "let answer = 42;";
+        let span = Position::new(4, 6).span();
+
+        assert_eq!(Span { start: 4, end: 10 }, span);
+        assert_eq!("answer", span.text(source));
+        assert_eq!(
+            "answer = 42",
+            span.to(Position::new(13, 2).span()).text(source)
+        );
+    }
+
+    #[test]
+    fn test_position_at_max_offset() {
+        let position = Position::new(Position::MAX_OFFSET - 2, 2);
+
+        assert_eq!(u32::MAX, position.end());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_position_beyond_max_offset() {
+        Position::new(Position::MAX_OFFSET, 1);
+    }
+}
@@ -0,0 +1,113 @@
+use super::{LexError, Position, Token};
+
+/// Lex a regex literal (`/pattern/flags`) at the start of `input` (which has to start with `/`).
+/// Within the pattern, `/` has to be escaped (`\/`) unless it is part of a character class
+/// (`[/]`). The flags are all letters directly following the closing `/`.
+///
+/// Returns the length of the literal (in bytes) and either the token or an error. An unterminated
+/// literal is consumed up to the end of the line, so lexing can continue after it.
+pub(crate) fn lex_regex(input: &str, offset: usize) -> (usize, Result<Token, LexError>) {
+    let bytes = input.as_bytes();
+
+    let mut index = 1;
+    let mut in_class = false;
+
+    loop {
+        match bytes.get(index) {
+            None | Some(b'\n') => {
+                return (
+                    index,
+                    Err(LexError::new(
+                        "unterminated regex literal",
+                        Position::new(offset, index),
+                    )),
+                );
+            }
+            // an escape never reaches past the end of the line (or the input)
+            Some(b'\\') => match bytes.get(index + 1) {
+                None | Some(b'\n') => index += 1,
+                Some(_) => index += 2,
+            },
+            Some(b'[') => {
+                in_class = true;
+                index += 1;
+            }
+            Some(b']') => {
+                in_class = false;
+                index += 1;
+            }
+            Some(b'/') if !in_class => break,
+            Some(_) => index += 1,
+        }
+    }
+
+    // skip the closing `/`
+    index += 1;
+
+    let len = index
+        + input[index..]
+            .bytes()
+            .take_while(u8::is_ascii_alphabetic)
+            .count();
+
+    (
+        len,
+        Ok(Token::RegexLiteral {
+            value: input[..len].to_string(),
+            position: Position::new(offset, len),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex() {
+        let (len, Ok(Token::RegexLiteral { value, .. })) = lex_regex("/a+b/gi;", 0) else {
+            panic!("expected regex");
+        };
+        assert_eq!(7, len);
+        assert_eq!("/a+b/gi", value);
+    }
+
+    #[test]
+    fn test_escaped_slash() {
+        let (_, Ok(Token::RegexLiteral { value, .. })) = lex_regex("/a\\/b/ + c", 0) else {
+            panic!("expected regex");
+        };
+        assert_eq!("/a\\/b/", value);
+    }
+
+    #[test]
+    fn test_slash_in_class() {
+        let (_, Ok(Token::RegexLiteral { value, .. })) = lex_regex("/[/]+/", 0) else {
+            panic!("expected regex");
+        };
+        assert_eq!("/[/]+/", value);
+    }
+
+    #[test]
+    fn test_unterminated() {
+        let (len, Err(error)) = lex_regex("/abc\nlet", 3) else {
+            panic!("expected error");
+        };
+        assert_eq!(4, len);
+        assert_eq!(Position::new(3, 4), error.position);
+    }
+
+    #[test]
+    fn test_unterminated_with_trailing_backslash() {
+        let (len, Err(error)) = lex_regex("/a\\", 8) else {
+            panic!("expected error");
+        };
+        assert_eq!(3, len);
+        assert_eq!(Position::new(8, 3), error.position);
+
+        let (len, Err(_)) = lex_regex("/a\\\nlet", 0) else {
+            panic!("expected error");
+        };
+        assert_eq!(3, len);
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    fmt::{Debug, Display},
+    hash::{BuildHasher, Hash, Hasher, RandomState},
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock, Weak},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Handle of an interned string (e.g., the name of an identifier).
+///
+/// There is at most one live allocation per distinct string (see [`Symbol::new`]), so cloning a
+/// symbol is cheap and comparing two symbols is a pointer comparison. The text is freed once the
+/// last symbol referring to it is dropped.
+#[derive(Clone)]
+pub struct Symbol(Arc<str>);
+
+/// The process wide registry of all live symbols. It only holds weak references, so it does not
+/// keep any text alive; the entries of dropped symbols are swept once they make up half of it.
+#[derive(Default)]
+struct Registry {
+    hasher: RandomState,
+    symbols: HashMap<u64, Vec<Weak<str>>>,
+    len: usize,
+    sweep_at: usize,
+}
+
+/// The registry is swept no earlier than after this many entries.
+const MIN_SWEEP_AT: usize = 1024;
+
+impl Registry {
+    fn get() -> &'static Mutex<Registry> {
+        static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+        REGISTRY.get_or_init(Default::default)
+    }
+
+    fn symbol(&mut self, value: &str) -> Symbol {
+        let hash = self.hasher.hash_one(value);
+
+        let entries = self.symbols.entry(hash).or_default();
+        if let Some(symbol) = entries
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|symbol| &**symbol == value)
+        {
+            return Symbol(symbol);
+        }
+
+        let symbol: Arc<str> = value.into();
+        entries.push(Arc::downgrade(&symbol));
+        self.len += 1;
+
+        if self.len >= self.sweep_at.max(MIN_SWEEP_AT) {
+            self.sweep();
+        }
+
+        Symbol(symbol)
+    }
+
+    fn sweep(&mut self) {
+        self.symbols.retain(|_, entries| {
+            entries.retain(|entry| entry.strong_count() > 0);
+            !entries.is_empty()
+        });
+        self.len = self.symbols.values().map(Vec::len).sum();
+        self.sweep_at = self.len * 2;
+    }
+}
+
+/// Interner of a single lexing (or parsing) session. It caches the symbols handed out so far, so
+/// only the first occurrence of every name has to look it up in the process wide registry.
+#[derive(Debug, Default)]
+pub struct Interner {
+    symbols: HashSet<Symbol>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, value: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(value) {
+            return symbol.clone();
+        }
+
+        let symbol = Symbol::new(value);
+        self.symbols.insert(symbol.clone());
+        symbol
+    }
+}
+
+impl Symbol {
+    /// The symbol of the given string, which shares its allocation with all other live symbols of
+    /// the same string. Prefer [`Interner::intern`] when creating many symbols.
+    pub fn new(value: &str) -> Symbol {
+        Registry::get()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .symbol(value)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        // there is only a single allocation per distinct string
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // consistent with `Borrow<str>`, so an interner can be queried with a plain `&str`
+        self.as_str().hash(state)
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Symbol::new(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Symbol::new(&value)
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Symbol::new(s))
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::lexer::{Lexer, Token};
+
+    use super::{Interner, Registry, Symbol, MIN_SWEEP_AT};
+
+    #[test]
+    fn test_intern_same_string() {
+        let mut interner = Interner::new();
+        let foo = interner.intern("foo");
+
+        assert!(Arc::ptr_eq(&foo.0, &interner.intern("foo").0));
+        assert_ne!(foo, interner.intern("bar"));
+    }
+
+    #[test]
+    fn test_symbols_of_different_interners_share_allocation() {
+        let foo = Interner::new().intern("foo");
+
+        assert!(Arc::ptr_eq(&foo.0, &Interner::new().intern("foo").0));
+        assert!(Arc::ptr_eq(&foo.0, &Symbol::new("foo").0));
+        assert_eq!(foo, Symbol::from(String::from("foo")));
+        assert_ne!(foo, Symbol::new("bar"));
+    }
+
+    #[test]
+    fn test_symbols_are_freed() {
+        let mut interner = Interner::new();
+        let symbol = interner.intern("temporary");
+        let weak = Arc::downgrade(&symbol.0);
+
+        drop(interner);
+        assert!(weak.upgrade().is_some());
+
+        drop(symbol);
+        assert!(weak.upgrade().is_none());
+
+        // a new symbol of the same string gets a new allocation
+        let symbol = Symbol::new("temporary");
+        assert_eq!("temporary", symbol.as_str());
+        assert_eq!(symbol, Interner::new().intern("temporary"));
+    }
+
+    #[test]
+    fn test_sweep_dropped_symbols() {
+        let mut registry = Registry::default();
+        let kept = registry.symbol("kept");
+        for index in 0..MIN_SWEEP_AT {
+            registry.symbol(&format!("dropped_{index}"));
+        }
+
+        assert!(registry.len < MIN_SWEEP_AT);
+        assert_eq!(kept, registry.symbol("kept"));
+    }
+
+    #[test]
+    fn test_deserialize_shares_allocation() {
+        let symbol = Symbol::new("deserialized");
+        let encoded = bincode::serialize(&symbol).expect("should serialize");
+
+        let decoded: Symbol = bincode::deserialize(&encoded).expect("should deserialize");
+        assert!(Arc::ptr_eq(&symbol.0, &decoded.0));
+    }
+
+    #[test]
+    fn test_lexer_shares_symbols() {
+        let tokens = Lexer::new("let x = \"{x}\" + x;")
+            .lex()
+            .expect("should lex");
+        let symbols = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Id { value, .. } => Some(&value.0),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(3, symbols.len());
+        assert!(symbols.windows(2).all(|pair| Arc::ptr_eq(pair[0], pair[1])));
+    }
+
+    #[test]
+    fn test_as_str() {
+        let symbol = Symbol::new("some_identifier");
+
+        assert_eq!("some_identifier", symbol.as_str());
+        assert_eq!("some_identifier", symbol.to_string());
+        assert_eq!("\"some_identifier\"", format!("{symbol:?}"));
+    }
+
+    #[test]
+    fn test_share_between_threads() {
+        let symbol = Interner::new().intern("shared_between_threads");
+
+        let symbols = std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    let symbol = symbol.clone();
+                    scope.spawn(move || symbol)
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert!(symbols.iter().all(|other| Arc::ptr_eq(&symbol.0, &other.0)));
+    }
+}
@@ -1,12 +1,22 @@
 use pesca_parser_derive::{LooseEq, Token as ParseToken};
-use regex::{Match, Regex};
+use regex::Regex;
 
-type Position = usize;
+use super::{Directive, Interner, Position, Span, Symbol};
 
 #[derive(Debug, Clone, ParseToken, LooseEq)]
 pub enum Token {
     #[terminal("=")]
     Assign { position: Position },
+    #[terminal("+=")]
+    PlusAssign { position: Position },
+    #[terminal("-=")]
+    MinusAssign { position: Position },
+    #[terminal("*=")]
+    TimesAssign { position: Position },
+    #[terminal("/=")]
+    SlashAssign { position: Position },
+    #[terminal("%=")]
+    PercentAssign { position: Position },
     #[terminal("let")]
     Let { position: Position },
     #[terminal("const")]
@@ -14,21 +24,37 @@ pub enum Token {
     #[terminal("mut")]
     Mut { position: Position },
     #[literal("[a-zA-Z_][a-zA-Z0-9_]*")]
-    Id { value: String, position: Position },
+    Id { value: Symbol, position: Position },
     #[literal("[0-9]+")]
     Integer { value: u64, position: Position },
     #[literal("[0-9]+\\.[0-9]+")]
     FloatingPoint { value: f64, position: Position },
+    /// A malformed number literal (e.g., `0xZZ`), which was already reported by the lexer.
+    InvalidNumber { value: String, position: Position },
     #[terminal(";")]
     Semicolon { position: Position },
     #[literal("//.*")]
     Comment { value: String, position: Position },
+    /// A comment like `/// Adds two numbers.`, which documents the following item. Its value is
+    /// the text after the `///` (and a single space).
+    DocComment { value: String, position: Position },
+    /// A comment like `// pesca:off`, which is recognized by the lexer.
+    Directive {
+        value: Directive,
+        position: Position,
+    },
     #[terminal("+")]
     Plus { position: Position },
     #[terminal("-")]
     Minus { position: Position },
     #[terminal("*")]
     Times { position: Position },
+    #[terminal("**")]
+    DoubleTimes { position: Position },
+    #[terminal("/")]
+    Slash { position: Position },
+    #[terminal("%")]
+    Percent { position: Position },
     #[terminal("(")]
     LParen { position: Position },
     #[terminal(")")]
@@ -49,10 +75,20 @@ pub enum Token {
     ElseKeyword { position: Position },
     #[terminal("while")]
     WhileKeyword { position: Position },
+    #[terminal("loop")]
+    LoopKeyword { position: Position },
     #[terminal("return")]
     ReturnKeyword { position: Position },
+    #[terminal("yield")]
+    YieldKeyword { position: Position },
+    #[terminal("break")]
+    BreakKeyword { position: Position },
+    #[terminal("continue")]
+    ContinueKeyword { position: Position },
     #[terminal(":")]
     Colon { position: Position },
+    #[terminal("::")]
+    DoubleColon { position: Position },
     #[terminal(",")]
     Comma { position: Position },
     #[terminal(".")]
@@ -71,14 +107,128 @@ pub enum Token {
     LessThan { position: Position },
     #[terminal(">=")]
     GreaterOrEqual { position: Position },
-    #[terminal("<0")]
+    #[terminal(">>")]
+    ShiftRight { position: Position },
+    #[terminal("<<")]
+    ShiftLeft { position: Position },
+    #[terminal("<=")]
     LessOrEqual { position: Position },
     #[terminal("&")]
     Ampersand { position: Position },
+    #[terminal("&&")]
+    DoubleAmpersand { position: Position },
+    #[terminal("||")]
+    DoublePipe { position: Position },
+    #[terminal("|")]
+    Pipe { position: Position },
+    #[terminal("|>")]
+    PipeRightArrow { position: Position },
+    #[terminal("^")]
+    Caret { position: Position },
+    #[terminal("~")]
+    Tilde { position: Position },
+    #[terminal("true")]
+    TrueKeyword { position: Position },
+    #[terminal("false")]
+    FalseKeyword { position: Position },
     #[terminal("declare")]
     DeclareKeyword { position: Position },
+    #[terminal("extern")]
+    ExternKeyword { position: Position },
+    #[terminal("impl")]
+    ImplKeyword { position: Position },
+    #[terminal("trait")]
+    TraitKeyword { position: Position },
+    #[terminal("mod")]
+    ModKeyword { position: Position },
+    #[terminal("pub")]
+    PubKeyword { position: Position },
+    #[terminal("comptime")]
+    ComptimeKeyword { position: Position },
     #[terminal("struct")]
     StructKeyword { position: Position },
+    #[terminal("enum")]
+    EnumKeyword { position: Position },
+    #[terminal("use")]
+    UseKeyword { position: Position },
+    #[terminal("as")]
+    AsKeyword { position: Position },
     #[terminal("!")]
     ExclamationMark { position: Position },
+    #[terminal("?")]
+    QuestionMark { position: Position },
+    #[terminal("match")]
+    MatchKeyword { position: Position },
+    #[terminal("#if")]
+    HashIf { position: Position },
+    #[terminal("#else")]
+    HashElse { position: Position },
+    #[terminal("#[")]
+    HashBracket { position: Position },
+    #[terminal("@")]
+    At { position: Position },
+    #[terminal("..")]
+    DotDot { position: Position },
+    #[terminal("...")]
+    DotDotDot { position: Position },
+    #[terminal("..=")]
+    DotDotEqual { position: Position },
+    #[terminal("for")]
+    ForKeyword { position: Position },
+    #[terminal("in")]
+    InKeyword { position: Position },
+    #[literal("\"([^\"\\\\]|\\\\.)*\"")]
+    StringLiteral { value: String, position: Position },
+    #[literal("'([^'\\\\]|\\\\u\\{[^}']*\\}|\\\\.)'")]
+    CharLiteral { value: String, position: Position },
+    /// `/pattern/flags`, lexed by hand since it depends on the preceding token.
+    RegexLiteral { value: String, position: Position },
+    /// The (decoded) text of an interpolated string up to its first `{`, e.g., `"hello {`. Like
+    /// the following segments, it is lexed by hand.
+    FormatStringStart { value: String, position: Position },
+    /// The text between two interpolations, e.g., `}, you are {`.
+    FormatStringMiddle { value: String, position: Position },
+    /// The text after the last interpolation, e.g., `}!"`.
+    FormatStringEnd { value: String, position: Position },
+}
+
+impl Token {
+    /// The bytes of the original source this token was lexed from.
+    pub fn span(&self) -> Span {
+        self.position().span()
+    }
+}
+
+/// Conversion of the text matched by a `#[literal]` into the value of its token.
+pub trait FromLexeme {
+    fn from_lexeme(lexeme: &str, interner: &mut Interner) -> Self;
+}
+
+impl FromLexeme for Symbol {
+    fn from_lexeme(lexeme: &str, interner: &mut Interner) -> Self {
+        // look up the name first, so repeated identifiers do not allocate
+        interner.intern(lexeme)
+    }
+}
+
+impl FromLexeme for String {
+    fn from_lexeme(lexeme: &str, _: &mut Interner) -> Self {
+        lexeme.to_string()
+    }
+}
+
+impl FromLexeme for u64 {
+    fn from_lexeme(lexeme: &str, _: &mut Interner) -> Self {
+        lexeme
+            .parse()
+            .expect("the literal only matches valid integers")
+    }
+}
+
+impl FromLexeme for f64 {
+    fn from_lexeme(lexeme: &str, _: &mut Interner) -> Self {
+        lexeme
+            .parse()
+            .expect("the literal only matches valid numbers")
+    }
 }
@@ -1,3 +1,28 @@
+use super::{Directive, Position, Span, Token};
+
+/// Tokens which may occur anywhere in the input without affecting its structure (i.e., comments
+/// and directives). They are skipped when iterating over [`Tokens`].
+pub trait Trivia {
+    fn is_trivia(&self) -> bool;
+}
+
+impl Trivia for Token {
+    fn is_trivia(&self) -> bool {
+        matches!(self, Token::Comment { .. } | Token::Directive { .. })
+    }
+}
+
+/// Tokens which know their position within the input (used for reporting errors).
+pub trait Positioned {
+    fn position(&self) -> Position;
+}
+
+impl Positioned for Token {
+    fn position(&self) -> Position {
+        Token::position(self)
+    }
+}
+
 /// Struct for iterating over a vector of tokens.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Tokens<T> {
@@ -5,35 +30,41 @@ pub struct Tokens<T> {
     index: usize,
 }
 
-impl<T> Iterator for Tokens<T>
-where
-    T: Clone,
-{
-    type Item = T;
+impl<T: Trivia> Tokens<T> {
+    pub fn new(tokens: Vec<T>) -> Self {
+        Self { tokens, index: 0 }
+    }
 
-    /// Get the next item (if present).
-    fn next(&mut self) -> Option<T> {
-        if self.index < self.tokens.len() {
-            let item = self.tokens.get(self.index).cloned();
-            self.index += 1;
-            return item;
+    /// The index of the first token at or after `index`, which is no trivia.
+    fn skip_trivia(&self, mut index: usize) -> usize {
+        while self.tokens.get(index).is_some_and(T::is_trivia) {
+            index += 1;
         }
-
-        None
+        index
     }
-}
 
-impl<T> Tokens<T>
-where
-    T: Clone,
-{
-    pub fn new(tokens: Vec<T>) -> Self {
-        Self { tokens, index: 0 }
+    /// Get the next item (if present) and advance.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&T> {
+        let index = self.skip_trivia(self.index);
+        let item = self.tokens.get(index)?;
+        self.index = index + 1;
+        Some(item)
     }
 
     /// Peek at the next item.
-    pub fn peek(&mut self) -> Option<T> {
-        return self.tokens.get(self.index).cloned();
+    pub fn peek(&self) -> Option<&T> {
+        self.tokens.get(self.skip_trivia(self.index))
+    }
+
+    /// Peek at the item `offset` positions after the next one (i.e., `peek_at(0)` is the same as
+    /// `peek()`).
+    pub fn peek_at(&self, offset: usize) -> Option<&T> {
+        let mut index = self.skip_trivia(self.index);
+        for _ in 0..offset {
+            index = self.skip_trivia(index + 1);
+        }
+        self.tokens.get(index)
     }
 
     /// Get the current index.
@@ -45,13 +76,156 @@ where
     pub fn set_index(&mut self, index: usize) {
         self.index = index;
     }
+
+    /// All items which were not consumed yet (including trivia), e.g., to adjust them before
+    /// parsing.
+    pub fn rest_mut(&mut self) -> &mut [T] {
+        let index = self.index.min(self.tokens.len());
+        &mut self.tokens[index..]
+    }
+
+    /// Replace the next item by the two given items, e.g., to split a `>>` into two `>` when
+    /// closing nested generics. The split persists when backtracking to an earlier index.
+    pub fn split_next(&mut self, first: T, second: T) {
+        let index = self.skip_trivia(self.index);
+        if index >= self.tokens.len() {
+            return;
+        }
+
+        self.tokens[index] = first;
+        self.tokens.insert(index + 1, second);
+    }
+}
+
+impl Tokens<Token> {
+    /// The span of all tokens consumed since the given index (see [`Tokens::get_index`]), e.g.,
+    /// the source of a node parsed in between. It is `None` if no token was consumed.
+    pub fn span_since(&self, start: usize) -> Option<Span> {
+        let start = self.skip_trivia(start);
+        let first = self.tokens.get(start..self.index)?.first()?;
+        let last = self.tokens[self.index - 1].span();
+
+        Some(first.span().to(last))
+    }
+
+    /// Peek at the comment before the next item (if there is one).
+    pub fn peek_comment(&self) -> Option<&Token> {
+        self.tokens[self.index.min(self.tokens.len())..]
+            .iter()
+            .take_while(|token| token.is_trivia())
+            .find(|token| matches!(token, Token::Comment { .. }))
+    }
+
+    /// Consume the comment before the next item (if there is one) and return its text. This is
+    /// used to retain comments between statements.
+    pub fn next_comment(&mut self) -> Option<String> {
+        let offset = self.tokens[self.index.min(self.tokens.len())..]
+            .iter()
+            .take_while(|token| token.is_trivia())
+            .position(|token| matches!(token, Token::Comment { .. }))?;
+
+        let Token::Comment { value, .. } = &self.tokens[self.index + offset] else {
+            unreachable!()
+        };
+        self.index += offset + 1;
+        Some(value.clone())
+    }
+
+    /// All directives (e.g., `// pesca:off`) of the input along with their positions. They are
+    /// not part of the syntax tree, since they may occur anywhere (e.g., within an expression).
+    /// The loader keeps them on the program (see `Program::directives`).
+    pub fn directives(&self) -> impl Iterator<Item = (&Directive, Position)> {
+        self.tokens.iter().filter_map(|token| match token {
+            Token::Directive { value, position } => Some((value, *position)),
+            _ => None,
+        })
+    }
 }
 
-impl<T> From<Vec<T>> for Tokens<T>
-where
-    T: Clone,
-{
+impl<T: Trivia> From<Vec<T>> for Tokens<T> {
     fn from(value: Vec<T>) -> Self {
         Self::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::{Lexer, Span},
+        parser::{
+            ast::{Expression, TypeName},
+            FromTokens,
+        },
+    };
+
+    use super::{Token, Tokens, Trivia};
+
+    impl Trivia for i32 {
+        fn is_trivia(&self) -> bool {
+            *self == 0
+        }
+    }
+
+    #[test]
+    fn test_skip_trivia() {
+        let mut tokens = Tokens::new(vec![0, 1, 0, 0, 2, 0]);
+
+        assert_eq!(Some(&1), tokens.peek());
+        assert_eq!(Some(&2), tokens.peek_at(1));
+        assert_eq!(None, tokens.peek_at(2));
+        assert_eq!(Some(&1), tokens.next());
+        assert_eq!(Some(&2), tokens.next());
+        assert_eq!(None, tokens.next());
+    }
+
+    #[test]
+    fn test_split_next() {
+        let mut tokens = Tokens::new(vec![1, 23, 4]);
+        tokens.next();
+
+        tokens.split_next(2, 3);
+
+        assert_eq!(Some(&2), tokens.next());
+        assert_eq!(Some(&3), tokens.next());
+        assert_eq!(Some(&4), tokens.next());
+        assert_eq!(None, tokens.next());
+    }
+
+    #[test]
+    fn test_split_next_at_end() {
+        let mut tokens = Tokens::new(vec![1]);
+        tokens.next();
+
+        tokens.split_next(2, 3);
+
+        assert_eq!(None, tokens.peek());
+    }
+
+    #[test]
+    fn test_span_since() {
+        let source = "let x = foo(1, 2) + 3;";
+        let mut tokens: Tokens<Token> = Lexer::new(source).lex().expect("should lex").into();
+        tokens.set_index(3);
+
+        assert_eq!(None, tokens.span_since(3));
+
+        Expression::parse(&mut tokens).expect("should parse");
+
+        let span = tokens.span_since(3).expect("should consume tokens");
+        assert_eq!(Span { start: 8, end: 21 }, span);
+        assert_eq!("foo(1, 2) + 3", span.text(source));
+    }
+
+    #[test]
+    fn test_span_of_split_tokens() {
+        let source = "Foo<Bar<i32>>";
+        let mut tokens: Tokens<Token> = Lexer::new(source).lex().expect("should lex").into();
+
+        TypeName::parse(&mut tokens).expect("should parse");
+
+        let spans = tokens.tokens.iter().map(Token::span).collect::<Vec<_>>();
+        assert_eq!(Some(&Span { start: 11, end: 12 }), spans.get(5));
+        assert_eq!(Some(&Span { start: 12, end: 13 }), spans.get(6));
+        assert_eq!(Some(Span { start: 0, end: 13 }), tokens.span_since(0));
+    }
+}
@@ -1,2 +1,5 @@
 pub mod lexer;
+pub mod loader;
 pub mod parser;
+pub mod semantic;
+pub mod sourcemap;
@@ -0,0 +1,122 @@
+use std::{error::Error, fmt::Display};
+
+use super::Program;
+
+/// Magic bytes at the start of every encoded program.
+pub const MAGIC: &[u8; 4] = b"PSCA";
+
+/// Version of the binary format. This has to be incremented whenever the AST changes in an
+/// incompatible way.
+pub const FORMAT_VERSION: u16 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    InvalidHeader,
+    UnsupportedVersion { found: u16 },
+    Malformed(String),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidHeader => f.write_str("input is not an encoded program"),
+            DecodeError::UnsupportedVersion { found } => write!(
+                f,
+                "unsupported format version {found} (expected {FORMAT_VERSION})"
+            ),
+            DecodeError::Malformed(message) => write!(f, "malformed program: {message}"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+impl Program {
+    /// Encode this program in a compact binary format, e.g., for caching parse results between
+    /// runs.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, self).expect("encoding a program should not fail");
+        bytes
+    }
+
+    /// Decode a program previously encoded with [`Program::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Program, DecodeError> {
+        let Some(rest) = bytes.strip_prefix(MAGIC) else {
+            return Err(DecodeError::InvalidHeader);
+        };
+
+        let Some((version, payload)) = rest.split_first_chunk::<2>() else {
+            return Err(DecodeError::InvalidHeader);
+        };
+
+        let version = u16::from_le_bytes(*version);
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion { found: version });
+        }
+
+        bincode::deserialize(payload).map_err(|e| DecodeError::Malformed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{loader::parse_program, parser::ParseSettings};
+
+    use super::*;
+
+    fn program(source: &str) -> Program {
+        parse_program("test.why", source.into(), ParseSettings::default()).expect("should parse")
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let program = program(
+            "fn add(x: i32, y: i32): i32 {
+                return x + y;
+            }
+
+            let foo: [f64] = [1.5, 2.5];
+            struct Foo {
+                bar: (i32) -> &i32;
+            };
+            let baz = Foo { bar: \\(x) => x };",
+        );
+
+        let bytes = program.encode();
+
+        assert_eq!(Ok(program), Program::decode(&bytes));
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert_eq!(
+            Err(DecodeError::InvalidHeader),
+            Program::decode(b"something")
+        );
+        assert_eq!(Err(DecodeError::InvalidHeader), Program::decode(b"PSCA"));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut bytes = program("let a = 1;").encode();
+        bytes[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+
+        assert_eq!(
+            Err(DecodeError::UnsupportedVersion {
+                found: FORMAT_VERSION + 1
+            }),
+            Program::decode(&bytes)
+        );
+    }
+
+    #[test]
+    fn test_truncated_payload() {
+        let bytes = program("let a = 1;").encode();
+
+        let result = Program::decode(&bytes[..bytes.len() - 1]);
+
+        assert!(matches!(result, Err(DecodeError::Malformed(_))));
+    }
+}
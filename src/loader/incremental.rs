@@ -0,0 +1,232 @@
+use std::ops::Range;
+
+use crate::lexer::Position;
+
+use super::{parse_source, LoadError, ParsedSource, Program, SourceFile};
+
+impl Program {
+    /// Replace the given byte range of the source with `new_text` and update the statements
+    /// accordingly.
+    ///
+    /// Only the statements touched by the edit (and their direct neighbours) are reparsed, all other
+    /// statements are reused. If the reparsed region does not form complete statements on its own,
+    /// the whole file is reparsed. On success, the range of (new) statement indices which were
+    /// reparsed is returned. On error, the program is left untouched.
+    ///
+    /// Note: Imports are not resolved again.
+    pub fn apply_edit(
+        &mut self,
+        range: Range<usize>,
+        new_text: &str,
+    ) -> Result<Range<usize>, LoadError> {
+        assert!(
+            range.start <= range.end && range.end <= self.file.source().len(),
+            "edit range {range:?} out of bounds"
+        );
+
+        let source = self.file.source();
+        let mut new_source = String::with_capacity(source.len() - range.len() + new_text.len());
+        new_source.push_str(&source[..range.start]);
+        new_source.push_str(new_text);
+        new_source.push_str(&source[range.end..]);
+        let file = SourceFile::new(self.file.path.clone(), new_source);
+
+        if self.items.is_empty() {
+            return self.reparse(file);
+        }
+
+        // find all items overlapping the edit (an insertion touches the item it is inserted into)
+        let first = self
+            .items
+            .iter()
+            .position(|item| range.start < item.end)
+            .unwrap_or(self.items.len() - 1);
+        let last = self
+            .items
+            .iter()
+            .rposition(|item| item.start < range.end.max(range.start + 1))
+            .unwrap_or(first)
+            .max(first);
+
+        // include the direct neighbours, since an edit might affect them as well (e.g., removing a
+        // semicolon)
+        let first = first.saturating_sub(1);
+        let last = (last + 1).min(self.items.len() - 1);
+
+        let start = self.items[first].start;
+        let old_end = self.items[last].end;
+        let new_end = (old_end + new_text.len()) - range.len();
+
+        let Ok(ParsedSource {
+            statements,
+            items,
+            deprecations,
+            directives,
+        }) = parse_source(&file, start..new_end, self.settings)
+        else {
+            return self.reparse(file);
+        };
+
+        let shift = |item: &Range<usize>| {
+            (item.start + new_text.len() - range.len())..(item.end + new_text.len() - range.len())
+        };
+
+        let reparsed = first..first + statements.len();
+
+        self.statements.splice(first..=last, statements);
+        let tail = self.items[last + 1..].iter().map(shift).collect::<Vec<_>>();
+        self.items.truncate(first);
+        self.items.extend(items);
+        self.items.extend(tail);
+
+        let shift_offset = |offset: usize| offset + new_text.len() - range.len();
+        splice_positioned(
+            &mut self.deprecations,
+            deprecations,
+            start..old_end,
+            shift_offset,
+            |deprecation| &mut deprecation.position,
+        );
+        splice_positioned(
+            &mut self.directives,
+            directives,
+            start..old_end,
+            shift_offset,
+            |(_, position)| position,
+        );
+        self.file = file;
+
+        Ok(reparsed)
+    }
+
+    fn reparse(&mut self, file: SourceFile) -> Result<Range<usize>, LoadError> {
+        let ParsedSource {
+            statements,
+            items,
+            deprecations,
+            directives,
+        } = parse_source(&file, 0..file.source().len(), self.settings)?;
+
+        self.statements = statements;
+        self.items = items;
+        self.deprecations = deprecations;
+        self.directives = directives;
+        self.file = file;
+
+        Ok(0..self.statements.len())
+    }
+}
+
+/// Replace the entries (in source order) within the reparsed region of the old source with the
+/// reparsed ones and move the entries after the region to their new offsets.
+fn splice_positioned<T>(
+    entries: &mut Vec<T>,
+    reparsed: Vec<T>,
+    region: Range<usize>,
+    shift_offset: impl Fn(usize) -> usize,
+    position: fn(&mut T) -> &mut Position,
+) {
+    let mut after = vec![];
+
+    for mut entry in std::mem::take(entries) {
+        let offset = position(&mut entry).offset as usize;
+        if offset < region.start {
+            entries.push(entry);
+        } else if offset >= region.end {
+            let length = position(&mut entry).length as usize;
+            *position(&mut entry) = Position::new(shift_offset(offset), length);
+            after.push(entry);
+        }
+    }
+
+    entries.extend(reparsed);
+    entries.extend(after);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        loader::{parse_program, Program},
+        parser::ParseSettings,
+    };
+
+    fn program(source: &str) -> Program {
+        parse_program("test.why", source.into(), ParseSettings::default()).expect("should parse")
+    }
+
+    fn assert_matches_full_parse(program: &Program) {
+        let expected = self::program(program.file.source());
+        assert_eq!(expected.statements, program.statements);
+        assert_eq!(expected.items, program.items);
+        assert_eq!(expected.deprecations, program.deprecations);
+        assert_eq!(expected.directives, program.directives);
+    }
+
+    #[test]
+    fn test_edit_single_statement() {
+        let mut program = program("let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;\n");
+
+        let reparsed = program.apply_edit(19..20, "42").expect("should apply");
+
+        assert_eq!(
+            "let a = 1;\nlet b = 42;\nlet c = 3;\nlet d = 4;\n",
+            program.file.source()
+        );
+        assert_eq!(0..3, reparsed);
+        assert_matches_full_parse(&program);
+    }
+
+    #[test]
+    fn test_edit_reuses_distant_statements() {
+        let mut program = program("let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;\nlet e = 5;\n");
+
+        let reparsed = program.apply_edit(41..42, "1337").expect("should apply");
+
+        assert_eq!(2..5, reparsed);
+        assert_matches_full_parse(&program);
+    }
+
+    #[test]
+    fn test_edit_inserts_statement() {
+        let mut program = program("let a = 1;\nlet b = 2;\n");
+
+        program
+            .apply_edit(11..11, "let x = 3;\n")
+            .expect("should apply");
+
+        assert_eq!(3, program.statements.len());
+        assert_matches_full_parse(&program);
+    }
+
+    #[test]
+    fn test_edit_removes_statement() {
+        let mut program = program("let a = 1;\nlet b = 2;\nlet c = 3;\n");
+
+        program.apply_edit(11..22, "").expect("should apply");
+
+        assert_eq!("let a = 1;\nlet c = 3;\n", program.file.source());
+        assert_matches_full_parse(&program);
+    }
+
+    #[test]
+    fn test_edit_spanning_statements() {
+        let mut program = program("fn foo(): i32 {\n    42\n}\n\nlet b = foo();\n");
+
+        program
+            .apply_edit(20..31, "1337\n}\nlet c")
+            .expect("should apply");
+
+        assert_matches_full_parse(&program);
+    }
+
+    #[test]
+    fn test_faulty_edit_keeps_program() {
+        let mut program = program("let a = 1;\nlet b = 2;\n");
+        let before = program.clone();
+
+        let result = program.apply_edit(8..9, "let");
+
+        assert!(result.is_err());
+        assert_eq!(before, program);
+    }
+}
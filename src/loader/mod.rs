@@ -0,0 +1,770 @@
+mod encoding;
+mod incremental;
+mod parallel;
+mod source_file;
+#[cfg(test)]
+mod test_dir;
+mod workspace;
+
+pub use self::encoding::*;
+pub use self::parallel::*;
+pub use self::source_file::*;
+pub use self::workspace::*;
+
+use std::{
+    error::Error,
+    fmt::Display,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    lexer::{check_input_len, Directive, LexError, Lexer, Position, Tokens},
+    parser::{
+        ast::{Import, Statement},
+        edition::Deprecation,
+        parse_items, ParseError, ParseSettings,
+    },
+};
+
+/// The file extension of source files.
+pub const FILE_EXTENSION: &str = "why";
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Lex {
+        path: PathBuf,
+        location: LineCol,
+        error: LexError,
+    },
+    Parse {
+        path: PathBuf,
+        location: Option<LineCol>,
+        /// Locations of the notes of the error (in the same order).
+        note_locations: Vec<LineCol>,
+        error: Box<ParseError>,
+    },
+    UnresolvedImport {
+        path: PathBuf,
+        import: Import,
+    },
+    ImportCycle {
+        cycle: Vec<PathBuf>,
+    },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io { path, error } => write!(f, "{}: {error}", path.display()),
+            LoadError::Lex {
+                path,
+                location,
+                error,
+            } => write!(f, "{}:{location}: {}", path.display(), error.message),
+            LoadError::Parse {
+                path,
+                location,
+                note_locations,
+                error,
+            } => {
+                match location {
+                    Some(location) => {
+                        write!(f, "{}:{location}: {}", path.display(), error.message)?
+                    }
+                    None => write!(f, "{}: {error}", path.display())?,
+                }
+                for (note, location) in error.notes.iter().zip(note_locations) {
+                    write!(f, "\n{}:{location}: note: {}", path.display(), note.message)?;
+                }
+                Ok(())
+            }
+            LoadError::UnresolvedImport { path, import } => {
+                write!(f, "{}: could not resolve import '{import}'", path.display())
+            }
+            LoadError::ImportCycle { cycle } => {
+                let cycle = cycle
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                write!(f, "import cycle detected: {cycle}")
+            }
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Load the given entry file and (recursively) all files it imports.
+///
+/// Imports are resolved relative to the directory of the entry file: `use a::b::c;` refers to the
+/// first existing file of `a/b/c.why`, `a/b.why` and `a.why`. All files discovered in the same
+/// "round" of imports are parsed concurrently. All files are parsed with the given settings.
+pub fn load(entry: impl AsRef<Path>, settings: ParseSettings) -> Result<Workspace, LoadError> {
+    let entry = canonicalize(entry.as_ref())?;
+    let root = entry
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let mut workspace = Workspace::default();
+    let mut pending = vec![entry];
+
+    while !pending.is_empty() {
+        let mut ids = vec![];
+        for result in parse_files(&pending, settings) {
+            ids.push(workspace.insert(result?));
+        }
+
+        let mut next = vec![];
+        for id in ids {
+            let program = workspace.get(id).expect("program was just inserted");
+            let mut imports = vec![];
+            collect_imports(&program.statements, &mut imports);
+            let path = program.file.path.clone();
+
+            for import in imports {
+                let target = resolve(&root, &path, &import)?;
+                let target = match workspace.find(&target) {
+                    Some(program) => program.id,
+                    None => {
+                        // the file will be parsed in the next round, so it gets its id then
+                        let index = match next.iter().position(|item| *item == target) {
+                            Some(index) => index,
+                            None => {
+                                next.push(target);
+                                next.len() - 1
+                            }
+                        };
+                        FileId(workspace.programs().len() + index)
+                    }
+                };
+                workspace.get_mut(id).imports.push((import, target));
+            }
+        }
+
+        pending = next;
+    }
+
+    check_cycles(&workspace)?;
+
+    Ok(workspace)
+}
+
+/// Load every source file within the given directory (recursively). The files are parsed
+/// concurrently and inserted into the workspace sorted by their path. In contrast to [`load`],
+/// imports are not resolved.
+///
+/// All errors are collected (again, sorted by the path of the failing file).
+pub fn load_directory(
+    dir: impl AsRef<Path>,
+    settings: ParseSettings,
+) -> Result<Workspace, Vec<LoadError>> {
+    let mut paths = vec![];
+    collect_files(dir.as_ref(), &mut paths).map_err(|error| vec![error])?;
+    paths.sort();
+
+    let mut workspace = Workspace::default();
+    let mut errors = vec![];
+
+    for result in parse_files(&paths, settings) {
+        match result {
+            Ok(program) => {
+                workspace.insert(program);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(workspace)
+    } else {
+        Err(errors)
+    }
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), LoadError> {
+    let entries = fs::read_dir(dir).map_err(|error| LoadError::Io {
+        path: dir.to_path_buf(),
+        error,
+    })?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|error| LoadError::Io {
+                path: dir.to_path_buf(),
+                error,
+            })?
+            .path();
+
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == FILE_EXTENSION) {
+            paths.push(canonicalize(&path)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read, lex and parse a single file. The returned program does not have any resolved imports.
+pub fn parse_file(path: &Path, settings: ParseSettings) -> Result<Program, LoadError> {
+    let source = fs::read_to_string(path).map_err(|error| LoadError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+
+    parse_program(path, source, settings)
+}
+
+/// Parse the given source (belonging to the file at `path`) into a program without any resolved
+/// imports.
+pub fn parse_program(
+    path: impl AsRef<Path>,
+    source: String,
+    settings: ParseSettings,
+) -> Result<Program, LoadError> {
+    let file = SourceFile::new(path.as_ref(), source);
+    let ParsedSource {
+        statements,
+        items,
+        deprecations,
+        directives,
+    } = parse_source(&file, 0..file.source().len(), settings)?;
+
+    Ok(Program {
+        id: FileId(0),
+        file,
+        settings,
+        statements,
+        items,
+        deprecations,
+        directives,
+        imports: vec![],
+    })
+}
+
+/// The statements parsed from (a byte range of) a file. All ranges and positions are relative to
+/// the whole file.
+struct ParsedSource {
+    statements: Vec<Statement>,
+    items: Vec<Range<usize>>,
+    deprecations: Vec<Deprecation>,
+    directives: Vec<(Directive, Position)>,
+}
+
+/// Parse the given byte range of a file into statements and their byte ranges.
+fn parse_source(
+    file: &SourceFile,
+    range: Range<usize>,
+    settings: ParseSettings,
+) -> Result<ParsedSource, LoadError> {
+    // all positions are absolute within the file, so it has to fit (not only the range)
+    check_input_len(file.source().len()).map_err(|error| LoadError::Lex {
+        path: file.path.clone(),
+        location: file.line_col(0),
+        error,
+    })?;
+
+    let offset = range.start;
+    let source = &file.source()[range];
+    // positions of the tokens are relative to the parsed range
+    let shift = |position: Position| {
+        Position::new(position.offset as usize + offset, position.length as usize)
+    };
+
+    let tokens = Lexer::new(source).lex().map_err(|error| LoadError::Lex {
+        path: file.path.clone(),
+        location: file.line_col(error.position.offset as usize + offset),
+        error,
+    })?;
+
+    let mut tokens = Tokens::from(tokens);
+    let directives = tokens
+        .directives()
+        .map(|(directive, position)| (directive.clone(), shift(position)))
+        .collect();
+
+    let (parsed, deprecations) =
+        parse_items(&mut tokens, settings).map_err(|error| LoadError::Parse {
+            path: file.path.clone(),
+            location: error
+                .position
+                .map(|position| file.line_col(position.offset as usize + offset)),
+            note_locations: error
+                .notes
+                .iter()
+                .map(|note| file.line_col(note.position.offset as usize + offset))
+                .collect(),
+            error: Box::new(error),
+        })?;
+
+    // an item reaches from the start of its statement to the start of the next one (the first
+    // one also covers everything in front of it)
+    let items = (0..parsed.len())
+        .map(|index| {
+            let start = if index == 0 { 0 } else { parsed[index].1.start };
+            let end = parsed
+                .get(index + 1)
+                .map(|(_, span)| span.start)
+                .unwrap_or(source.len());
+
+            start + offset..end + offset
+        })
+        .collect();
+    let statements = parsed.into_iter().map(|(statement, _)| statement).collect();
+
+    let deprecations = deprecations
+        .into_iter()
+        .map(|deprecation| Deprecation {
+            position: shift(deprecation.position),
+            ..deprecation
+        })
+        .collect();
+
+    Ok(ParsedSource {
+        statements,
+        items,
+        deprecations,
+        directives,
+    })
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf, LoadError> {
+    fs::canonicalize(path).map_err(|error| LoadError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Collect the imports of the given statements, including the ones within (nested) modules.
+fn collect_imports(statements: &[Statement], imports: &mut Vec<Import>) {
+    for statement in statements {
+        match statement {
+            Statement::Import(import) => imports.push(import.clone()),
+            Statement::Module(module) => collect_imports(&module.statements, imports),
+            _ => {}
+        }
+    }
+}
+
+/// Resolve an import (from the file `importer`) relative to the given root directory. Imports
+/// within modules are resolved just like top-level ones.
+fn resolve(root: &Path, importer: &Path, import: &Import) -> Result<PathBuf, LoadError> {
+    for len in (1..=import.path.len()).rev() {
+        let mut candidate = root.to_path_buf();
+        for segment in &import.path[..len] {
+            candidate.push(segment.0.as_str());
+        }
+        candidate.set_extension(FILE_EXTENSION);
+
+        if candidate.is_file() {
+            return canonicalize(&candidate);
+        }
+    }
+
+    Err(LoadError::UnresolvedImport {
+        path: importer.to_path_buf(),
+        import: import.clone(),
+    })
+}
+
+/// Check the import graph of the workspace for cycles (reachable from the entry file).
+fn check_cycles(workspace: &Workspace) -> Result<(), LoadError> {
+    fn visit(
+        workspace: &Workspace,
+        id: FileId,
+        stack: &mut Vec<FileId>,
+        done: &mut Vec<bool>,
+    ) -> Result<(), LoadError> {
+        if let Some(start) = stack.iter().position(|item| *item == id) {
+            let cycle = stack[start..]
+                .iter()
+                .chain(std::iter::once(&id))
+                .map(|id| {
+                    workspace
+                        .get(*id)
+                        .expect("id should exist")
+                        .file
+                        .path
+                        .clone()
+                })
+                .collect();
+            return Err(LoadError::ImportCycle { cycle });
+        }
+
+        if done[id.0] {
+            return Ok(());
+        }
+
+        stack.push(id);
+        let program = workspace.get(id).expect("id should exist");
+        for (_, target) in &program.imports {
+            visit(workspace, *target, stack, done)?;
+        }
+        stack.pop();
+        done[id.0] = true;
+
+        Ok(())
+    }
+
+    if workspace.programs().is_empty() {
+        return Ok(());
+    }
+
+    let mut done = vec![false; workspace.programs().len()];
+    visit(workspace, FileId(0), &mut vec![], &mut done)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::edition::LanguageEdition;
+
+    use super::{test_dir::TestDir, *};
+
+    fn setup(name: &str, files: &[(&str, &str)]) -> TestDir {
+        let dir = TestDir::new(&format!("loader-{name}"));
+
+        for (file, content) in files {
+            dir.write(file, content);
+        }
+
+        dir
+    }
+
+    #[test]
+    fn test_load_single_file() {
+        let dir = setup("single", &[("main.why", "let a = 42;")]);
+
+        let workspace = load(dir.join("main.why"), ParseSettings::default()).expect("should load");
+
+        assert_eq!(workspace.programs().len(), 1);
+        let entry = workspace.entry().expect("should have entry");
+        assert_eq!(entry.id, FileId(0));
+        assert_eq!(entry.statements.len(), 1);
+        assert!(entry.imports.is_empty());
+    }
+
+    #[test]
+    fn test_load_resolves_imports() {
+        let dir = setup(
+            "resolve",
+            &[
+                ("main.why", "use geometry::Point;\nuse geometry::shapes;"),
+                ("geometry.why", "struct Point {};"),
+                ("geometry/shapes.why", "struct Circle {};"),
+            ],
+        );
+
+        let workspace = load(dir.join("main.why"), ParseSettings::default()).expect("should load");
+
+        assert_eq!(workspace.programs().len(), 3);
+
+        let entry = workspace.entry().expect("should have entry");
+        let imported = entry
+            .imports
+            .iter()
+            .map(|(_, id)| workspace.get(*id).expect("should exist").file.path.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                fs::canonicalize(dir.join("geometry.why")).unwrap(),
+                fs::canonicalize(dir.join("geometry/shapes.why")).unwrap()
+            ],
+            imported
+        );
+    }
+
+    #[test]
+    fn test_load_resolves_imports_in_modules() {
+        let dir = setup(
+            "module-imports",
+            &[
+                (
+                    "main.why",
+                    "mod shapes {\n    mod round {\n        use circle;\n    }\n}",
+                ),
+                ("circle.why", "struct Circle {};"),
+            ],
+        );
+
+        let workspace = load(dir.join("main.why"), ParseSettings::default()).expect("should load");
+
+        assert_eq!(workspace.programs().len(), 2);
+
+        let entry = workspace.entry().expect("should have entry");
+        assert_eq!(1, entry.imports.len());
+        assert_eq!(
+            fs::canonicalize(dir.join("circle.why")).unwrap(),
+            workspace
+                .get(entry.imports[0].1)
+                .expect("should exist")
+                .file
+                .path
+        );
+    }
+
+    #[test]
+    fn test_load_shared_import_once() {
+        let dir = setup(
+            "shared",
+            &[
+                ("main.why", "use a;\nuse b;"),
+                ("a.why", "use common;"),
+                ("b.why", "use common;"),
+                ("common.why", "let x = 1;"),
+            ],
+        );
+
+        let workspace = load(dir.join("main.why"), ParseSettings::default()).expect("should load");
+
+        assert_eq!(workspace.programs().len(), 4);
+    }
+
+    #[test]
+    fn test_load_detects_cycle() {
+        let dir = setup(
+            "cycle",
+            &[
+                ("main.why", "use a;"),
+                ("a.why", "use b;"),
+                ("b.why", "use a;"),
+            ],
+        );
+
+        let Err(LoadError::ImportCycle { cycle }) =
+            load(dir.join("main.why"), ParseSettings::default())
+        else {
+            panic!("expected import cycle");
+        };
+
+        let names = cycle
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a.why", "b.why", "a.why"], names);
+    }
+
+    #[test]
+    fn test_load_directory_sorted() {
+        let dir = setup(
+            "directory",
+            &[
+                ("b.why", "let b = 2;"),
+                ("a.why", "let a = 1;"),
+                ("nested/c.why", "let c = 3;"),
+                ("ignored.txt", "not a source file"),
+            ],
+        );
+
+        let workspace = load_directory(&dir, ParseSettings::default()).expect("should load");
+
+        let names = workspace
+            .programs()
+            .iter()
+            .map(|program| {
+                program
+                    .file
+                    .path
+                    .strip_prefix(fs::canonicalize(&dir).unwrap())
+            })
+            .map(|path| path.unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                PathBuf::from("a.why"),
+                PathBuf::from("b.why"),
+                PathBuf::from("nested/c.why")
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn test_load_unresolved_import() {
+        let dir = setup("unresolved", &[("main.why", "use nowhere;")]);
+
+        let result = load(dir.join("main.why"), ParseSettings::default());
+
+        assert!(matches!(result, Err(LoadError::UnresolvedImport { .. })));
+    }
+
+    #[test]
+    fn test_parse_in_edition() {
+        let settings = ParseSettings {
+            edition: LanguageEdition::Edition2023,
+        };
+        let mut program =
+            parse_program("main.why", "let match = 1;".into(), settings).expect("should parse");
+
+        assert_eq!(settings, program.settings);
+        assert!(parse_program(
+            "main.why",
+            "let match = 1;".into(),
+            ParseSettings::default()
+        )
+        .is_err());
+
+        // edits are reparsed in the same edition
+        program
+            .apply_edit(4..9, "for")
+            .expect("should reparse in edition 2023");
+    }
+
+    #[test]
+    fn test_deprecations_of_edition() {
+        let settings = ParseSettings {
+            edition: LanguageEdition::Edition2023,
+        };
+        let mut program = parse_program(
+            "main.why",
+            "let a = 1;\nlet match = 1;\nlet b = 2;\nlet c = 3;\nlet for = 4;\n".into(),
+            settings,
+        )
+        .expect("should parse");
+
+        assert_eq!(
+            vec![Position::new(15, 5), Position::new(52, 3)],
+            program
+                .deprecations
+                .iter()
+                .map(|deprecation| deprecation.position)
+                .collect::<Vec<_>>()
+        );
+
+        // deprecations of reparsed statements are replaced, the ones after them are shifted
+        program.apply_edit(15..20, "value").expect("should reparse");
+        program.apply_edit(0..0, "\n").expect("should reparse");
+
+        assert_eq!(
+            vec![Deprecation {
+                message: "`for` is a keyword since edition 2024".into(),
+                position: Position::new(53, 3),
+                suggestion: Some("for_".into())
+            }],
+            program.deprecations
+        );
+        assert_eq!(
+            "6:5",
+            program
+                .file
+                .line_col(program.deprecations[0].position.offset as usize)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_directives_of_program() {
+        let mut program = parse_program(
+            "main.why",
+            "// pesca:off\nlet a = 1;\n// pesca:allow(unused_variable)\nlet b = 2;\n".into(),
+            ParseSettings::default(),
+        )
+        .expect("should parse");
+
+        let directives = |program: &Program| {
+            program
+                .directives
+                .iter()
+                .map(|(directive, position)| {
+                    (
+                        directive.name.clone(),
+                        directive.arguments.clone(),
+                        *position,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![
+                ("off".to_string(), vec![], Position::new(0, 12)),
+                (
+                    "allow".to_string(),
+                    vec!["unused_variable".to_string()],
+                    Position::new(24, 31)
+                ),
+            ],
+            directives(&program)
+        );
+
+        // directives within a reparsed region are collected again, the ones after it are shifted
+        program.apply_edit(21..22, "10").expect("should reparse");
+        program.apply_edit(0..0, "\n").expect("should reparse");
+
+        assert_eq!(
+            vec![
+                ("off".to_string(), vec![], Position::new(1, 12)),
+                (
+                    "allow".to_string(),
+                    vec!["unused_variable".to_string()],
+                    Position::new(26, 31)
+                ),
+            ],
+            directives(&program)
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let Err(error) = parse_program(
+            "main.why",
+            "let a = 1;\n\nlet b = ;".into(),
+            ParseSettings::default(),
+        ) else {
+            panic!("expected parse error");
+        };
+
+        let LoadError::Parse { location, .. } = &error else {
+            panic!("expected parse error");
+        };
+        assert_eq!(Some(LineCol { line: 3, column: 9 }), *location);
+        assert!(error.to_string().starts_with("main.why:3:9: "));
+    }
+
+    #[test]
+    fn test_lex_error_location() {
+        let Err(error) = parse_program(
+            "main.why",
+            "let a = 1;\nlet b = 2 $ 3;".into(),
+            ParseSettings::default(),
+        ) else {
+            panic!("expected lex error");
+        };
+
+        let LoadError::Lex { location, .. } = &error else {
+            panic!("expected lex error");
+        };
+        assert_eq!(
+            LineCol {
+                line: 2,
+                column: 11
+            },
+            *location
+        );
+        assert_eq!("main.why:2:11: unexpected character '$'", error.to_string());
+    }
+
+    #[test]
+    fn test_parse_error_notes() {
+        let Err(error) = parse_program(
+            "main.why",
+            "fn f(x: i32,\n     x: i32): i32 { x }".into(),
+            ParseSettings::default(),
+        ) else {
+            panic!("expected parse error");
+        };
+
+        assert_eq!(
+            "main.why:2:6: duplicate parameter 'x'\nmain.why:1:6: note: 'x' is first defined here",
+            error.to_string()
+        );
+    }
+}
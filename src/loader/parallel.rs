@@ -0,0 +1,125 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::parser::{stats, ParseSettings};
+
+use super::{parse_file, LoadError, Program};
+
+/// Parse all given files concurrently. The results are returned in the same order as the paths,
+/// independent of the order in which the files finished parsing. If parse statistics are collected
+/// on the calling thread, the statistics of all files are added to them.
+pub fn parse_files(paths: &[PathBuf], settings: ParseSettings) -> Vec<Result<Program, LoadError>> {
+    let workers = thread::available_parallelism()
+        .map(|amount| amount.get())
+        .unwrap_or(1)
+        .min(paths.len());
+
+    if workers <= 1 {
+        return paths
+            .iter()
+            .map(|path| parse_file(path, settings))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let recording = stats::enabled();
+
+    let mut results = thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let work = || {
+                        let mut results = vec![];
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(path) = paths.get(index) else {
+                                return results;
+                            };
+                            results.push((index, parse_file(path, settings)));
+                        }
+                    };
+
+                    match recording {
+                        true => stats::collect_on_thread(work),
+                        false => (work(), Default::default()),
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                let (results, recorded) = handle.join().expect("parser thread panicked");
+                stats::record(recorded);
+                results
+            })
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loader::test_dir::TestDir;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_files_keeps_order() {
+        let dir = TestDir::new("parallel");
+
+        let paths = (0..16)
+            .map(|index| {
+                let source = format!("let a{index} = {index};\n").repeat(index + 1);
+                dir.write(&format!("file_{index}.why"), &source)
+            })
+            .collect::<Vec<_>>();
+
+        let results = parse_files(&paths, ParseSettings::default());
+
+        assert_eq!(results.len(), 16);
+        for (index, result) in results.into_iter().enumerate() {
+            let program = result.expect("should parse");
+            assert_eq!(program.file.path, paths[index]);
+            assert_eq!(program.statements.len(), index + 1);
+        }
+    }
+
+    #[test]
+    fn test_parse_files_records_stats() {
+        let dir = TestDir::new("parallel-stats");
+
+        let paths = (0..8)
+            .map(|index| dir.write(&format!("file_{index}.why"), "let a = 1;\nlet b = 2;\n"))
+            .collect::<Vec<_>>();
+
+        let (results, stats) = stats::collect(|| parse_files(&paths, ParseSettings::default()));
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(16, stats.rules["Statement"].invocations);
+    }
+
+    #[test]
+    fn test_parse_files_reports_errors_in_order() {
+        let dir = TestDir::new("parallel-err");
+
+        let paths = vec![dir.join("missing_a.why"), dir.join("missing_b.why")];
+
+        let results = parse_files(&paths, ParseSettings::default());
+
+        let failed = results
+            .into_iter()
+            .map(|result| match result {
+                Err(LoadError::Io { path, .. }) => path,
+                _ => panic!("expected io error"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(paths, failed);
+    }
+}
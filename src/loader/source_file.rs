@@ -0,0 +1,111 @@
+use std::{fmt::Display, path::PathBuf, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// A (one based) line and column within a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for LineCol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The content of a source file. Line and column information is only computed (once) when it is
+/// needed, e.g., for rendering diagnostics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    source: String,
+    #[serde(skip)]
+    line_starts: OnceLock<Vec<u32>>,
+}
+
+impl SourceFile {
+    pub fn new(path: impl Into<PathBuf>, source: String) -> Self {
+        Self {
+            path: path.into(),
+            source,
+            line_starts: OnceLock::new(),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the line and column of the given byte offset. The column is counted in characters.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.source.len());
+        let line_starts = self.line_starts();
+
+        let line = line_starts.partition_point(|start| *start as usize <= offset) - 1;
+        let line_start = line_starts[line] as usize;
+        let column = self.source[line_start..offset].chars().count();
+
+        LineCol {
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+
+    fn line_starts(&self) -> &[u32] {
+        self.line_starts.get_or_init(|| {
+            std::iter::once(0)
+                .chain(
+                    self.source
+                        .match_indices('\n')
+                        .map(|(index, _)| index as u32 + 1),
+                )
+                .collect()
+        })
+    }
+}
+
+impl PartialEq for SourceFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.source == other.source
+    }
+}
+
+impl Eq for SourceFile {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col_first_line() {
+        let file = SourceFile::new("test.why", "let a = 42;".into());
+
+        assert_eq!(LineCol { line: 1, column: 1 }, file.line_col(0));
+        assert_eq!(LineCol { line: 1, column: 9 }, file.line_col(8));
+    }
+
+    #[test]
+    fn test_line_col_multiple_lines() {
+        let file = SourceFile::new("test.why", "let a = 42;\n\nlet b = a;".into());
+
+        assert_eq!(
+            LineCol {
+                line: 1,
+                column: 12
+            },
+            file.line_col(11)
+        );
+        assert_eq!(LineCol { line: 2, column: 1 }, file.line_col(12));
+        assert_eq!(LineCol { line: 3, column: 5 }, file.line_col(17));
+    }
+
+    #[test]
+    fn test_line_col_counts_characters() {
+        let file = SourceFile::new("test.why", "// äöü\nfoo".into());
+
+        assert_eq!(LineCol { line: 1, column: 7 }, file.line_col(9));
+        assert_eq!(LineCol { line: 2, column: 2 }, file.line_col(11));
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// Temporary directory for tests, which is removed (along with its contents) when dropped.
+pub(crate) struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    /// Create an empty directory. The name has to be unique among all tests, since they run
+    /// concurrently.
+    pub(crate) fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("pesca-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("should create directory");
+
+        Self { path }
+    }
+
+    /// Write a file (and all missing parent directories) within this directory.
+    pub(crate) fn write(&self, file: &str, content: &str) -> PathBuf {
+        let path = self.path.join(file);
+        fs::create_dir_all(path.parent().expect("should have parent"))
+            .expect("should create directory");
+        fs::write(&path, content).expect("should write file");
+        path
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TestDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,75 @@
+use std::{ops::Range, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lexer::{Directive, Position},
+    parser::{
+        ast::{Import, Statement},
+        edition::Deprecation,
+        ParseSettings,
+    },
+};
+
+use super::SourceFile;
+
+/// Handle of a single file within a [`Workspace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FileId(pub usize);
+
+/// A parsed source file together with the files its imports resolved to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Program {
+    pub id: FileId,
+    pub file: SourceFile,
+    /// The settings the source was parsed with (and is reparsed with after an edit).
+    pub settings: ParseSettings,
+    pub statements: Vec<Statement>,
+    /// The byte range of every top-level statement within the source. The ranges cover the whole
+    /// source, i.e., whitespace between two statements belongs to the preceding one.
+    pub items: Vec<Range<usize>>,
+    /// Usages of syntax which is deprecated in the edition of the settings (in source order).
+    pub deprecations: Vec<Deprecation>,
+    /// The directives (e.g., `// pesca:off`) of the source with their positions (in source
+    /// order), so tools working on the program can honor them.
+    pub directives: Vec<(Directive, Position)>,
+    pub imports: Vec<(Import, FileId)>,
+}
+
+/// All programs reachable from an entry file. The entry file always has the id `FileId(0)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Workspace {
+    programs: Vec<Program>,
+}
+
+impl Workspace {
+    /// Insert a program into this workspace. The program gets assigned the next free id.
+    pub fn insert(&mut self, program: Program) -> FileId {
+        let id = FileId(self.programs.len());
+        self.programs.push(Program { id, ..program });
+        id
+    }
+
+    pub(super) fn get_mut(&mut self, id: FileId) -> &mut Program {
+        &mut self.programs[id.0]
+    }
+
+    pub fn entry(&self) -> Option<&Program> {
+        self.programs.first()
+    }
+
+    pub fn get(&self, id: FileId) -> Option<&Program> {
+        self.programs.get(id.0)
+    }
+
+    /// Find the program which was loaded from the given (canonical) path.
+    pub fn find(&self, path: &Path) -> Option<&Program> {
+        self.programs
+            .iter()
+            .find(|program| program.file.path == path)
+    }
+
+    pub fn programs(&self) -> &[Program] {
+        &self.programs
+    }
+}
@@ -1,15 +1,40 @@
-use std::{error::Error, fs};
+use std::{error::Error, path::Path};
 
-use clap::{command, Parser};
-use pesca_lang::{lexer::Lexer, parser::parse};
+use clap::Parser;
+use pesca_lang::{
+    loader::{self, Workspace},
+    parser::{edition::LanguageEdition, railroad, stats, ParseSettings},
+    semantic::{check_entry_point, EntryPoint},
+};
+
+#[cfg(feature = "counting-allocator")]
+#[global_allocator]
+static ALLOCATOR: stats::CountingAllocator = stats::CountingAllocator;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 #[command(propagate_version = true)]
 pub struct Cli {
-    /// The path to the source file.
-    #[arg(index = 1)]
-    pub file: std::path::PathBuf,
+    /// The path to the source file (or a directory containing source files).
+    #[arg(index = 1, required_unless_present = "syntax")]
+    pub file: Option<std::path::PathBuf>,
+
+    /// Print parse statistics (per rule invocations, backtracks, time and peak memory) of the
+    /// workspace. Peak memory requires the `counting-allocator` feature.
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Check that the workspace is an executable, i.e., defines exactly one `fn main(): void`.
+    #[arg(long)]
+    pub executable: bool,
+
+    /// The edition the source files are written in.
+    #[arg(long, default_value_t = LanguageEdition::LATEST)]
+    pub edition: LanguageEdition,
+
+    /// Write the railroad diagrams of the grammar (as an HTML document) to the given path.
+    #[arg(long, value_name = "PATH")]
+    pub syntax: Option<std::path::PathBuf>,
 }
 
 impl Cli {
@@ -21,17 +46,68 @@ impl Cli {
 fn main() -> Result<(), Box<dyn Error>> {
     let args = Cli::init();
 
-    let input = fs::read_to_string(args.file)?;
+    if let Some(path) = &args.syntax {
+        std::fs::write(path, railroad::document(&railroad::rules()))?;
+    }
+
+    let Some(file) = &args.file else {
+        return Ok(());
+    };
+
+    let settings = ParseSettings {
+        edition: args.edition,
+    };
+
+    let (workspace, stats) = match args.stats {
+        true => {
+            let (workspace, stats) = stats::collect(|| load(file, settings));
+            (workspace?, Some(stats))
+        }
+        false => (load(file, settings)?, None),
+    };
 
-    println!("{input}");
+    for program in workspace.programs() {
+        for deprecation in &program.deprecations {
+            let location = program.file.line_col(deprecation.position.offset as usize);
+            eprintln!("{}:{location}: {deprecation}", program.file.path.display());
+        }
+    }
 
-    let lexer = Lexer::new(&input);
-    let tokens = lexer.lex()?;
+    if args.executable {
+        let diagnostics = check_entry_point(&workspace, &EntryPoint::default());
+        if !diagnostics.is_empty() {
+            for diagnostic in &diagnostics {
+                eprintln!("{diagnostic}");
+            }
+            std::process::exit(1);
+        }
+    }
 
-    println!("{tokens:#?}");
+    for program in workspace.programs() {
+        println!("{}", program.file.path.display());
+        println!("{:#?}", program.statements);
+    }
 
-    let statements = parse(&mut tokens.into())?;
+    if let Some(stats) = stats {
+        eprintln!("{stats}");
+    }
 
-    println!("{statements:#?}");
     Ok(())
 }
+
+/// Load the workspace of the given file (or of all files within the given directory).
+fn load(file: &Path, settings: ParseSettings) -> Result<Workspace, Box<dyn Error>> {
+    if !file.is_dir() {
+        return Ok(loader::load(file, settings)?);
+    }
+
+    match loader::load_directory(file, settings) {
+        Ok(workspace) => Ok(workspace),
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{error}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+
+use crate::lazy_comb;
+use crate::lexer::{Token, Tokens};
+use crate::parser::{combinators::Comb, FromTokens, ParseError};
+
+use super::{parse_doc, AstNode, Expression, Id};
+
+/// An annotation in front of a declaration, either written as `#[name(args)]` or as
+/// `@name(args)`. The arguments (including the parentheses) are optional, e.g., `#[inline]` or
+/// `@deprecated("use bar instead")`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attribute {
+    pub name: Id,
+    pub args: Vec<AttributeArgument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributeArgument {
+    /// `key = value`, e.g., `since = "0.2"`.
+    Named {
+        name: Id,
+        value: Expression,
+    },
+    Expression(Expression),
+}
+
+impl Attribute {
+    /// Parse all attributes in front of a declaration.
+    pub(crate) fn parse_all(tokens: &mut Tokens<Token>) -> Result<Vec<Attribute>, ParseError> {
+        let mut attributes = vec![];
+
+        loop {
+            let bracketed = match tokens.peek() {
+                Some(Token::HashBracket { .. }) => true,
+                Some(Token::At { .. }) => false,
+                _ => return Ok(attributes),
+            };
+            tokens.next();
+
+            let Some(AstNode::Id(name)) = Comb::ID.parse(tokens)?.into_iter().next() else {
+                unreachable!()
+            };
+
+            let args = match tokens.peek() {
+                Some(Token::LParen { .. }) => Self::parse_arguments(tokens)?,
+                _ => vec![],
+            };
+
+            if bracketed {
+                Comb::RBRACKET.parse(tokens)?;
+            }
+
+            attributes.push(Attribute { name, args });
+        }
+    }
+
+    /// Parse the doc comments and attributes in front of a declaration, which may be written in
+    /// any order (e.g., `#[inline]` followed by `/// ...`).
+    pub(crate) fn parse_with_doc(
+        tokens: &mut Tokens<Token>,
+    ) -> Result<(Option<String>, Vec<Attribute>), ParseError> {
+        let mut doc: Option<String> = None;
+        let mut attributes = vec![];
+
+        loop {
+            let lines = parse_doc(tokens);
+            let parsed = Self::parse_all(tokens)?;
+            if lines.is_none() && parsed.is_empty() {
+                return Ok((doc, attributes));
+            }
+
+            if let Some(lines) = lines {
+                doc = Some(match doc {
+                    Some(doc) => format!("{doc}\n{lines}"),
+                    None => lines,
+                });
+            }
+            attributes.extend(parsed);
+        }
+    }
+
+    /// Parse `(arg, key = value, ...)`.
+    fn parse_arguments(tokens: &mut Tokens<Token>) -> Result<Vec<AttributeArgument>, ParseError> {
+        Comb::LPAREN.parse(tokens)?;
+
+        let mut args = vec![];
+
+        while !matches!(tokens.peek(), Some(Token::RParen { .. })) {
+            let arg = match (tokens.peek(), tokens.peek_at(1)) {
+                (Some(Token::Id { .. }), Some(Token::Assign { .. })) => {
+                    let matcher = lazy_comb!(Comb::ID >> Comb::ASSIGN >> Comb::EXPR);
+                    let mut result = matcher.parse(tokens)?.into_iter();
+
+                    let (Some(AstNode::Id(name)), Some(AstNode::Expression(value))) =
+                        (result.next(), result.next())
+                    else {
+                        unreachable!()
+                    };
+
+                    AttributeArgument::Named { name, value }
+                }
+                _ => {
+                    let AstNode::Expression(value) = Expression::parse(tokens)? else {
+                        unreachable!()
+                    };
+
+                    AttributeArgument::Expression(value)
+                }
+            };
+            args.push(arg);
+
+            if !matches!(tokens.peek(), Some(Token::Comma { .. })) {
+                break;
+            }
+            tokens.next();
+        }
+
+        Comb::RPAREN.parse(tokens)?;
+
+        Ok(args)
+    }
+
+    /// Skip the attributes starting at `offset` without parsing them, returning the offset of the
+    /// first token after them. This allows to look ahead at the declaration they belong to.
+    pub(crate) fn skip(tokens: &Tokens<Token>, mut offset: usize) -> usize {
+        loop {
+            let closing = match tokens.peek_at(offset) {
+                Some(Token::HashBracket { .. }) => Some(Self::skip_group(tokens, offset)),
+                Some(Token::At { .. }) => match tokens.peek_at(offset + 2) {
+                    Some(Token::LParen { .. }) => Some(Self::skip_group(tokens, offset + 2)),
+                    _ => Some(offset + 1),
+                },
+                _ => None,
+            };
+
+            match closing {
+                Some(closing) => offset = closing + 1,
+                None => return offset,
+            }
+        }
+    }
+
+    /// Like [`Attribute::skip`], but also skips doc comments (in any order). Additionally returns
+    /// whether any attributes were skipped.
+    pub(crate) fn skip_with_doc(tokens: &Tokens<Token>, mut offset: usize) -> (usize, bool) {
+        let mut attributed = false;
+
+        loop {
+            let start = offset;
+            while let Some(Token::DocComment { .. }) = tokens.peek_at(offset) {
+                offset += 1;
+            }
+
+            let end = Self::skip(tokens, offset);
+            attributed |= end > offset;
+            offset = end;
+
+            if offset == start {
+                return (offset, attributed);
+            }
+        }
+    }
+
+    /// The offset of the token closing the group opened at `offset` (or the offset after the last
+    /// token if it is not closed).
+    fn skip_group(tokens: &Tokens<Token>, mut offset: usize) -> usize {
+        let mut depth = 0usize;
+
+        while let Some(token) = tokens.peek_at(offset) {
+            match token {
+                Token::HashBracket { .. } | Token::LBracket { .. } | Token::LParen { .. } => {
+                    depth += 1
+                }
+                Token::RBracket { .. } | Token::RParen { .. } => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return offset;
+                    }
+                }
+                _ => {}
+            }
+            offset += 1;
+        }
+
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+    use crate::parser::ast::Num;
+
+    use super::*;
+
+    fn tokens(input: &str) -> Tokens<Token> {
+        Lexer::new(input).lex().expect("should work").into()
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let mut tokens = tokens("#[inline] @deprecated(\"use bar\", since = 2) fn");
+
+        assert_eq!(
+            Ok(vec![
+                Attribute {
+                    name: Id("inline".into()),
+                    args: vec![]
+                },
+                Attribute {
+                    name: Id("deprecated".into()),
+                    args: vec![
+                        AttributeArgument::Expression(Expression::String("use bar".into())),
+                        AttributeArgument::Named {
+                            name: Id("since".into()),
+                            value: Expression::Num(Num::Integer(2))
+                        }
+                    ]
+                }
+            ]),
+            Attribute::parse_all(&mut tokens)
+        );
+        assert!(matches!(tokens.peek(), Some(Token::FnKeyword { .. })));
+    }
+
+    #[test]
+    fn test_parse_unclosed_attribute_fails() {
+        assert!(Attribute::parse_all(&mut tokens("#[inline fn")).is_err());
+    }
+
+    #[test]
+    fn test_skip() {
+        let tokens = tokens("#[cfg(all(a, b[0]))] @inline @deprecated(\"x\") pub fn");
+
+        assert!(matches!(
+            tokens.peek_at(Attribute::skip(&tokens, 0)),
+            Some(Token::PubKeyword { .. })
+        ));
+        assert_eq!(
+            Attribute::skip(&tokens, 0),
+            Attribute::skip(&tokens, Attribute::skip(&tokens, 0))
+        );
+    }
+}
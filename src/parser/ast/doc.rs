@@ -0,0 +1,36 @@
+use crate::lexer::{Token, Tokens};
+
+/// Consume the doc comments (`/// ...`) in front of an item. Consecutive doc comments are joined
+/// line by line.
+pub(crate) fn parse_doc(tokens: &mut Tokens<Token>) -> Option<String> {
+    let mut lines = vec![];
+
+    while let Some(Token::DocComment { value, .. }) = tokens.peek() {
+        lines.push(value.clone());
+        tokens.next();
+    }
+
+    match lines.is_empty() {
+        true => None,
+        false => Some(lines.join("\n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_doc() {
+        let mut tokens = Lexer::new("/// first\n///second\nfoo")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(Some("first\nsecond".into()), parse_doc(&mut tokens));
+        assert_eq!(None, parse_doc(&mut tokens));
+        assert!(matches!(tokens.peek(), Some(Token::Id { .. })));
+    }
+}
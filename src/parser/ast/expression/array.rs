@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{ast::AstNode, combinators::Comb, FromTokens, ParseError},
 };
 
 use super::{Expression, Num};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Array {
     Literal {
         values: Vec<Expression>,
@@ -19,7 +22,7 @@ pub enum Array {
 impl FromTokens<Token> for Array {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let start = tokens.get_index();
-        let matcher = Comb::LBRACKET >> (Comb::EXPR % Comb::COMMA) >> Comb::RBRACKET;
+        let matcher = lazy_comb!(Comb::LBRACKET >> (Comb::ELEMENT % Comb::COMMA) >> Comb::RBRACKET);
 
         if let Ok(result) = matcher.parse(tokens) {
             let mut values = vec![];
@@ -34,7 +37,8 @@ impl FromTokens<Token> for Array {
         }
         tokens.set_index(start);
 
-        let matcher = Comb::LBRACKET >> Comb::EXPR >> Comb::SEMI >> Comb::NUM >> Comb::RBRACKET;
+        let matcher =
+            lazy_comb!(Comb::LBRACKET >> Comb::EXPR >> Comb::SEMI >> Comb::NUM >> Comb::RBRACKET);
         if let Ok(result) = matcher.parse(tokens) {
             let Some(AstNode::Expression(initial_value)) = result.first().cloned() else {
                 unreachable!()
@@ -54,6 +58,7 @@ impl FromTokens<Token> for Array {
         Err(ParseError {
             message: "failed to parse array initialization".into(),
             position: None,
+            notes: vec![],
         })
     }
 }
@@ -66,7 +71,7 @@ impl From<Array> for AstNode {
 
 #[cfg(test)]
 mod tests {
-    use crate::lexer::Lexer;
+    use crate::{lexer::Lexer, parser::ast::Id};
 
     use super::*;
 
@@ -98,6 +103,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_literal_with_spread() {
+        let mut tokens = Lexer::new("[1, 2, ...rest]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Array::parse(&mut tokens);
+        assert_eq!(
+            Ok(Array::Literal {
+                values: vec![
+                    Expression::Num(Num::Integer(1)),
+                    Expression::Num(Num::Integer(2)),
+                    Expression::Spread(Box::new(Expression::Id(Id("rest".into()))))
+                ]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_spread_default_fails() {
+        let mut tokens = Lexer::new("[...xs; 5]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Array::parse(&mut tokens).is_err());
+    }
+
     #[test]
     fn test_simple_default() {
         let mut tokens = Lexer::new("[42; 5]")
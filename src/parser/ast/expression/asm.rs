@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{ast::AstNode, combinators::Comb, FromTokens, ParseError},
+};
+
+use super::Expression;
+
+/// Inline assembly, e.g., `asm { "mov {0}, {1}" : x : y }`. The template is a string literal (with
+/// its escape sequences decoded by the lexer), it and the operands (outputs and inputs) are not
+/// interpreted any further.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Asm {
+    pub template: String,
+    pub outputs: Vec<Expression>,
+    pub inputs: Vec<Expression>,
+}
+
+impl Asm {
+    /// Whether the tokens start an asm block. `asm` is a contextual keyword, which is only
+    /// followed by a block with a string template.
+    pub(crate) fn starts(tokens: &Tokens<Token>) -> bool {
+        matches!(
+            (tokens.peek(), tokens.peek_at(1), tokens.peek_at(2)),
+            (
+                Some(Token::Id { value, .. }),
+                Some(Token::LBrace { .. }),
+                Some(Token::StringLiteral { .. })
+            ) if value.as_str() == "asm"
+        )
+    }
+
+    fn parse_operands(tokens: &mut Tokens<Token>) -> Result<Vec<Expression>, ParseError> {
+        if !matches!(tokens.peek(), Some(Token::Colon { .. })) {
+            return Ok(vec![]);
+        }
+
+        let matcher = lazy_comb!(Comb::COLON >> !(Comb::EXPR % Comb::COMMA));
+
+        Ok(matcher
+            .parse(tokens)?
+            .into_iter()
+            .map(|operand| {
+                let AstNode::Expression(operand) = operand else {
+                    unreachable!()
+                };
+                operand
+            })
+            .collect())
+    }
+}
+
+impl FromTokens<Token> for Asm {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        if !Asm::starts(tokens) {
+            let position = tokens.peek().map(Token::position);
+            return Err(ParseError {
+                message: "expected asm block".into(),
+                position,
+                notes: vec![],
+            });
+        }
+
+        // skip `asm {`
+        tokens.next();
+        tokens.next();
+
+        let Some(Token::StringLiteral { value, .. }) = tokens.next() else {
+            unreachable!()
+        };
+        let template = value.clone();
+
+        let outputs = Asm::parse_operands(tokens)?;
+        let inputs = Asm::parse_operands(tokens)?;
+
+        Comb::RBRACE.parse(tokens)?;
+
+        Ok(Asm {
+            template,
+            outputs,
+            inputs,
+        }
+        .into())
+    }
+}
+
+impl From<Asm> for AstNode {
+    fn from(value: Asm) -> Self {
+        AstNode::Asm(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::ast::{Id, Num, Statement},
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<AstNode, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+        Asm::parse(&mut tokens)
+    }
+
+    #[test]
+    fn test_parse_template_only() {
+        assert_eq!(
+            Ok(Asm {
+                template: "nop".into(),
+                outputs: vec![],
+                inputs: vec![]
+            }
+            .into()),
+            parse("asm { \"nop\" }")
+        );
+    }
+
+    #[test]
+    fn test_parse_operands() {
+        assert_eq!(
+            Ok(Asm {
+                template: "add {0}, {1}\n".into(),
+                outputs: vec![Expression::Id(Id("x".into()))],
+                inputs: vec![
+                    Expression::Id(Id("y".into())),
+                    Expression::Num(Num::Integer(1))
+                ]
+            }
+            .into()),
+            parse("asm { \"add {0}, {1}\\n\" : x : y, 1 }")
+        );
+    }
+
+    #[test]
+    fn test_parse_inputs_without_outputs() {
+        assert_eq!(
+            Ok(Asm {
+                template: "push {0}".into(),
+                outputs: vec![],
+                inputs: vec![Expression::Id(Id("y".into()))]
+            }
+            .into()),
+            parse("asm { \"push {0}\" : : y }")
+        );
+    }
+
+    #[test]
+    fn test_parse_as_statement() {
+        let mut tokens = Lexer::new("asm { \"nop\" };")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Expression(Expression::Asm(
+                _
+            ))))
+        ));
+    }
+
+    #[test]
+    fn test_asm_is_contextual() {
+        let mut tokens = Lexer::new("asm + 1")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(matches!(
+            Expression::parse(&mut tokens),
+            Ok(AstNode::Expression(Expression::Binary(_)))
+        ));
+    }
+}
@@ -1,20 +1,33 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    lexer::{Token, Tokens},
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
     parser::{
-        ast::{AstNode, Statement},
+        ast::{AstNode, Expression, Statement},
         combinators::Comb,
         FromTokens, ParseError,
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Block {
     pub statements: Vec<Statement>,
+    /// The final expression without a semicolon, which is the value of the block (e.g., the
+    /// `y * 2` in `{ let y = 2; y * 2 }`).
+    pub tail: Option<Box<Expression>>,
+}
+
+impl Block {
+    /// `{ statements }`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE)
+    }
 }
 
 impl FromTokens<Token> for Block {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE;
+        let matcher = Self::grammar();
 
         let mut result = matcher.parse(tokens)?.into_iter();
 
@@ -24,7 +37,16 @@ impl FromTokens<Token> for Block {
             statements.push(statement);
         }
 
-        Ok(Block { statements }.into())
+        let tail = match statements.pop() {
+            Some(Statement::YieldingExpression(expression)) => Some(Box::new(expression)),
+            Some(statement) => {
+                statements.push(statement);
+                None
+            }
+            None => None,
+        };
+
+        Ok(Block { statements, tail }.into())
     }
 }
 
@@ -38,7 +60,7 @@ impl From<Block> for AstNode {
 mod tests {
     use crate::{
         lexer::Lexer,
-        parser::ast::{Expression, Id, Initialisation, Num},
+        parser::ast::{Expression, Id, Initialisation, Num, Pattern},
     };
 
     use super::*;
@@ -49,7 +71,14 @@ mod tests {
 
         let result = Block::parse(&mut tokens);
 
-        assert_eq!(Ok(Block { statements: vec![] }.into()), result)
+        assert_eq!(
+            Ok(Block {
+                statements: vec![],
+                tail: None
+            }
+            .into()),
+            result
+        )
     }
 
     #[test]
@@ -63,9 +92,8 @@ mod tests {
 
         assert_eq!(
             Ok(Block {
-                statements: vec![Statement::YieldingExpression(Expression::Id(
-                    Id("x".into())
-                ))]
+                statements: vec![],
+                tail: Some(Box::new(Expression::Id(Id("x".into()))))
             }
             .into()),
             result
@@ -88,15 +116,32 @@ mod tests {
 
         assert_eq!(
             Ok(Block {
-                statements: vec![
-                    Statement::Initialization(Initialisation {
-                        id: Id("a".into()),
-                        mutable: false,
-                        value: Expression::Num(Num::Integer(42)),
-                        type_name: None
-                    },),
-                    Statement::YieldingExpression(Expression::Id(Id("a".into())))
-                ]
+                statements: vec![Statement::Initialization(Box::new(Initialisation {
+                    pattern: Pattern::Id(Id("a".into())),
+                    mutable: false,
+                    value: Expression::Num(Num::Integer(42)),
+                    type_name: None
+                }),),],
+                tail: Some(Box::new(Expression::Id(Id("a".into()))))
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_block_without_tail() {
+        let mut tokens = Lexer::new("{ x; }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Block::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Block {
+                statements: vec![Statement::Expression(Expression::Id(Id("x".into())))],
+                tail: None
             }
             .into()),
             result
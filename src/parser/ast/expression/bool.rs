@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lexer::{Token, Tokens},
+    parser::{ast::AstNode, FromTokens, ParseError},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bool(pub bool);
+
+impl FromTokens<Token> for Bool {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError>
+    where
+        Self: Sized,
+    {
+        match tokens.next() {
+            Some(Token::TrueKeyword { .. }) => Ok(Bool(true).into()),
+            Some(Token::FalseKeyword { .. }) => Ok(Bool(false).into()),
+            Some(token) => Err(ParseError {
+                message: "Tried to parse Bool from non Bool token".into(),
+                position: Some(token.position()),
+                notes: vec![],
+            }),
+            None => Err(ParseError::eof("Bool")),
+        }
+    }
+}
+
+impl From<Bool> for AstNode {
+    fn from(value: Bool) -> Self {
+        AstNode::Bool(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mut tokens = Lexer::new("true false")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(Ok(AstNode::Bool(Bool(true))), Bool::parse(&mut tokens));
+        assert_eq!(Ok(AstNode::Bool(Bool(false))), Bool::parse(&mut tokens));
+    }
+
+    #[test]
+    fn test_parse_non_bool_fails() {
+        let mut tokens = Lexer::new("truth")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Bool::parse(&mut tokens).is_err());
+    }
+}
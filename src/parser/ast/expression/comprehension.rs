@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
+    parser::{ast::AstNode, combinators::Comb, FromTokens, ParseError},
+};
+
+use super::{Expression, Id};
+
+/// An array comprehension, e.g., `[x * x for x in xs if x > 2]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comprehension {
+    pub expression: Expression,
+    pub binding: Id,
+    pub iterable: Expression,
+    pub filter: Option<Expression>,
+}
+
+impl Comprehension {
+    /// `[expression for binding in iterable if filter]`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(
+            Comb::LBRACKET
+                >> Comb::EXPR
+                >> Comb::FOR_KEYWORD
+                >> Comb::ID
+                >> Comb::IN_KEYWORD
+                >> Comb::EXPR
+                >> !(Comb::IF_KEYWORD >> Comb::EXPR)
+                >> Comb::RBRACKET
+        )
+    }
+}
+
+impl FromTokens<Token> for Comprehension {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = Self::grammar();
+
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Expression(expression)) = result.next() else {
+            unreachable!()
+        };
+
+        let Some(AstNode::Id(binding)) = result.next() else {
+            unreachable!()
+        };
+
+        let Some(AstNode::Expression(iterable)) = result.next() else {
+            unreachable!()
+        };
+
+        let filter = result.next().map(|filter| {
+            let AstNode::Expression(filter) = filter else {
+                unreachable!()
+            };
+            filter
+        });
+
+        Ok(Comprehension {
+            expression,
+            binding,
+            iterable,
+            filter,
+        }
+        .into())
+    }
+}
+
+impl From<Comprehension> for AstNode {
+    fn from(value: Comprehension) -> Self {
+        AstNode::Comprehension(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::ast::{BinaryExpression, Num},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_simple_comprehension() {
+        let mut tokens = Lexer::new("[x * x for x in xs]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Comprehension::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Comprehension {
+                expression: Expression::Binary(Box::new(BinaryExpression::Multiplication(
+                    Expression::Id(Id("x".into())),
+                    Expression::Id(Id("x".into()))
+                ))),
+                binding: Id("x".into()),
+                iterable: Expression::Id(Id("xs".into())),
+                filter: None
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_comprehension_with_filter() {
+        let mut tokens = Lexer::new("[x for x in xs if x > 2]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Comprehension::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Comprehension {
+                expression: Expression::Id(Id("x".into())),
+                binding: Id("x".into()),
+                iterable: Expression::Id(Id("xs".into())),
+                filter: Some(Expression::Binary(Box::new(BinaryExpression::GreaterThan(
+                    Expression::Id(Id("x".into())),
+                    Expression::Num(Num::Integer(2))
+                ))))
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_comprehension_as_expression() {
+        let mut tokens = Lexer::new("[x for x in xs]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Expression(Expression::Comprehension(_)))
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+use super::{BinaryExpression, Expression, Num, Prefix};
+
+impl Expression {
+    /// Evaluate this expression at compile time, if it only consists of number literals, arithmetic
+    /// and compile-time blocks (`comptime { ... }`). Returns `None` if the expression can not be
+    /// evaluated (e.g., because it references a variable, divides by zero or overflows).
+    pub fn fold_constant(&self) -> Option<Num> {
+        match self {
+            Expression::Num(Num::Invalid(_)) => None,
+            Expression::Num(num) => Some(num.clone()),
+            Expression::Parens(inner) => inner.fold_constant(),
+            Expression::Prefix(Prefix::Minus { expr }) => expr.fold_constant()?.negate(),
+            Expression::Binary(binary) => Self::fold_binary(binary),
+            Expression::Comptime(block) | Expression::Block(block) => {
+                match (block.statements.as_slice(), &block.tail) {
+                    ([], Some(expression)) => expression.fold_constant(),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn fold_binary(binary: &BinaryExpression) -> Option<Num> {
+        let (lhs, rhs) = binary.operands();
+
+        match (lhs.fold_constant()?, rhs.fold_constant()?) {
+            (Num::FloatingPoint(lhs), Num::FloatingPoint(rhs)) => {
+                let value = match binary {
+                    BinaryExpression::Addition(_, _) => lhs + rhs,
+                    BinaryExpression::Substraction(_, _) => lhs - rhs,
+                    BinaryExpression::Multiplication(_, _) => lhs * rhs,
+                    BinaryExpression::Division(_, _) => lhs / rhs,
+                    BinaryExpression::Modulo(_, _) => lhs % rhs,
+                    BinaryExpression::Exponentiation(_, _) => lhs.powf(rhs),
+                    _ => return None,
+                };
+                // dividing by zero (or overflowing) does not yield a number
+                value.is_finite().then_some(Num::FloatingPoint(value))
+            }
+            // there are no implicit conversions between integers and floating point numbers, so
+            // both have to be integers now
+            (lhs, rhs) => {
+                let (lhs, rhs) = (lhs.as_signed()?, rhs.as_signed()?);
+
+                let value = match binary {
+                    BinaryExpression::Addition(_, _) => lhs.checked_add(rhs),
+                    BinaryExpression::Substraction(_, _) => lhs.checked_sub(rhs),
+                    BinaryExpression::Multiplication(_, _) => lhs.checked_mul(rhs),
+                    BinaryExpression::Division(_, _) => lhs.checked_div(rhs),
+                    BinaryExpression::Modulo(_, _) => lhs.checked_rem(rhs),
+                    BinaryExpression::Exponentiation(_, _) => {
+                        lhs.checked_pow(u32::try_from(rhs).ok()?)
+                    }
+                    _ => None,
+                }?;
+                Num::from_signed(value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::{ast::AstNode, FromTokens},
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Expression {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+
+        let Ok(AstNode::Expression(expression)) = Expression::parse(&mut tokens) else {
+            panic!("expected expression");
+        };
+        expression
+    }
+
+    #[test]
+    fn test_parse_comptime() {
+        assert!(matches!(parse("comptime { 42 }"), Expression::Comptime(_)));
+        assert!(matches!(parse("const { 42 }"), Expression::Comptime(_)));
+    }
+
+    #[test]
+    fn test_fold_comptime() {
+        assert_eq!(
+            Some(Num::Integer(7)),
+            parse("comptime { 1 + 2 * 3 }").fold_constant()
+        );
+        assert_eq!(
+            Some(Num::FloatingPoint(1.5)),
+            parse("const { (1.0 + 2.0) / 2.0 }").fold_constant()
+        );
+        assert_eq!(
+            Some(Num::NegativeInteger(1)),
+            parse("comptime { -7 % 3 }").fold_constant()
+        );
+        assert_eq!(
+            Some(Num::Integer(512)),
+            parse("comptime { 2 ** 3 ** 2 }").fold_constant()
+        );
+    }
+
+    #[test]
+    fn test_fold_negative_integers() {
+        assert_eq!(
+            Some(Num::NegativeInteger(1)),
+            parse("comptime { 1 - 2 }").fold_constant()
+        );
+        assert_eq!(
+            Some(Num::Integer(6)),
+            parse("comptime { -2 * -(1 + 2) }").fold_constant()
+        );
+        assert_eq!(
+            None,
+            parse("comptime { (0 - 0xFFFF_FFFF_FFFF_FFFF) - 1 }").fold_constant()
+        );
+    }
+
+    #[test]
+    fn test_fold_fails_for_non_constants() {
+        assert_eq!(None, parse("comptime { x + 1 }").fold_constant());
+        assert_eq!(None, parse("comptime { 1 / 0 }").fold_constant());
+        assert_eq!(None, parse("comptime { 1 % 0 }").fold_constant());
+        assert_eq!(None, parse("comptime { 1.0 / 0.0 }").fold_constant());
+        assert_eq!(None, parse("comptime { 0.0 % 0.0 }").fold_constant());
+        assert_eq!(None, parse("comptime { 2 ** -1 }").fold_constant());
+        assert_eq!(None, parse("comptime { 1 + 2.0 }").fold_constant());
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lexer::{Token, Tokens},
+    parser::{ast::AstNode, FromTokens, ParseError},
+};
+
+use super::Expression;
+
+/// A part of an interpolated string, e.g., `"hello {name}"` consists of the text `hello ` and
+/// the expression `name`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Segment {
+    Text(String),
+    Expression(Expression),
+}
+
+impl Segment {
+    /// Parse all segments of an interpolated string, starting at its
+    /// [`Token::FormatStringStart`]. Empty text segments are skipped.
+    pub fn parse_all(tokens: &mut Tokens<Token>) -> Result<Vec<Segment>, ParseError> {
+        let mut segments = vec![];
+
+        match tokens.next() {
+            Some(Token::FormatStringStart { value, .. }) => {
+                Segment::push_text(&mut segments, value)
+            }
+            Some(token) => {
+                return Err(ParseError {
+                    message: "Tried to parse interpolated string from non FormatStringStart token"
+                        .into(),
+                    position: Some(token.position()),
+                    notes: vec![],
+                })
+            }
+            None => return Err(ParseError::eof("interpolated string")),
+        }
+
+        loop {
+            if let Some(
+                token @ (Token::FormatStringMiddle { .. } | Token::FormatStringEnd { .. }),
+            ) = tokens.peek()
+            {
+                return Err(ParseError {
+                    message: "expected an expression between `{` and `}`".into(),
+                    position: Some(token.position()),
+                    notes: vec![],
+                });
+            }
+
+            let AstNode::Expression(expr) = Expression::parse(tokens)? else {
+                unreachable!()
+            };
+            segments.push(Segment::Expression(expr));
+
+            match tokens.next() {
+                Some(Token::FormatStringMiddle { value, .. }) => {
+                    Segment::push_text(&mut segments, value)
+                }
+                Some(Token::FormatStringEnd { value, .. }) => {
+                    Segment::push_text(&mut segments, value);
+                    return Ok(segments);
+                }
+                Some(token) => {
+                    return Err(ParseError {
+                        message: "expected `}` after the interpolated expression".into(),
+                        position: Some(token.position()),
+                        notes: vec![],
+                    })
+                }
+                None => return Err(ParseError::eof("interpolated string")),
+            }
+        }
+    }
+
+    fn push_text(segments: &mut Vec<Segment>, value: &str) {
+        if !value.is_empty() {
+            segments.push(Segment::Text(value.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::{Lexer, Position},
+        parser::ast::{BinaryExpression, Id, Num},
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<Vec<Segment>, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("should lex").into();
+
+        Segment::parse_all(&mut tokens)
+    }
+
+    #[test]
+    fn test_parse_segments() {
+        assert_eq!(
+            Ok(vec![
+                Segment::Text("hello ".into()),
+                Segment::Expression(Expression::Id(Id("name".into()))),
+                Segment::Text(", you are ".into()),
+                Segment::Expression(Expression::Binary(Box::new(BinaryExpression::Addition(
+                    Expression::Id(Id("age".into())),
+                    Expression::Num(Num::Integer(1))
+                )))),
+            ]),
+            parse(r#""hello {name}, you are {age + 1}""#)
+        );
+    }
+
+    #[test]
+    fn test_parse_only_interpolation() {
+        assert_eq!(
+            Ok(vec![Segment::Expression(Expression::String("x".into()))]),
+            parse(r#""{"x"}""#)
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_interpolation_fails() {
+        let error = parse(r#""a {} b""#).expect_err("should fail");
+
+        assert_eq!(Some(Position::new(4, 4)), error.position);
+    }
+
+    #[test]
+    fn test_parse_unfinished_interpolation_fails() {
+        let error = parse(r#""a {b c}""#).expect_err("should fail");
+
+        assert_eq!(Some(Position::new(6, 1)), error.position);
+    }
+}
@@ -1,16 +1,27 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{
-        ast::{AstNode, Statement, TypeName},
+        ast::{AstNode, Attribute, Statement, TypeName, Visibility},
         combinators::Comb,
-        FromTokens, ParseError,
+        ensure_unique, FromTokens, ParseError,
     },
 };
 
 use super::Id;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Function {
+    /// The doc comments (`/// ...`) in front of the declaration.
+    pub doc: Option<String>,
+    /// The attributes in front of the declaration, e.g., `#[inline]`.
+    pub attributes: Vec<Attribute>,
+    pub visibility: Visibility,
+    /// Whether this is a generator (`fn* name(): i32 { ... }`), which produces its values via
+    /// `yield`.
+    pub generator: bool,
     pub id: Option<Id>,
     pub parameters: Vec<Parameter>,
     pub return_type: TypeName,
@@ -19,34 +30,52 @@ pub struct Function {
 
 impl FromTokens<Token> for Function {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::FN_KEYWORD
-            >> !Comb::ID
-            >> Comb::LPAREN
-            // parameter list (optional)
-            >> (Comb::PARAMETER % Comb::COMMA)
-            >> Comb::RPAREN
-            // return type
-            >> Comb::COLON
-            >> Comb::TYPE_NAME
-            // body of the function
-            >> Comb::LBRACE
-            >> (Comb::STATEMENT ^ ())
-            >> Comb::RBRACE;
+        Self::parse_with(tokens, false)
+    }
+}
 
-        let mut result = matcher.parse(tokens)?.into_iter().peekable();
+impl Function {
+    /// Parse a function within an impl block, whose parameters may contain a `&self` receiver.
+    pub(crate) fn parse_method(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        Self::parse_with(tokens, true)
+    }
+
+    fn parse_with(tokens: &mut Tokens<Token>, receiver: bool) -> Result<AstNode, ParseError> {
+        let (doc, attributes) = Attribute::parse_with_doc(tokens)?;
+        let visibility = Visibility::parse(tokens);
+
+        Comb::FN_KEYWORD.parse(tokens)?;
 
-        let id = match result.next_if(|item| matches!(item, AstNode::Id(_))) {
+        let generator = matches!(tokens.peek(), Some(Token::Times { .. }));
+        if generator {
+            tokens.next();
+        }
+
+        // the name is optional, so a keyword would otherwise only fail at the parameters
+        if let Some(error) = tokens.peek().and_then(Id::keyword_error) {
+            return Err(error);
+        }
+
+        let matcher = lazy_comb!(!Comb::ID);
+
+        let id = match matcher.parse(tokens)?.into_iter().next() {
             Some(AstNode::Id(id)) => Some(id),
             _ => None,
         };
 
-        let mut parameters = vec![];
+        let parameters = Parameter::parse_list(tokens, receiver)?;
 
-        while let Some(AstNode::Parameter(param)) =
-            result.next_if(|item| matches!(item, AstNode::Parameter(_)))
-        {
-            parameters.push(param);
-        }
+        let matcher = lazy_comb!(
+            // return type
+            Comb::COLON
+            >> Comb::TYPE_NAME
+            // body of the function
+            >> Comb::LBRACE
+            >> (Comb::STATEMENT ^ ())
+            >> Comb::RBRACE
+        );
+
+        let mut result = matcher.parse(tokens)?.into_iter().peekable();
 
         let Some(AstNode::TypeName(return_type)) = result.next() else {
             unreachable!();
@@ -61,6 +90,10 @@ impl FromTokens<Token> for Function {
         }
 
         Ok(Function {
+            doc,
+            attributes,
+            visibility,
+            generator,
             id,
             parameters,
             return_type,
@@ -72,11 +105,11 @@ impl FromTokens<Token> for Function {
 
 impl From<Function> for AstNode {
     fn from(value: Function) -> Self {
-        AstNode::Function(value)
+        AstNode::Function(Box::new(value))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: Id,
     pub type_name: Option<TypeName>,
@@ -84,25 +117,100 @@ pub struct Parameter {
 
 impl FromTokens<Token> for Parameter {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::ID >> !(Comb::COLON >> Comb::TYPE_NAME);
-        let result = matcher.parse(tokens)?;
+        // `&self` is a receiver by reference, i.e., short for `self: &Self`
+        if Self::starts_reference_receiver(tokens) {
+            tokens.next();
+            tokens.next();
+
+            return Ok(Parameter {
+                name: Id("self".into()),
+                type_name: Some(TypeName::Reference(Box::new(TypeName::Literal(
+                    "Self".into(),
+                )))),
+            }
+            .into());
+        }
 
-        let Some(AstNode::Id(name)) = result.first() else {
+        let matcher = lazy_comb!(Comb::ID >> !(Comb::COLON >> Comb::TYPE_NAME));
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
             unreachable!()
         };
 
-        let type_name = result.get(1).map(|type_name| {
+        let type_name = result.next().map(|type_name| {
             let AstNode::TypeName(type_name) = type_name else {
                 unreachable!()
             };
-            type_name.clone()
+            type_name
         });
 
-        Ok(Parameter {
-            name: name.clone(),
-            type_name,
+        Ok(Parameter { name, type_name }.into())
+    }
+}
+
+impl Parameter {
+    fn starts_reference_receiver(tokens: &Tokens<Token>) -> bool {
+        matches!(
+            (tokens.peek(), tokens.peek_at(1)),
+            (Some(Token::Ampersand { .. }), Some(Token::Id { value, .. })) if value.as_str() == "self"
+        )
+    }
+
+    /// Whether this is a `self` receiver (i.e., `self` or `&self`).
+    pub fn is_receiver(&self) -> bool {
+        self.name.0.as_str() == "self"
+    }
+
+    /// Parse a parenthesized list of parameters, e.g., `(x: i32, y)`. Every name may only be used
+    /// once. A `&self` receiver is only allowed if `receiver` is set, i.e., for methods.
+    pub(crate) fn parse_list(
+        tokens: &mut Tokens<Token>,
+        receiver: bool,
+    ) -> Result<Vec<Parameter>, ParseError> {
+        Comb::LPAREN.parse(tokens)?;
+
+        let mut parameters = vec![];
+        let mut positions = vec![];
+
+        while let Some(token) = tokens.peek() {
+            if matches!(token, Token::RParen { .. }) {
+                break;
+            }
+            positions.push(token.position());
+
+            if !receiver && Self::starts_reference_receiver(tokens) {
+                return Err(ParseError {
+                    message: "`&self` can only be used by methods in impl blocks and traits".into(),
+                    position: Some(token.position()),
+                    notes: vec![],
+                });
+            }
+
+            let Some(AstNode::Parameter(parameter)) =
+                Comb::PARAMETER.parse(tokens)?.into_iter().next()
+            else {
+                unreachable!()
+            };
+            parameters.push(parameter);
+
+            if !matches!(tokens.peek(), Some(Token::Comma { .. })) {
+                break;
+            }
+            tokens.next();
         }
-        .into())
+
+        Comb::RPAREN.parse(tokens)?;
+
+        ensure_unique(
+            "parameter",
+            parameters
+                .iter()
+                .map(|parameter| parameter.name.0.clone())
+                .zip(positions),
+        )?;
+
+        Ok(parameters)
     }
 }
 
@@ -115,8 +223,8 @@ impl From<Parameter> for AstNode {
 #[cfg(test)]
 mod tests {
     use crate::{
-        lexer::Lexer,
-        parser::ast::{BinaryExpression, Expression},
+        lexer::{Lexer, Position},
+        parser::ast::{BinaryExpression, Expression, Postfix},
     };
 
     use super::*;
@@ -132,6 +240,10 @@ mod tests {
 
         assert_eq!(
             Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: None,
                 parameters: vec![],
                 return_type: TypeName::Literal("i32".into()),
@@ -142,6 +254,73 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_function_with_callback_param() {
+        let mut tokens = Lexer::new("fn (cb: (i32) -> void): void {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Function::parse(&mut tokens);
+
+        let Ok(AstNode::Function(function)) = result else {
+            panic!("expected function, got {result:?}");
+        };
+        assert_eq!(
+            vec![Parameter {
+                name: Id("cb".into()),
+                type_name: Some(TypeName::Fn {
+                    params: vec![TypeName::Literal("i32".into())],
+                    return_type: Box::new(TypeName::Void)
+                })
+            }],
+            function.parameters
+        );
+    }
+
+    #[test]
+    fn test_higher_order_function() {
+        let mut tokens = Lexer::new("fn apply(f: (i32) -> i32, x: i32): i32 { f(x) }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Function::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
+                id: Some(Id("apply".into())),
+                parameters: vec![
+                    Parameter {
+                        name: Id("f".into()),
+                        type_name: Some(TypeName::Fn {
+                            params: vec![TypeName::Literal("i32".into())],
+                            return_type: Box::new(TypeName::Literal("i32".into()))
+                        })
+                    },
+                    Parameter {
+                        name: Id("x".into()),
+                        type_name: Some(TypeName::Literal("i32".into()))
+                    }
+                ],
+                return_type: TypeName::Literal("i32".into()),
+                statements: vec![Statement::YieldingExpression(Expression::Postfix(
+                    Postfix::Call {
+                        expr: Box::new(Expression::Id(Id("f".into()))),
+                        type_args: vec![],
+                        args: vec![Expression::Id(Id("x".into()))]
+                    }
+                ))]
+            }
+            .into()),
+            result
+        )
+    }
+
     #[test]
     fn test_function_with_single_param() {
         let mut tokens = Lexer::new("fn (x: i32): i32 {}")
@@ -153,6 +332,10 @@ mod tests {
 
         assert_eq!(
             Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: None,
                 parameters: vec![Parameter {
                     name: Id("x".into()),
@@ -166,6 +349,34 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_function_with_optional_return_type() {
+        let mut tokens = Lexer::new("fn find(x: i32): Point? {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Function::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
+                id: Some(Id("find".into())),
+                parameters: vec![Parameter {
+                    name: Id("x".into()),
+                    type_name: Some(TypeName::Literal("i32".into()))
+                }],
+                return_type: TypeName::Optional(Box::new(TypeName::Literal("Point".into()))),
+                statements: vec![]
+            }
+            .into()),
+            result
+        )
+    }
+
     #[test]
     fn test_function_with_multiple_params() {
         let mut tokens = Lexer::new("fn (x: i32, y: i32): i32 {}")
@@ -177,6 +388,10 @@ mod tests {
 
         assert_eq!(
             Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: None,
                 parameters: vec![
                     Parameter {
@@ -207,6 +422,10 @@ mod tests {
 
         assert_eq!(
             Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: None,
                 parameters: vec![
                     Parameter {
@@ -231,6 +450,23 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_function_with_duplicate_parameters() {
+        let mut tokens = Lexer::new("fn (x: i32, y: i32, x: i32): i32 {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Function::parse(&mut tokens);
+
+        let Err(error) = result else {
+            panic!("expected error, got {result:?}");
+        };
+        assert_eq!("duplicate parameter 'x'", error.message);
+        assert_eq!(Some(Position::new(20, 1)), error.position);
+        assert_eq!(Position::new(4, 1), error.notes[0].position);
+    }
+
     #[test]
     fn test_function_with_name() {
         let mut tokens = Lexer::new("fn main(x: i32, y: i32): i32 { return x + y; }")
@@ -242,6 +478,10 @@ mod tests {
 
         assert_eq!(
             Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: Some(Id("main".into())),
                 parameters: vec![
                     Parameter {
@@ -265,4 +505,130 @@ mod tests {
             result
         )
     }
+
+    #[test]
+    fn test_function_with_keyword_as_name() {
+        let mut tokens = Lexer::new("fn match(): void {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Err(ParseError {
+                message: "keyword `match` cannot be used as a name".into(),
+                position: Some(Position::new(3, 5)),
+                notes: vec![],
+            }),
+            Function::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_function_with_multiple_return_values() {
+        let mut tokens = Lexer::new("fn divmod(a: i32, b: i32): (i32, i32) { return (a, b); }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Function::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
+                id: Some(Id("divmod".into())),
+                parameters: vec![
+                    Parameter {
+                        name: Id("a".into()),
+                        type_name: Some(TypeName::Literal("i32".into()))
+                    },
+                    Parameter {
+                        name: Id("b".into()),
+                        type_name: Some(TypeName::Literal("i32".into()))
+                    }
+                ],
+                return_type: TypeName::Tuple(vec![TypeName::Literal("i32".into()); 2]),
+                statements: vec![Statement::Return(Expression::Tuple(vec![
+                    Expression::Id(Id("a".into())),
+                    Expression::Id(Id("b".into()))
+                ]))]
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_function_with_reference_receiver() {
+        let mut tokens = Lexer::new("fn area(&self, scale: f64): f64 {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let Ok(AstNode::Function(function)) = Function::parse_method(&mut tokens) else {
+            panic!("expected function");
+        };
+
+        assert_eq!(
+            Parameter {
+                name: Id("self".into()),
+                type_name: Some(TypeName::Reference(Box::new(TypeName::Literal(
+                    "Self".into()
+                ))))
+            },
+            function.parameters[0]
+        );
+        assert!(function.parameters[0].is_receiver());
+        assert!(!function.parameters[1].is_receiver());
+    }
+
+    #[test]
+    fn test_reference_receiver_outside_of_method_fails() {
+        let mut tokens = Lexer::new("fn area(&self): f64 {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Err(ParseError {
+                message: "`&self` can only be used by methods in impl blocks and traits".into(),
+                position: Some(Position::new(8, 1)),
+                notes: vec![],
+            }),
+            Function::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_generator_function() {
+        let mut tokens = Lexer::new("fn* count(n: i32): i32 { yield n; yield n + 1; }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let Ok(AstNode::Function(function)) = Function::parse(&mut tokens) else {
+            panic!("expected function");
+        };
+
+        assert!(function.generator);
+        assert_eq!(Some(Id("count".into())), function.id);
+        assert_eq!(
+            Statement::Yield(Expression::Id(Id("n".into()))),
+            function.statements[0]
+        );
+
+        let mut tokens = Lexer::new("fn* (): i32 { yield 1; }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let Ok(AstNode::Function(function)) = Function::parse(&mut tokens) else {
+            panic!("expected function");
+        };
+
+        assert!(function.generator);
+        assert_eq!(None, function.id);
+    }
 }
@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    lexer::{Token, Tokens},
+    lexer::{Symbol, Token, Tokens},
     parser::{ast::AstNode, FromTokens, ParseError},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Id(pub String);
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Id(pub Symbol);
 
 impl FromTokens<Token> for Id {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, crate::parser::ParseError>
@@ -12,12 +14,17 @@ impl FromTokens<Token> for Id {
         Self: Sized,
     {
         let value = match tokens.next() {
-            Some(Token::Id { value, .. }) => value,
+            Some(Token::Id { value, .. }) => value.clone(),
             Some(token) => {
+                if let Some(error) = Id::keyword_error(token) {
+                    return Err(error);
+                }
+
                 return Err(ParseError {
                     message: format!("Tried to parse Id from non id token ({:?})", token),
                     position: Some(token.position()),
-                })
+                    notes: vec![],
+                });
             }
             None => return Err(ParseError::eof("Id")),
         };
@@ -25,6 +32,17 @@ impl FromTokens<Token> for Id {
     }
 }
 
+impl Id {
+    /// Error for a reserved keyword (e.g., `let`) used where a name is expected.
+    pub(crate) fn keyword_error(token: &Token) -> Option<ParseError> {
+        token.keyword().map(|keyword| ParseError {
+            message: format!("keyword `{keyword}` cannot be used as a name"),
+            position: Some(token.position()),
+            notes: vec![],
+        })
+    }
+}
+
 impl From<Id> for AstNode {
     fn from(value: Id) -> Self {
         AstNode::Id(value)
@@ -33,13 +51,15 @@ impl From<Id> for AstNode {
 
 #[cfg(test)]
 mod tests {
+    use crate::lexer::Position;
+
     use super::*;
 
     #[test]
     fn test_parse() {
         let tokens = vec![Token::Id {
             value: "some_id".into(),
-            position: 0,
+            position: Position::default(),
         }];
         assert_eq!(
             Id::parse(&mut tokens.into()),
@@ -51,7 +71,7 @@ mod tests {
     fn test_error_on_non_id() {
         let tokens = vec![Token::Integer {
             value: 3,
-            position: 0,
+            position: Position::default(),
         }];
         assert!(Id::parse(&mut tokens.into()).is_err());
     }
@@ -61,4 +81,19 @@ mod tests {
         let tokens = vec![];
         assert!(Id::parse(&mut tokens.into()).is_err());
     }
+
+    #[test]
+    fn test_error_on_keyword() {
+        let tokens = vec![Token::Let {
+            position: Position::new(4, 3),
+        }];
+        assert_eq!(
+            Id::parse(&mut tokens.into()),
+            Err(ParseError {
+                message: "keyword `let` cannot be used as a name".into(),
+                position: Some(Position::new(4, 3)),
+                notes: vec![],
+            })
+        );
+    }
 }
@@ -1,4 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::Token,
     parser::{
         ast::{AstNode, Statement},
@@ -9,24 +12,27 @@ use crate::{
 
 use super::Expression;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct If {
     pub condition: Box<Expression>,
     pub statements: Vec<Statement>,
-    pub else_statements: Vec<Statement>,
+    /// The statements of the `else` branch, which is `None` if there is no `else` at all.
+    pub else_statements: Option<Vec<Statement>>,
 }
 
 impl FromTokens<Token> for If {
     fn parse(
         tokens: &mut crate::lexer::Tokens<Token>,
     ) -> Result<crate::parser::ast::AstNode, crate::parser::ParseError> {
-        let matcher = Comb::IF_KEYWORD
-            >> Comb::LPAREN
-            >> Comb::EXPR
-            >> Comb::RPAREN
-            >> Comb::LBRACE
-            >> (Comb::STATEMENT ^ ())
-            >> Comb::RBRACE;
+        let matcher = lazy_comb!(
+            Comb::IF_KEYWORD
+                >> Comb::LPAREN
+                >> Comb::EXPR
+                >> Comb::RPAREN
+                >> Comb::LBRACE
+                >> (Comb::STATEMENT ^ ())
+                >> Comb::RBRACE
+        );
 
         let mut result = matcher.parse(tokens)?.into_iter().peekable();
 
@@ -42,18 +48,26 @@ impl FromTokens<Token> for If {
             statements.push(statement);
         }
 
-        let matcher =
-            !(Comb::ELSE_KEYWORD >> Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE);
+        let else_statements = match tokens.peek() {
+            Some(Token::ElseKeyword { .. }) => {
+                let matcher = lazy_comb!(
+                    Comb::ELSE_KEYWORD >> Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE
+                );
 
-        let mut result = matcher.parse(tokens)?.into_iter().peekable();
+                let mut result = matcher.parse(tokens)?.into_iter().peekable();
 
-        let mut else_statements = vec![];
+                let mut else_statements = vec![];
 
-        while let Some(AstNode::Statement(statement)) =
-            result.next_if(|item| matches!(item, AstNode::Statement(_)))
-        {
-            else_statements.push(statement);
-        }
+                while let Some(AstNode::Statement(statement)) =
+                    result.next_if(|item| matches!(item, AstNode::Statement(_)))
+                {
+                    else_statements.push(statement);
+                }
+
+                Some(else_statements)
+            }
+            _ => None,
+        };
 
         Ok(If {
             condition: Box::new(condition),
@@ -66,7 +80,7 @@ impl FromTokens<Token> for If {
 
 impl From<If> for AstNode {
     fn from(value: If) -> Self {
-        AstNode::If(value)
+        AstNode::If(Box::new(value))
     }
 }
 
@@ -87,7 +101,7 @@ mod tests {
             Ok(If {
                 condition: Box::new(Expression::Id(Id("x".into()))),
                 statements: vec![],
-                else_statements: vec![]
+                else_statements: None
             }
             .into()),
             If::parse(&mut tokens)
@@ -105,7 +119,7 @@ mod tests {
             Ok(If {
                 condition: Box::new(Expression::Id(Id("x".into()))),
                 statements: vec![],
-                else_statements: vec![]
+                else_statements: Some(vec![])
             }
             .into()),
             If::parse(&mut tokens)
@@ -128,7 +142,7 @@ mod tests {
                         Expression::Num(Num::Integer(4))
                     )
                 )))],
-                else_statements: vec![]
+                else_statements: None
             }
             .into()),
             If::parse(&mut tokens)
@@ -151,12 +165,12 @@ mod tests {
                         Expression::Num(Num::Integer(4))
                     )
                 )))],
-                else_statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
-                    BinaryExpression::Addition(
+                else_statements: Some(vec![Statement::YieldingExpression(Expression::Binary(
+                    Box::new(BinaryExpression::Addition(
                         Expression::Num(Num::Integer(42)),
                         Expression::Num(Num::Integer(1337))
-                    )
-                )))],
+                    ))
+                ))]),
             }
             .into()),
             If::parse(&mut tokens)
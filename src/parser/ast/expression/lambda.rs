@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{ast::AstNode, combinators::Comb, FromTokens, ParseError},
 };
 
 use super::{Expression, Parameter};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Lambda {
     pub parameters: Vec<Parameter>,
     pub expression: Box<Expression>,
@@ -13,25 +16,17 @@ pub struct Lambda {
 
 impl FromTokens<Token> for Lambda {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::BACKSLASH
-            >> Comb::LPAREN
-            // parameter list (optional)
-            >> (Comb::PARAMETER % Comb::COMMA)
-            >> Comb::RPAREN
-            >> Comb::BIG_RIGHT_ARROW
-            // return type
-            >> Comb::EXPR;
-
-        let mut result = matcher.parse(tokens)?.into_iter().peekable();
-
-        let mut parameters = vec![];
-
-        while let Some(AstNode::Parameter(param)) =
-            result.next_if(|item| matches!(item, AstNode::Parameter(_)))
-        {
-            parameters.push(param);
+        if let Some(Token::Id { .. }) = tokens.peek() {
+            return Self::parse_shorthand(tokens);
         }
 
+        Comb::BACKSLASH.parse(tokens)?;
+
+        let parameters = Parameter::parse_list(tokens, false)?;
+
+        let matcher = lazy_comb!(Comb::BIG_RIGHT_ARROW >> Comb::EXPR);
+        let mut result = matcher.parse(tokens)?.into_iter();
+
         let Some(AstNode::Expression(expression)) = result.next() else {
             unreachable!()
         };
@@ -44,6 +39,31 @@ impl FromTokens<Token> for Lambda {
     }
 }
 
+impl Lambda {
+    /// Parse a lambda with a single untyped parameter, e.g., `x => x + 1`.
+    fn parse_shorthand(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = lazy_comb!(Comb::ID >> Comb::BIG_RIGHT_ARROW >> Comb::EXPR);
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
+            unreachable!()
+        };
+
+        let Some(AstNode::Expression(expression)) = result.next() else {
+            unreachable!()
+        };
+
+        Ok(Lambda {
+            parameters: vec![Parameter {
+                name,
+                type_name: None,
+            }],
+            expression: Box::new(expression),
+        }
+        .into())
+    }
+}
+
 impl From<Lambda> for AstNode {
     fn from(value: Lambda) -> Self {
         AstNode::Lambda(value)
@@ -54,7 +74,7 @@ impl From<Lambda> for AstNode {
 mod tests {
     use crate::{
         lexer::Lexer,
-        parser::ast::{BinaryExpression, Block, Id, Num, Statement},
+        parser::ast::{BinaryExpression, Block, Id, Num},
     };
 
     use super::*;
@@ -147,13 +167,47 @@ mod tests {
                     type_name: None
                 }],
                 expression: Box::new(Expression::Block(Block {
-                    statements: vec![Statement::YieldingExpression(Expression::Id(
-                        Id("x".into())
-                    ))]
+                    statements: vec![],
+                    tail: Some(Box::new(Expression::Id(Id("x".into()))))
                 }))
             }
             .into()),
             result
         )
     }
+
+    #[test]
+    fn test_shorthand_lambda() {
+        let mut tokens = Lexer::new("x => x + 1")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Lambda::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Lambda {
+                parameters: vec![Parameter {
+                    name: Id("x".into()),
+                    type_name: None
+                }],
+                expression: Box::new(Expression::Binary(Box::new(BinaryExpression::Addition(
+                    Expression::Id(Id("x".into())),
+                    Expression::Num(Num::Integer(1))
+                ))))
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_lambda_with_duplicate_parameters() {
+        let mut tokens = Lexer::new("\\(x, x) => x")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Lambda::parse(&mut tokens).is_err());
+    }
 }
@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
+    parser::{
+        ast::{AstNode, Pattern},
+        combinators::Comb,
+        FromTokens, ParseError,
+    },
+};
+
+use super::Expression;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Match {
+    pub value: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+}
+
+impl Match {
+    /// `{ pattern => body, ... }` after `match value`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::LBRACE >> (Comb::MATCH_ARM % Comb::COMMA) >> !Comb::COMMA >> Comb::RBRACE)
+    }
+}
+
+impl FromTokens<Token> for Match {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        Comb::MATCH_KEYWORD.parse(tokens)?;
+
+        // the value is no struct initialisation, so `match s {}` has no arms
+        let value = Expression::parse_scrutinee(tokens)?;
+
+        let matcher = Self::grammar();
+
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let mut arms = vec![];
+
+        while let Some(AstNode::MatchArm(arm)) = result.next() {
+            arms.push(*arm);
+        }
+
+        Ok(Match {
+            value: Box::new(value),
+            arms,
+        }
+        .into())
+    }
+}
+
+impl From<Match> for AstNode {
+    fn from(value: Match) -> Self {
+        AstNode::Match(Box::new(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Expression,
+}
+
+impl MatchArm {
+    /// `pattern => body`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::PATTERN >> Comb::BIG_RIGHT_ARROW >> Comb::EXPR)
+    }
+}
+
+impl FromTokens<Token> for MatchArm {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = Self::grammar();
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Pattern(pattern)) = result.next() else {
+            unreachable!()
+        };
+
+        let Some(AstNode::Expression(body)) = result.next() else {
+            unreachable!()
+        };
+
+        Ok(MatchArm { pattern, body }.into())
+    }
+}
+
+impl From<MatchArm> for AstNode {
+    fn from(value: MatchArm) -> Self {
+        AstNode::MatchArm(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::ast::{Id, LiteralPattern, Num, Postfix},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parse_match_arm() {
+        let mut tokens = Lexer::new("1..=9 => 42")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = MatchArm::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(MatchArm {
+                pattern: Pattern::Range {
+                    start: LiteralPattern::Integer(1),
+                    end: LiteralPattern::Integer(9),
+                    inclusive: true
+                },
+                body: Expression::Num(Num::Integer(42))
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_match() {
+        let mut tokens = Lexer::new("match x { 0 => 1, 1..=9 => 2, \"ten\" => 3, _ => 4, }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Match::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Match {
+                value: Box::new(Expression::Id(Id("x".into()))),
+                arms: vec![
+                    MatchArm {
+                        pattern: Pattern::Literal(LiteralPattern::Integer(0)),
+                        body: Expression::Num(Num::Integer(1))
+                    },
+                    MatchArm {
+                        pattern: Pattern::Range {
+                            start: LiteralPattern::Integer(1),
+                            end: LiteralPattern::Integer(9),
+                            inclusive: true
+                        },
+                        body: Expression::Num(Num::Integer(2))
+                    },
+                    MatchArm {
+                        pattern: Pattern::Literal(LiteralPattern::String("ten".into())),
+                        body: Expression::Num(Num::Integer(3))
+                    },
+                    MatchArm {
+                        pattern: Pattern::Wildcard,
+                        body: Expression::Num(Num::Integer(4))
+                    },
+                ]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_match_with_binding_and_string_arms() {
+        let mut tokens = Lexer::new("match x { 0 => \"zero\", n => f(n) }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Match::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Match {
+                value: Box::new(Expression::Id(Id("x".into()))),
+                arms: vec![
+                    MatchArm {
+                        pattern: Pattern::Literal(LiteralPattern::Integer(0)),
+                        body: Expression::String("zero".into())
+                    },
+                    MatchArm {
+                        pattern: Pattern::Id(Id("n".into())),
+                        body: Expression::Postfix(Postfix::Call {
+                            expr: Box::new(Expression::Id(Id("f".into()))),
+                            type_args: vec![],
+                            args: vec![Expression::Id(Id("n".into()))]
+                        })
+                    },
+                ]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_match_on_bool() {
+        let mut tokens = Lexer::new("match b { true => 1, false => 0 }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Match::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Match {
+                value: Box::new(Expression::Id(Id("b".into()))),
+                arms: vec![
+                    MatchArm {
+                        pattern: Pattern::Literal(LiteralPattern::Bool(true)),
+                        body: Expression::Num(Num::Integer(1))
+                    },
+                    MatchArm {
+                        pattern: Pattern::Literal(LiteralPattern::Bool(false)),
+                        body: Expression::Num(Num::Integer(0))
+                    },
+                ]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_match_as_expression() {
+        let mut tokens = Lexer::new("match c { 'a'..'z' => 1 }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Expression(Expression::Match(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_match_with_binding() {
+        let mut tokens = Lexer::new("match x { n @ 1..=9 => n, _ => 0 }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Match::parse(&mut tokens);
+
+        let Ok(AstNode::Match(match_expression)) = result else {
+            panic!("expected match, got {result:?}");
+        };
+        assert_eq!(
+            MatchArm {
+                pattern: Pattern::Binding {
+                    name: Id("n".into()),
+                    pattern: Box::new(Pattern::Range {
+                        start: LiteralPattern::Integer(1),
+                        end: LiteralPattern::Integer(9),
+                        inclusive: true
+                    })
+                },
+                body: Expression::Id(Id("n".into()))
+            },
+            match_expression.arms[0]
+        );
+    }
+
+    #[test]
+    fn test_parse_match_without_arms() {
+        let parse = |input: &str| {
+            let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+            Match::parse(&mut tokens)
+        };
+
+        assert_eq!(
+            Ok(Match {
+                value: Box::new(Expression::Id(Id("s".into()))),
+                arms: vec![]
+            }
+            .into()),
+            parse("match s {}")
+        );
+
+        // a parenthesized struct initialisation is still fine
+        let Ok(AstNode::Match(match_expression)) = parse("match (Unit {}) {}") else {
+            panic!("expected match");
+        };
+        assert!(matches!(
+            *match_expression.value,
+            Expression::Parens(ref value) if matches!(**value, Expression::StructInitialisation(_))
+        ));
+        assert!(match_expression.arms.is_empty());
+    }
+}
@@ -1,155 +1,547 @@
 mod array;
+mod asm;
 mod binary;
 mod block;
+mod bool;
+mod comprehension;
+mod comptime;
+mod format_string;
 mod function;
 mod id;
 mod if_expression;
 mod lambda;
+mod match_expression;
 mod num;
+mod operator;
 mod postfix;
 mod prefix;
+mod regex_literal;
 mod struct_initialisation;
 
 pub use self::array::*;
+pub use self::asm::*;
 pub use self::binary::*;
 pub use self::block::*;
+pub use self::bool::*;
+pub use self::comprehension::*;
+pub use self::format_string::*;
 pub use self::function::*;
 pub use self::id::*;
 pub use self::if_expression::*;
 pub use self::lambda::*;
+pub use self::match_expression::*;
 pub use self::num::*;
 pub use self::postfix::*;
 pub use self::prefix::*;
+pub use self::regex_literal::*;
 pub use self::struct_initialisation::*;
 
-use crate::lexer::Tokens;
+use serde::{Deserialize, Serialize};
+
+use crate::lazy_comb;
+use crate::lexer::{Position, Tokens};
 use crate::parser::combinators::Comb;
 use crate::{
     lexer::Token,
-    parser::{FromTokens, ParseError},
+    parser::{stats, FromTokens, Note, ParseError},
 };
 
-use super::AstNode;
+use self::operator::{Associativity, Operation, Operator};
+
+use super::{AstNode, TypeName};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Expression {
+    /// `()`, the only value of type `void`. Within expressions, `void` is an ordinary identifier.
+    Unit,
     Id(Id),
     Num(Num),
-    Function(Function),
+    Bool(Bool),
+    /// A string literal (with all escape sequences already decoded).
+    String(String),
+    /// A string with interpolated expressions, e.g., `"hello {name}"`.
+    FormatString(Vec<Segment>),
+    Function(Box<Function>),
     Lambda(Lambda),
-    If(If),
+    If(Box<If>),
     Block(Block),
     Parens(Box<Expression>),
     Postfix(Postfix),
     Prefix(Prefix),
     Binary(Box<BinaryExpression>),
+    /// `lhs && rhs`, which short-circuits (i.e., `rhs` is only evaluated if `lhs` holds).
+    LazyAnd {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+    /// `lhs || rhs`, which short-circuits (i.e., `rhs` is only evaluated if `lhs` does not hold).
+    LazyOr {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+    /// `value |> function`, which calls `function` with `value` (as its first argument, if
+    /// `function` is a call itself, e.g., `xs |> map(f)`).
+    Pipe {
+        value: Box<Expression>,
+        function: Box<Expression>,
+    },
     Array(Array),
-    StructInitialisation(StructInitialisation),
+    StructInitialisation(Box<StructInitialisation>),
+    Match(Box<Match>),
+    Comprehension(Box<Comprehension>),
+    Tuple(Vec<Expression>),
+    /// `start..end`, or `start..=end` if the end is `inclusive`.
+    Range {
+        start: Box<Expression>,
+        end: Box<Expression>,
+        inclusive: bool,
+    },
+    /// `condition ? then_branch : else_branch`, which binds weaker than any other operator.
+    Ternary {
+        condition: Box<Expression>,
+        then_branch: Box<Expression>,
+        else_branch: Box<Expression>,
+    },
+    /// `expr as target`, which binds as strong as a postfix operator.
+    Cast {
+        expr: Box<Expression>,
+        target: Box<TypeName>,
+    },
+    /// `expr[start..end]` (or `expr[start..=end]`), i.e., indexing with a range.
+    Slice {
+        expr: Box<Expression>,
+        start: Box<Expression>,
+        end: Box<Expression>,
+        inclusive: bool,
+    },
+    /// `a::b::c`, e.g., an item of a module (`geometry::origin`) or a variant of an enum
+    /// (`Shape::Circle`). Which one it is can only be decided by the semantic analysis.
+    Path(Vec<Id>),
+    /// `...expr` within an array literal or the arguments of a call, which inserts all elements
+    /// of `expr` at its place.
+    Spread(Box<Expression>),
+    /// `expr?`, which propagates an error (or an absent value) of `expr` to the caller.
+    Try(Box<Expression>),
+    Regex(RegexLiteral),
+    Asm(Box<Asm>),
+    /// `comptime { ... }` (or `const { ... }`), which is evaluated at compile time.
+    Comptime(Block),
 }
 
 impl FromTokens<Token> for Expression {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        Ok(Self::parse_operators(tokens, 0, true)?.into())
+    }
+}
+
+impl Expression {
+    /// Parse an expression whose infix operators have at least the given precedence (by
+    /// precedence climbing, see [`Operator::of`]).
+    ///
+    /// Without `struct_init`, `<id> {}` is no struct initialisation (e.g., the scrutinee in
+    /// `match s {}`), unless it is enclosed in delimiters.
+    fn parse_operators(
+        tokens: &mut Tokens<Token>,
+        min_precedence: u8,
+        struct_init: bool,
+    ) -> Result<Expression, ParseError> {
+        let mut lhs = Self::parse_operand(tokens, struct_init)?;
+
+        // the last operator combined on this level (with its position), operators with a higher
+        // precedence were already combined within the right operand
+        let mut previous: Option<(u8, Position)> = None;
+
+        while let Some(token) = tokens.peek() {
+            let Some(operator) = Operator::of(token) else {
+                break;
+            };
+
+            if operator.precedence < min_precedence {
+                break;
+            }
+
+            let position = token.position();
+
+            if let (Associativity::None(message), Some((precedence, first))) =
+                (operator.associativity, previous)
+            {
+                if precedence == operator.precedence {
+                    return Err(ParseError {
+                        message: message.into(),
+                        position: Some(position),
+                        notes: vec![Note {
+                            message: "chained with this operator".into(),
+                            position: first,
+                        }],
+                    });
+                }
+            }
+
+            tokens.next();
+
+            lhs = match operator.operation {
+                Operation::Binary(operation) => Expression::Binary(Box::new(operation(
+                    lhs,
+                    Self::parse_operators(tokens, operator.right_precedence(), struct_init)?,
+                ))),
+                Operation::LazyAnd => Expression::LazyAnd {
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(Self::parse_operators(
+                        tokens,
+                        operator.right_precedence(),
+                        struct_init,
+                    )?),
+                },
+                Operation::LazyOr => Expression::LazyOr {
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(Self::parse_operators(
+                        tokens,
+                        operator.right_precedence(),
+                        struct_init,
+                    )?),
+                },
+                Operation::Pipe => Expression::Pipe {
+                    value: Box::new(lhs),
+                    function: Box::new(Self::parse_operators(
+                        tokens,
+                        operator.right_precedence(),
+                        struct_init,
+                    )?),
+                },
+                Operation::Range { inclusive } => Expression::Range {
+                    start: Box::new(lhs),
+                    end: Box::new(Self::parse_operators(
+                        tokens,
+                        operator.right_precedence(),
+                        struct_init,
+                    )?),
+                    inclusive,
+                },
+                Operation::Ternary => {
+                    // the `?` and `:` enclose the `then_branch` just like parentheses
+                    let then_branch = Self::parse_operators(tokens, 0, struct_init)?;
+                    Comb::COLON.parse(tokens)?;
+                    let else_branch =
+                        Self::parse_operators(tokens, operator.right_precedence(), struct_init)?;
+
+                    Expression::Ternary {
+                        condition: Box::new(lhs),
+                        then_branch: Box::new(then_branch),
+                        else_branch: Box::new(else_branch),
+                    }
+                }
+            };
+
+            previous = Some((operator.precedence, position));
+        }
+
+        Ok(lhs)
+    }
+
+    /// Parse a single operand of an infix operator, i.e., an expression with its prefix and
+    /// postfix operators.
+    fn parse_operand(
+        tokens: &mut Tokens<Token>,
+        struct_init: bool,
+    ) -> Result<Expression, ParseError> {
         let mut expr = match tokens.peek() {
             Some(Token::LParen { .. }) => {
-                let matcher = Comb::LPAREN >> Comb::EXPR >> Comb::RPAREN;
-                let result = matcher.parse(tokens)?;
-                let expr = match result.first() {
-                    Some(AstNode::Expression(rhs)) => rhs.clone(),
-                    None | Some(_) => unreachable!(),
-                };
-                Expression::Parens(Box::new(expr))
+                let matcher = lazy_comb!(Comb::LPAREN >> (Comb::ELEMENT % Comb::COMMA));
+                let mut values = matcher
+                    .parse(tokens)?
+                    .into_iter()
+                    .map(|value| {
+                        let AstNode::Expression(value) = value else {
+                            unreachable!()
+                        };
+                        value
+                    })
+                    .collect::<Vec<_>>();
+
+                let trailing_comma =
+                    !values.is_empty() && matches!(tokens.peek(), Some(Token::Comma { .. }));
+                if trailing_comma {
+                    tokens.next();
+                }
+                Comb::RPAREN.parse(tokens)?;
+
+                // a single parenthesized expression is not a tuple (unless it is spread or has a
+                // trailing comma, e.g., `(a,)`)
+                match values.pop() {
+                    Some(expr)
+                        if values.is_empty()
+                            && !trailing_comma
+                            && !matches!(expr, Expression::Spread(_)) =>
+                    {
+                        Expression::Parens(Box::new(expr))
+                    }
+                    Some(expr) => {
+                        values.push(expr);
+                        Expression::Tuple(values)
+                    }
+                    None => Expression::Unit,
+                }
             }
-            Some(Token::Minus { .. }) => {
-                let matcher = Comb::MINUS >> Comb::EXPR;
-                let result = matcher.parse(tokens)?;
+            Some(Token::StringLiteral { value, .. }) => {
+                let value = value.clone();
+                tokens.next();
+
+                Expression::String(value)
+            }
+            Some(Token::FormatStringStart { .. }) => {
+                Expression::FormatString(Segment::parse_all(tokens)?)
+            }
+            Some(Token::Id { .. })
+                if matches!(
+                    (tokens.peek_at(1), tokens.peek_at(2)),
+                    (Some(Token::DoubleColon { .. }), Some(Token::Id { .. }))
+                ) =>
+            {
+                let start = tokens.get_index();
+
+                let matcher = lazy_comb!(Comb::ID % Comb::DOUBLE_COLON);
+                let path = matcher
+                    .parse(tokens)?
+                    .into_iter()
+                    .map(|segment| {
+                        let AstNode::Id(segment) = segment else {
+                            unreachable!()
+                        };
+                        segment
+                    })
+                    .collect::<Vec<_>>();
+
+                // a variant with a struct payload is initialised like a struct, e.g.,
+                // `Shape::Rect { w: 1.0, h: 2.0 }`
+                match (tokens.peek(), tokens.peek_at(1), tokens.peek_at(2)) {
+                    (
+                        Some(Token::LBrace { .. }),
+                        Some(Token::Id { .. }),
+                        Some(Token::Colon { .. }),
+                    )
+                    | (Some(Token::LBrace { .. }), Some(Token::RBrace { .. }), _)
+                        if struct_init
+                            || !matches!(tokens.peek_at(1), Some(Token::RBrace { .. })) =>
+                    {
+                        tokens.set_index(start);
+                        stats::backtrack();
+
+                        let Some(AstNode::StructInitialisation(initialisation)) =
+                            Comb::STRUCT_INITILISATION.parse(tokens)?.into_iter().next()
+                        else {
+                            unreachable!()
+                        };
+                        Expression::StructInitialisation(initialisation)
+                    }
+                    _ => Expression::Path(path),
+                }
+            }
+            Some(Token::IfKeyword { position }) => {
+                let position = *position;
 
-                let Some(AstNode::Expression(expr)) = result.first() else {
+                let Some(AstNode::If(if_expression)) = Comb::IF.parse(tokens)?.into_iter().next()
+                else {
                     unreachable!();
                 };
 
-                Expression::Prefix(Prefix::Minus {
-                    expr: Box::new(expr.clone()),
-                })
+                // without an `else`, there would be no value if the condition does not hold
+                if if_expression.else_statements.is_none() {
+                    return Err(ParseError {
+                        message: "`if` used as an expression needs an `else` branch".into(),
+                        position: Some(position),
+                        notes: vec![],
+                    });
+                }
+
+                Expression::If(if_expression)
             }
-            Some(Token::ExclamationMark { .. }) => {
-                let matcher = Comb::EXCLAMATION_MARK >> Comb::EXPR;
-                let result = matcher.parse(tokens)?;
+            Some(Token::ComptimeKeyword { .. } | Token::Const { .. }) => {
+                let matcher =
+                    lazy_comb!((Comb::COMPTIME_KEYWORD | Comb::CONST_KEYWORD) >> Comb::BLOCK);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let Some(AstNode::Expression(expr)) = result.first() else {
+                let Some(AstNode::Block(block)) = result.next() else {
                     unreachable!();
                 };
 
-                Expression::Prefix(Prefix::Negation {
-                    expr: Box::new(expr.clone()),
-                })
+                Expression::Comptime(block)
             }
-            _ => {
-                let matcher = Comb::FUNCTION
-                    | Comb::IF
-                    | Comb::NUM
-                    | Comb::STRUCT_INITILISATION
-                    | Comb::ID
-                    | Comb::LAMBDA
-                    | Comb::BLOCK
-                    | Comb::ARRAY;
-                let result = matcher.parse(tokens)?;
-                match result.first() {
-                    Some(AstNode::Id(id)) => Expression::Id(id.clone()),
-                    Some(AstNode::Num(num)) => Expression::Num(num.clone()),
-                    Some(AstNode::Function(func)) => {
-                        return Ok(Expression::Function(func.clone()).into())
+            Some(Token::Minus { .. }) => {
+                tokens.next();
+                let expr = Box::new(Self::parse_operators(
+                    tokens,
+                    Operator::POWER_PRECEDENCE,
+                    struct_init,
+                )?);
+
+                return Ok(Prefix::Minus { expr }.fold_literal());
+            }
+            Some(Token::Tilde { .. }) => {
+                tokens.next();
+                let expr = Box::new(Self::parse_operators(
+                    tokens,
+                    Operator::POWER_PRECEDENCE,
+                    struct_init,
+                )?);
+
+                return Ok(Prefix::BitNot { expr }.fold_literal());
+            }
+            Some(Token::ExclamationMark { .. }) => {
+                tokens.next();
+                let expr = Box::new(Self::parse_operators(
+                    tokens,
+                    Operator::POWER_PRECEDENCE,
+                    struct_init,
+                )?);
+
+                return Ok(Prefix::Negation { expr }.fold_literal());
+            }
+            Some(token) => {
+                // select the rule based on the first token, so only `<id> {}` needs to backtrack
+                // (it might be an empty struct initialisation or just an identifier)
+                let matcher = match token {
+                    Token::FnKeyword { .. } => &Comb::FUNCTION,
+                    Token::MatchKeyword { .. } => &Comb::MATCH,
+                    Token::Integer { .. }
+                    | Token::FloatingPoint { .. }
+                    | Token::InvalidNumber { .. } => &Comb::NUM,
+                    Token::TrueKeyword { .. } | Token::FalseKeyword { .. } => &Comb::BOOL,
+                    Token::Id { .. } if Asm::starts(tokens) => &Comb::ASM,
+                    Token::Id { .. } => match tokens.peek_at(1) {
+                        // `<id> { <field>: ...` can only be a struct initialisation, while e.g.
+                        // the `{` in `match x { 1 => 2 }` starts something else
+                        Some(Token::LBrace { .. }) => {
+                            match (tokens.peek_at(2), tokens.peek_at(3)) {
+                                (Some(Token::Id { .. }), Some(Token::Colon { .. })) => {
+                                    &Comb::STRUCT_INITILISATION
+                                }
+                                (Some(Token::RBrace { .. }), _) if struct_init => {
+                                    lazy_comb!(Comb::STRUCT_INITILISATION | Comb::ID)
+                                }
+                                _ => &Comb::ID,
+                            }
+                        }
+                        Some(Token::BigRightArrow { .. }) => &Comb::LAMBDA,
+                        _ => &Comb::ID,
+                    },
+                    Token::RegexLiteral { .. } => &Comb::REGEX_LITERAL,
+                    Token::Backslash { .. } => &Comb::LAMBDA,
+                    Token::LBrace { .. } => &Comb::BLOCK,
+                    Token::LBracket { .. } => lazy_comb!(Comb::ARRAY | Comb::COMPREHENSION),
+                    token => {
+                        return Err(ParseError {
+                            message: format!(
+                                "Unexpected {token:?} while trying to parse expression"
+                            ),
+                            position: Some(token.position()),
+                            notes: vec![],
+                        })
                     }
-                    Some(AstNode::Lambda(lambda)) => {
-                        return Ok(Expression::Lambda(lambda.clone()).into())
+                };
+                let mut result = matcher.parse(tokens)?.into_iter();
+                match result.next() {
+                    Some(AstNode::Id(id)) => Expression::Id(id),
+                    Some(AstNode::Num(num)) => Expression::Num(num),
+                    Some(AstNode::Bool(bool)) => Expression::Bool(bool),
+                    Some(AstNode::RegexLiteral(regex)) => Expression::Regex(regex),
+                    Some(AstNode::Asm(asm)) => Expression::Asm(asm),
+                    // a function expression might be invoked immediately, so it continues with the
+                    // postfix operators (unlike a lambda, whose body would already swallow them)
+                    Some(AstNode::Function(func)) => Expression::Function(func),
+                    Some(AstNode::Lambda(lambda)) => return Ok(Expression::Lambda(lambda)),
+                    Some(AstNode::Match(match_expression)) => Expression::Match(match_expression),
+                    Some(AstNode::Block(block)) => Expression::Block(block),
+                    Some(AstNode::Array(array)) => Expression::Array(array),
+                    Some(AstNode::Comprehension(comprehension)) => {
+                        Expression::Comprehension(comprehension)
                     }
-                    Some(AstNode::If(if_expression)) => Expression::If(if_expression.clone()),
-                    Some(AstNode::Block(block)) => Expression::Block(block.clone()),
-                    Some(AstNode::Array(array)) => Expression::Array(array.clone()),
                     Some(AstNode::StructInitialisation(initialisation)) => {
-                        Expression::StructInitialisation(initialisation.clone())
+                        Expression::StructInitialisation(initialisation)
                     }
                     None | Some(_) => unreachable!(),
                 }
             }
+            None => return Err(ParseError::eof("expression")),
         };
 
         loop {
             let Some(next) = tokens.peek() else {
-                return Ok(expr.into());
+                return Ok(expr);
             };
 
             match next {
                 Token::LParen { .. } => {
-                    expr = Expression::Postfix(Self::parse_call(expr, tokens)?);
+                    expr = Expression::Postfix(Self::parse_call(expr, vec![], tokens)?);
+                    continue;
+                }
+                Token::DoubleColon { .. }
+                    if matches!(tokens.peek_at(1), Some(Token::LessThan { .. })) =>
+                {
+                    tokens.next();
+                    let type_args = TypeName::parse_generic_arguments(tokens)?;
+                    expr = Expression::Postfix(Self::parse_call(expr, type_args, tokens)?);
                     continue;
                 }
                 Token::LBracket { .. } => {
-                    expr = Expression::Postfix(Self::parse_index(expr, tokens)?);
+                    expr = Self::parse_index(expr, tokens)?;
                     continue;
                 }
                 Token::Dot { .. } => {
-                    expr = Expression::Postfix(Self::parse_property_access(expr, tokens)?);
+                    expr = Expression::Postfix(Self::parse_member_access(expr, tokens)?);
                     continue;
                 }
-                Token::Plus { .. }
-                | Token::Minus { .. }
-                | Token::Times { .. }
-                | Token::Equal { .. }
-                | Token::GreaterThan { .. }
-                | Token::LessThan { .. }
-                | Token::GreaterOrEqual { .. }
-                | Token::LessOrEqual { .. } => {
-                    return Ok(Self::parse_binary(expr, tokens)?.into());
+                Token::AsKeyword { .. } => {
+                    expr = Self::parse_cast(expr, tokens)?;
+                    continue;
+                }
+                // a `?` might also start the branches of a ternary (e.g., `a ? b : c`)
+                Token::QuestionMark { .. } => {
+                    if Self::starts_ternary(tokens, struct_init) {
+                        return Ok(expr);
+                    }
+
+                    tokens.next();
+                    expr = Expression::Try(Box::new(expr));
+                    continue;
                 }
-                _ => return Ok(expr.into()),
+                _ => return Ok(expr),
             };
         }
     }
-}
 
-impl Expression {
-    fn parse_call(expr: Expression, tokens: &mut Tokens<Token>) -> Result<Postfix, ParseError> {
-        let matcher = Comb::LPAREN >> (Comb::EXPR % Comb::COMMA) >> Comb::RPAREN;
+    /// Parse the scrutinee of a `match`, where `<id> {}` starts the arms instead of being an
+    /// empty struct initialisation.
+    pub(crate) fn parse_scrutinee(tokens: &mut Tokens<Token>) -> Result<Expression, ParseError> {
+        Self::parse_operators(tokens, 0, false)
+    }
+
+    /// Parse an expression which might be spread, see [`Comb::ELEMENT`].
+    pub(crate) fn parse_element(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        if let Some(Token::DotDotDot { .. }) = tokens.peek() {
+            tokens.next();
+
+            return Ok(
+                Expression::Spread(Box::new(Self::parse_operators(tokens, 0, true)?)).into(),
+            );
+        }
+
+        Self::parse(tokens)
+    }
+
+    fn parse_call(
+        expr: Expression,
+        type_args: Vec<TypeName>,
+        tokens: &mut Tokens<Token>,
+    ) -> Result<Postfix, ParseError> {
+        Ok(Postfix::Call {
+            expr: Box::new(expr),
+            type_args,
+            args: Self::parse_arguments(tokens)?,
+        })
+    }
+
+    fn parse_arguments(tokens: &mut Tokens<Token>) -> Result<Vec<Expression>, ParseError> {
+        let matcher = lazy_comb!(Comb::LPAREN >> (Comb::ELEMENT % Comb::COMMA) >> Comb::RPAREN);
 
         let result = matcher.parse(tokens)?.into_iter();
 
@@ -163,70 +555,158 @@ impl Expression {
             args.push(arg);
         }
 
-        Ok(Postfix::Call {
-            expr: Box::new(expr),
-            args,
-        })
+        Ok(args)
     }
 
-    fn parse_index(expr: Expression, tokens: &mut Tokens<Token>) -> Result<Postfix, ParseError> {
-        let matcher = Comb::LBRACKET >> Comb::EXPR >> Comb::RBRACKET;
+    /// Parse `as <type>`. A trailing `?` followed by another expression belongs to a ternary
+    /// instead of the type, e.g., `x as bool ? a : b` is `(x as bool) ? a : b`.
+    fn parse_cast(expr: Expression, tokens: &mut Tokens<Token>) -> Result<Expression, ParseError> {
+        let matcher = lazy_comb!(Comb::AS_KEYWORD >> Comb::TYPE_NAME);
+        let mut result = matcher.parse(tokens)?.into_iter();
 
-        let result = matcher.parse(tokens)?;
-
-        let Some(AstNode::Expression(index)) = result.first().cloned() else {
+        let Some(AstNode::TypeName(mut target)) = result.next() else {
             unreachable!()
         };
 
-        Ok(Postfix::Index {
+        if let TypeName::Optional(inner) = target {
+            match tokens.peek().is_some_and(Self::starts_operand) {
+                true => {
+                    // the `?` was the last token of the type
+                    tokens.set_index(tokens.get_index() - 1);
+                    target = *inner;
+                }
+                false => target = TypeName::Optional(inner),
+            }
+        }
+
+        Ok(Expression::Cast {
             expr: Box::new(expr),
-            index: Box::new(index),
+            target: Box::new(target),
         })
     }
 
-    fn parse_property_access(
-        expr: Expression,
-        tokens: &mut Tokens<Token>,
-    ) -> Result<Postfix, ParseError> {
-        let matcher = Comb::DOT >> Comb::ID;
+    /// Whether the token can start an operand of a binary operator. A `{` is left out, since it
+    /// rather starts the body after the condition of an `if` or `while`.
+    fn starts_operand(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Id { .. }
+                | Token::Integer { .. }
+                | Token::FloatingPoint { .. }
+                | Token::InvalidNumber { .. }
+                | Token::StringLiteral { .. }
+                | Token::CharLiteral { .. }
+                | Token::RegexLiteral { .. }
+                | Token::FormatStringStart { .. }
+                | Token::TrueKeyword { .. }
+                | Token::FalseKeyword { .. }
+                | Token::LParen { .. }
+                | Token::LBracket { .. }
+                | Token::Minus { .. }
+                | Token::ExclamationMark { .. }
+                | Token::Tilde { .. }
+                | Token::Backslash { .. }
+                | Token::IfKeyword { .. }
+                | Token::FnKeyword { .. }
+                | Token::MatchKeyword { .. }
+                | Token::ComptimeKeyword { .. }
+                | Token::Const { .. }
+        )
+    }
+
+    /// Whether the next `?` starts the branches of a ternary instead of being a try. If the token
+    /// after it might also continue a try (e.g., `f()? - 1` or `f()?[0]`), it is only a ternary
+    /// if an expression and a `:` follow.
+    fn starts_ternary(tokens: &mut Tokens<Token>, struct_init: bool) -> bool {
+        match tokens.peek_at(1) {
+            Some(Token::LParen { .. } | Token::LBracket { .. } | Token::Minus { .. }) => {}
+            Some(token) => return Self::starts_operand(token),
+            None => return false,
+        }
+
+        let index = tokens.get_index();
+        tokens.next();
+        let ternary = Self::parse_operators(tokens, 0, struct_init).is_ok()
+            && matches!(tokens.peek(), Some(Token::Colon { .. }));
+        tokens.set_index(index);
+        stats::backtrack();
 
-        let result = matcher.parse(tokens)?;
+        ternary
+    }
+
+    /// Parse `[index]`, which is a slice if the index is a (non-parenthesized) range.
+    fn parse_index(expr: Expression, tokens: &mut Tokens<Token>) -> Result<Expression, ParseError> {
+        let matcher = lazy_comb!(Comb::LBRACKET >> Comb::EXPR >> Comb::RBRACKET);
 
-        let Some(AstNode::Id(property)) = result.first().cloned() else {
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Expression(index)) = result.next() else {
             unreachable!()
         };
 
-        Ok(Postfix::PropertyAccess {
-            expr: Box::new(expr),
-            property,
+        Ok(match index {
+            Expression::Range {
+                start,
+                end,
+                inclusive,
+            } => Expression::Slice {
+                expr: Box::new(expr),
+                start,
+                end,
+                inclusive,
+            },
+            index => Expression::Postfix(Postfix::Index {
+                expr: Box::new(expr),
+                index: Box::new(index),
+            }),
         })
     }
 
-    fn parse_binary(lhs: Expression, tokens: &mut Tokens<Token>) -> Result<Expression, ParseError> {
-        let Some(operation) = tokens.next() else {
-            unreachable!()
-        };
+    /// Parse `.property`, which might be followed by arguments (and explicit generic arguments)
+    /// to make it a method call, or `.index` for accessing a tuple element.
+    fn parse_member_access(
+        expr: Expression,
+        tokens: &mut Tokens<Token>,
+    ) -> Result<Postfix, ParseError> {
+        if let Some(Token::Integer { value, .. }) = tokens.peek_at(1) {
+            let index = *value;
+            tokens.next();
+            tokens.next();
+
+            return Ok(Postfix::TupleAccess {
+                expr: Box::new(expr),
+                index,
+            });
+        }
+
+        let matcher = lazy_comb!(Comb::DOT >> Comb::ID);
+
+        let mut result = matcher.parse(tokens)?.into_iter();
 
-        let matcher = Comb::EXPR;
-        let result = matcher.parse(tokens)?;
-        let rhs = match result.first() {
-            Some(AstNode::Expression(rhs)) => rhs.clone(),
-            None | Some(_) => unreachable!(),
+        let Some(AstNode::Id(property)) = result.next() else {
+            unreachable!()
         };
 
-        let binary = match operation {
-            Token::Plus { .. } => BinaryExpression::Addition(lhs, rhs),
-            Token::Minus { .. } => BinaryExpression::Substraction(lhs, rhs),
-            Token::Times { .. } => BinaryExpression::Multiplication(lhs, rhs),
-            Token::Equal { .. } => BinaryExpression::Equal(lhs, rhs),
-            Token::GreaterThan { .. } => BinaryExpression::GreaterThan(lhs, rhs),
-            Token::LessThan { .. } => BinaryExpression::LessThen(lhs, rhs),
-            Token::GreaterOrEqual { .. } => BinaryExpression::GreaterOrEqual(lhs, rhs),
-            Token::LessOrEqual { .. } => BinaryExpression::LessOrEqual(lhs, rhs),
-            _ => unreachable!(),
+        let type_args = match (tokens.peek(), tokens.peek_at(1)) {
+            (Some(Token::DoubleColon { .. }), Some(Token::LessThan { .. })) => {
+                tokens.next();
+                TypeName::parse_generic_arguments(tokens)?
+            }
+            (Some(Token::LParen { .. }), _) => vec![],
+            _ => {
+                return Ok(Postfix::PropertyAccess {
+                    expr: Box::new(expr),
+                    property,
+                })
+            }
         };
 
-        Ok(Expression::Binary(Box::new(binary.balance())))
+        Ok(Postfix::MethodCall(Box::new(MethodCall {
+            receiver: expr,
+            method: property,
+            type_args,
+            args: Self::parse_arguments(tokens)?,
+        })))
     }
 }
 
@@ -239,8 +719,8 @@ impl From<Expression> for AstNode {
 #[cfg(test)]
 mod tests {
     use crate::{
-        lexer::Lexer,
-        parser::ast::{Statement, TypeName},
+        lexer::{Lexer, Position},
+        parser::ast::{Statement, TypeName, Visibility},
     };
 
     use super::*;
@@ -249,7 +729,7 @@ mod tests {
     fn test_parse_id() {
         let tokens = vec![Token::Id {
             value: "some_id".into(),
-            position: 0,
+            position: Position::default(),
         }];
 
         assert_eq!(
@@ -262,7 +742,7 @@ mod tests {
     fn test_parse_num() {
         let tokens = vec![Token::Integer {
             value: 42,
-            position: 0,
+            position: Position::default(),
         }];
 
         assert_eq!(
@@ -281,12 +761,16 @@ mod tests {
         let result = Expression::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Expression::Function(Function {
+            Ok(Expression::Function(Box::new(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: None,
                 parameters: vec![],
                 statements: vec![],
                 return_type: TypeName::Literal("i32".into())
-            })
+            }))
             .into()),
             result
         )
@@ -306,7 +790,11 @@ mod tests {
         let result = Expression::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Expression::Function(Function {
+            Ok(Expression::Function(Box::new(Function {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                generator: false,
                 id: None,
                 parameters: vec![
                     Parameter {
@@ -325,7 +813,7 @@ mod tests {
                         Expression::Id(Id("y".into())),
                     )
                 )))]
-            })
+            }))
             .into()),
             result
         )
@@ -372,12 +860,13 @@ mod tests {
                     }
                 ],
                 expression: Box::new(Expression::Block(Block {
-                    statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
+                    statements: vec![],
+                    tail: Some(Box::new(Expression::Binary(Box::new(
                         BinaryExpression::Addition(
                             Expression::Id(Id("x".into())),
                             Expression::Id(Id("y".into())),
                         )
-                    )))]
+                    ))))
                 }))
             })
             .into()),
@@ -386,43 +875,33 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_if() {
-        let mut tokens = Lexer::new("if (x) { 3 + 4 } else { 42 + 1337 }")
+    fn test_parse_shorthand_lambda_as_argument() {
+        let mut tokens = Lexer::new("map(xs, x => x * 2)")
             .lex()
             .expect("should work")
             .into();
 
-        assert_eq!(
-            Ok(Expression::If(If {
-                condition: Box::new(Expression::Id(Id("x".into()))),
-                statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
-                    BinaryExpression::Addition(
-                        Expression::Num(Num::Integer(3)),
-                        Expression::Num(Num::Integer(4))
-                    )
-                )))],
-                else_statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
-                    BinaryExpression::Addition(
-                        Expression::Num(Num::Integer(42)),
-                        Expression::Num(Num::Integer(1337))
-                    )
-                )))],
-            })
-            .into()),
-            Expression::parse(&mut tokens)
-        )
-    }
-
-    #[test]
-    fn test_parse_postfix_call_simple() {
-        let mut tokens = Lexer::new("foo()").lex().expect("should work").into();
-
         let result = Expression::parse(&mut tokens);
 
         assert_eq!(
             Ok(Expression::Postfix(Postfix::Call {
-                expr: Box::new(Expression::Id(Id("foo".into()))),
-                args: vec![]
+                expr: Box::new(Expression::Id(Id("map".into()))),
+                type_args: vec![],
+                args: vec![
+                    Expression::Id(Id("xs".into())),
+                    Expression::Lambda(Lambda {
+                        parameters: vec![Parameter {
+                            name: Id("x".into()),
+                            type_name: None
+                        }],
+                        expression: Box::new(Expression::Binary(Box::new(
+                            BinaryExpression::Multiplication(
+                                Expression::Id(Id("x".into())),
+                                Expression::Num(Num::Integer(2))
+                            )
+                        )))
+                    })
+                ]
             })
             .into()),
             result
@@ -430,163 +909,1526 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_postfix_call_complex() {
-        let mut tokens = Lexer::new("(\\(x, y) => x + y)(42, 1337)")
-            .lex()
-            .expect("should work")
-            .into();
+    fn test_chained_comparison_fails() {
+        let mut tokens = Lexer::new("a < b < c").lex().expect("should work").into();
 
         let result = Expression::parse(&mut tokens);
 
-        assert_eq!(
-            Ok(Expression::Postfix(Postfix::Call {
-                expr: Box::new(Expression::Parens(Box::new(Expression::Lambda(Lambda {
-                    parameters: vec![
-                        Parameter {
-                            name: Id("x".into()),
-                            type_name: None
-                        },
-                        Parameter {
-                            name: Id("y".into()),
-                            type_name: None
-                        }
-                    ],
-                    expression: Box::new(Expression::Binary(Box::new(BinaryExpression::Addition(
-                        Expression::Id(Id("x".into())),
-                        Expression::Id(Id("y".into()))
-                    ))))
-                })))),
-                args: vec![
-                    Expression::Num(Num::Integer(42)),
-                    Expression::Num(Num::Integer(1337))
-                ]
-            })
-            .into()),
-            result
-        );
+        let Err(error) = result else {
+            panic!("expected error, got {result:?}");
+        };
+        assert!(error.message.contains("can not be chained"));
+        assert!(error.message.contains("`(a < b) && (b < c)`"));
+        assert_eq!(Some(Position::new(6, 1)), error.position);
+        assert_eq!(Position::new(2, 1), error.notes[0].position);
     }
 
     #[test]
-    fn test_parse_array_empty() {
-        let mut tokens = Lexer::new("[]").lex().expect("something is wrong").into();
+    fn test_mixed_chained_comparison_fails() {
+        let mut tokens = Lexer::new("a >= b == c").lex().expect("should work").into();
 
         let result = Expression::parse(&mut tokens);
-        assert_eq!(
-            Ok(Expression::Array(Array::Literal { values: vec![] }).into()),
-            result
-        );
+
+        let Err(error) = result else {
+            panic!("expected error, got {result:?}");
+        };
+        assert!(error.message.contains("non-associative"));
+        assert_eq!(Some(Position::new(7, 2)), error.position);
+        assert_eq!(Position::new(2, 2), error.notes[0].position);
     }
 
     #[test]
-    fn test_parse_array_simple_literal() {
-        let mut tokens = Lexer::new("[42, 1337]")
+    fn test_separate_comparisons() {
+        let mut tokens = Lexer::new("(a < b) && (b < c)")
             .lex()
-            .expect("something is wrong")
+            .expect("should work")
             .into();
 
-        let result = Expression::parse(&mut tokens);
+        assert!(Expression::parse(&mut tokens).is_ok());
+    }
+
+    #[test]
+    fn test_parse_less_or_equal() {
+        let mut tokens = Lexer::new("a <= b + 1").lex().expect("should work").into();
+
         assert_eq!(
-            Ok(Expression::Array(Array::Literal {
-                values: vec![
-                    Expression::Num(Num::Integer(42)),
-                    Expression::Num(Num::Integer(1337))
-                ]
-            })
+            Ok(binary(
+                BinaryExpression::LessOrEqual,
+                id("a"),
+                binary(
+                    BinaryExpression::Addition,
+                    id("b"),
+                    Expression::Num(Num::Integer(1))
+                )
+            )
             .into()),
-            result
+            Expression::parse(&mut tokens)
         );
     }
 
     #[test]
-    fn test_parse_index_simple() {
-        let mut tokens = Lexer::new("foo[42]")
+    fn test_chained_comparison_with_arithmetic_fails() {
+        let mut tokens = Lexer::new("a == f(x < y) + 1 > c")
             .lex()
-            .expect("something is wrong")
+            .expect("should work")
             .into();
 
         let result = Expression::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Expression::Postfix(Postfix::Index {
-                expr: Box::new(Expression::Id(Id("foo".into()))),
-                index: Box::new(Expression::Num(Num::Integer(42)))
-            })
-            .into()),
-            result
-        )
+            Some(Position::new(18, 1)),
+            result.err().and_then(|error| error.position)
+        );
     }
 
     #[test]
-    fn parse_struct() {
-        let mut tokens = Lexer::new("Foo { bar: 42, baz: \\(x) => x + x }")
+    fn test_parenthesized_comparison_is_allowed() {
+        let mut tokens = Lexer::new("(a < b) == c")
             .lex()
-            .expect("something is wrong")
+            .expect("should work")
             .into();
 
-        let result = Expression::parse(&mut tokens);
+        assert!(Expression::parse(&mut tokens).is_ok());
+    }
+
+    #[test]
+    fn test_parse_mixed_number_literals() {
+        let mut tokens = Lexer::new("1 + 2.5e1").lex().expect("should work").into();
 
         assert_eq!(
-            Ok(Expression::StructInitialisation(StructInitialisation {
-                id: Id("Foo".into()),
-                fields: vec![
-                    StructFieldInitialisation {
-                        name: Id("bar".into()),
-                        value: Expression::Num(Num::Integer(42))
-                    },
-                    StructFieldInitialisation {
-                        name: Id("baz".into()),
-                        value: Expression::Lambda(Lambda {
-                            parameters: vec![Parameter {
-                                name: Id("x".into()),
-                                type_name: None
-                            }],
-                            expression: Box::new(Expression::Binary(Box::new(
-                                BinaryExpression::Addition(
-                                    Expression::Id(Id("x".into())),
-                                    Expression::Id(Id("x".into()))
-                                )
-                            )))
-                        })
-                    }
-                ]
-            })
+            Ok(Expression::Binary(Box::new(BinaryExpression::Addition(
+                Expression::Num(Num::Integer(1)),
+                Expression::Num(Num::FloatingPoint(25.0))
+            )))
             .into()),
-            result
+            Expression::parse(&mut tokens)
         );
     }
 
     #[test]
-    fn parse_property_access_simple() {
-        let mut tokens = Lexer::new("foo.bar")
+    fn test_parse_struct_initialisation_with_trailing_comma() {
+        let mut tokens = Lexer::new("Point { x: 1, y: f(2), }")
             .lex()
-            .expect("something is wrong")
+            .expect("should work")
             .into();
 
-        let result = Expression::parse(&mut tokens);
-
         assert_eq!(
-            Ok(Expression::Postfix(Postfix::PropertyAccess {
-                expr: Box::new(Expression::Id(Id("foo".into()))),
-                property: Id("bar".into())
-            })
-            .into()),
-            result
+            Ok(
+                Expression::StructInitialisation(Box::new(StructInitialisation {
+                    path: vec![],
+                    id: Id("Point".into()),
+                    fields: vec![
+                        StructFieldInitialisation {
+                            name: Id("x".into()),
+                            value: Expression::Num(Num::Integer(1))
+                        },
+                        StructFieldInitialisation {
+                            name: Id("y".into()),
+                            value: Expression::Postfix(Postfix::Call {
+                                expr: Box::new(Expression::Id(Id("f".into()))),
+                                type_args: vec![],
+                                args: vec![Expression::Num(Num::Integer(2))]
+                            })
+                        }
+                    ]
+                }))
+                .into()
+            ),
+            Expression::parse(&mut tokens)
         );
     }
 
     #[test]
-    fn parse_property_access_complex() {
-        let mut tokens = Lexer::new("foo().bar")
+    fn test_id_followed_by_braces_is_not_a_struct_initialisation() {
+        let mut tokens = Lexer::new("match x { y => 1 }")
             .lex()
-            .expect("something is wrong")
+            .expect("should work")
             .into();
 
-        let result = Expression::parse(&mut tokens);
-
-        assert_eq!(
+        let Ok(AstNode::Expression(Expression::Match(match_expression))) =
+            Expression::parse(&mut tokens)
+        else {
+            panic!("expected match expression");
+        };
+        assert_eq!(Expression::Id(Id("x".into())), *match_expression.value);
+    }
+
+    #[test]
+    fn test_parse_string() {
+        let mut tokens = Lexer::new(r#""hello world\n" == greeting"#)
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Equal(
+                Expression::String("hello world\n".into()),
+                Expression::Id(Id("greeting".into()))
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_format_string() {
+        let mut tokens = Lexer::new(r#""{a}" + b"#)
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Addition(
+                Expression::FormatString(vec![Segment::Expression(Expression::Id(Id("a".into())))]),
+                Expression::Id(Id("b".into()))
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_unit() {
+        let mut tokens = Lexer::new("() == void").lex().expect("should work").into();
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Equal(
+                Expression::Unit,
+                id("void")
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        let mut tokens = Lexer::new("!true").lex().expect("should work").into();
+
+        assert_eq!(
+            Ok(Expression::Prefix(Prefix::Negation {
+                expr: Box::new(Expression::Bool(Bool(true)))
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_logical_operators_bind_weaker_than_comparisons() {
+        let mut tokens = Lexer::new("a < b && b < c || false")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let comparison = |lhs: &str, rhs: &str| {
+            Expression::Binary(Box::new(BinaryExpression::LessThen(
+                Expression::Id(Id(lhs.into())),
+                Expression::Id(Id(rhs.into())),
+            )))
+        };
+
+        assert_eq!(
+            Ok(lazy_or(
+                lazy_and(comparison("a", "b"), comparison("b", "c")),
+                Expression::Bool(Bool(false))
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_and_binds_stronger_than_or() {
+        let mut tokens = Lexer::new("a || b && c").lex().expect("should work").into();
+
+        assert_eq!(
+            Ok(lazy_or(id("a"), lazy_and(id("b"), id("c"))).into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_lazy_operators_are_left_associative() {
+        let mut tokens = Lexer::new("a || b || c && d && e")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(lazy_or(
+                lazy_or(id("a"), id("b")),
+                lazy_and(lazy_and(id("c"), id("d")), id("e"))
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_chained_comparison_before_logical_operator_fails() {
+        let mut tokens = Lexer::new("a < b < c && d")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Some(Position::new(6, 1)),
+            Expression::parse(&mut tokens)
+                .err()
+                .and_then(|error| error.position)
+        );
+    }
+
+    #[test]
+    fn test_parse_if() {
+        let mut tokens = Lexer::new("if (x) { 3 + 4 } else { 42 + 1337 }")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::If(Box::new(If {
+                condition: Box::new(Expression::Id(Id("x".into()))),
+                statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
+                    BinaryExpression::Addition(
+                        Expression::Num(Num::Integer(3)),
+                        Expression::Num(Num::Integer(4))
+                    )
+                )))],
+                else_statements: Some(vec![Statement::YieldingExpression(Expression::Binary(
+                    Box::new(BinaryExpression::Addition(
+                        Expression::Num(Num::Integer(42)),
+                        Expression::Num(Num::Integer(1337))
+                    ))
+                ))]),
+            }))
+            .into()),
+            Expression::parse(&mut tokens)
+        )
+    }
+
+    #[test]
+    fn test_parse_if_without_else_fails() {
+        let mut tokens = Lexer::new("1 + if (x) { 2 }")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Err(ParseError {
+                message: "`if` used as an expression needs an `else` branch".into(),
+                position: Some(Position::new(4, 2)),
+                notes: vec![],
+            }),
+            Expression::parse(&mut tokens)
+        )
+    }
+
+    #[test]
+    fn test_parse_postfix_call_simple() {
+        let mut tokens = Lexer::new("foo()").lex().expect("should work").into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(Expression::Id(Id("foo".into()))),
+                type_args: vec![],
+                args: vec![]
+            })
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_parse_postfix_call_with_type_args() {
+        let mut tokens = Lexer::new("identity::<Pair<i32, i32>>(5)")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(Expression::Id(Id("identity".into()))),
+                type_args: vec![TypeName::Generic {
+                    name: "Pair".into(),
+                    args: vec![TypeName::Literal("i32".into()); 2]
+                }],
+                args: vec![Expression::Num(Num::Integer(5))]
+            })
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_parse_turbofish_without_call_fails() {
+        let mut tokens = Lexer::new("identity::<i32>;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert!(Expression::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_immediately_invoked_function() {
+        let mut tokens = Lexer::new("fn (): i32 { 42 }()")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Expression(Expression::Postfix(Postfix::Call { expr, .. })))
+                if matches!(*expr, Expression::Function(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_immediately_invoked_parenthesized_function() {
+        let mut tokens = Lexer::new("(fn (): i32 { 42 })()")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Expression(Expression::Postfix(Postfix::Call { expr, .. })))
+                if matches!(*expr, Expression::Parens(ref inner) if matches!(**inner, Expression::Function(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_call_of_call_result() {
+        let mut tokens = Lexer::new("foo()(1)").lex().expect("should work").into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(Expression::Postfix(Postfix::Call {
+                    expr: Box::new(Expression::Id(Id("foo".into()))),
+                    type_args: vec![],
+                    args: vec![]
+                })),
+                type_args: vec![],
+                args: vec![Expression::Num(Num::Integer(1))]
+            })
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_parse_postfix_call_complex() {
+        let mut tokens = Lexer::new("(\\(x, y) => x + y)(42, 1337)")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(Expression::Parens(Box::new(Expression::Lambda(Lambda {
+                    parameters: vec![
+                        Parameter {
+                            name: Id("x".into()),
+                            type_name: None
+                        },
+                        Parameter {
+                            name: Id("y".into()),
+                            type_name: None
+                        }
+                    ],
+                    expression: Box::new(Expression::Binary(Box::new(BinaryExpression::Addition(
+                        Expression::Id(Id("x".into())),
+                        Expression::Id(Id("y".into()))
+                    ))))
+                })))),
+                type_args: vec![],
+                args: vec![
+                    Expression::Num(Num::Integer(42)),
+                    Expression::Num(Num::Integer(1337))
+                ]
+            })
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_array_empty() {
+        let mut tokens = Lexer::new("[]").lex().expect("something is wrong").into();
+
+        let result = Expression::parse(&mut tokens);
+        assert_eq!(
+            Ok(Expression::Array(Array::Literal { values: vec![] }).into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_array_simple_literal() {
+        let mut tokens = Lexer::new("[42, 1337]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+        assert_eq!(
+            Ok(Expression::Array(Array::Literal {
+                values: vec![
+                    Expression::Num(Num::Integer(42)),
+                    Expression::Num(Num::Integer(1337))
+                ]
+            })
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_index_simple() {
+        let mut tokens = Lexer::new("foo[42]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Index {
+                expr: Box::new(Expression::Id(Id("foo".into()))),
+                index: Box::new(Expression::Num(Num::Integer(42)))
+            })
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_parse_nested_array_literal() {
+        let mut tokens = Lexer::new("[[1, 2], [3, 4]]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let row = |a, b| {
+            Expression::Array(Array::Literal {
+                values: vec![
+                    Expression::Num(Num::Integer(a)),
+                    Expression::Num(Num::Integer(b)),
+                ],
+            })
+        };
+
+        assert_eq!(
+            Ok(Expression::Array(Array::Literal {
+                values: vec![row(1, 2), row(3, 4)]
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_chained_index() {
+        let mut tokens = Lexer::new("grid[i][j + 1]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Index {
+                expr: Box::new(Expression::Postfix(Postfix::Index {
+                    expr: Box::new(Expression::Id(Id("grid".into()))),
+                    index: Box::new(Expression::Id(Id("i".into())))
+                })),
+                index: Box::new(Expression::Binary(Box::new(BinaryExpression::Addition(
+                    Expression::Id(Id("j".into())),
+                    Expression::Num(Num::Integer(1))
+                ))))
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_slice() {
+        let mut tokens = Lexer::new("arr[1..4][0..=n]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Slice {
+                expr: Box::new(Expression::Slice {
+                    expr: Box::new(Expression::Id(Id("arr".into()))),
+                    start: Box::new(Expression::Num(Num::Integer(1))),
+                    end: Box::new(Expression::Num(Num::Integer(4))),
+                    inclusive: false
+                }),
+                start: Box::new(Expression::Num(Num::Integer(0))),
+                end: Box::new(Expression::Id(Id("n".into()))),
+                inclusive: true
+            }
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_index_with_parenthesized_range() {
+        let mut tokens = Lexer::new("ranges[(1..4)]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Index {
+                expr: Box::new(Expression::Id(Id("ranges".into()))),
+                index: Box::new(Expression::Parens(Box::new(Expression::Range {
+                    start: Box::new(Expression::Num(Num::Integer(1))),
+                    end: Box::new(Expression::Num(Num::Integer(4))),
+                    inclusive: false
+                })))
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    fn id(name: &str) -> Expression {
+        Expression::Id(Id(name.into()))
+    }
+
+    fn ternary(
+        condition: Expression,
+        then_branch: Expression,
+        else_branch: Expression,
+    ) -> Expression {
+        Expression::Ternary {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch: Box::new(else_branch),
+        }
+    }
+
+    #[test]
+    fn test_parse_ternary_binds_weaker_than_or() {
+        let mut tokens = Lexer::new("a || b ? c + 1 : d")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(ternary(
+                lazy_or(id("a"), id("b")),
+                Expression::Binary(Box::new(BinaryExpression::Addition(
+                    id("c"),
+                    Expression::Num(Num::Integer(1))
+                ))),
+                id("d")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_ternaries() {
+        let mut tokens = Lexer::new("a ? b : c ? d : e")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(ternary(id("a"), id("b"), ternary(id("c"), id("d"), id("e"))).into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("a ? b ? c : d : e")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(ternary(id("a"), ternary(id("b"), id("c"), id("d")), id("e")).into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_ternary_with_prefix_and_range() {
+        let mut tokens = Lexer::new("!a ? b : c")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(ternary(
+                Expression::Prefix(Prefix::Negation {
+                    expr: Box::new(id("a"))
+                }),
+                id("b"),
+                id("c")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("x + 1..n ? b : c")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(ternary(
+                Expression::Range {
+                    start: Box::new(Expression::Binary(Box::new(BinaryExpression::Addition(
+                        id("x"),
+                        Expression::Num(Num::Integer(1))
+                    )))),
+                    end: Box::new(id("n")),
+                    inclusive: false
+                },
+                id("b"),
+                id("c")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_ternary_without_else_fails() {
+        let mut tokens = Lexer::new("a ? b")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Expression::parse(&mut tokens).is_err());
+    }
+
+    fn cast(expr: Expression, target: TypeName) -> Expression {
+        Expression::Cast {
+            expr: Box::new(expr),
+            target: Box::new(target),
+        }
+    }
+
+    #[test]
+    fn test_parse_cast_binds_like_postfix() {
+        let mut tokens = Lexer::new("x as f64 * 2.5")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(
+                Expression::Binary(Box::new(BinaryExpression::Multiplication(
+                    cast(id("x"), TypeName::Literal("f64".into())),
+                    Expression::Num(Num::FloatingPoint(2.5))
+                )))
+                .into()
+            ),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("-a.b as i64 as u8")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Prefix(Prefix::Minus {
+                expr: Box::new(cast(
+                    cast(
+                        Expression::Postfix(Postfix::PropertyAccess {
+                            expr: Box::new(id("a")),
+                            property: Id("b".into())
+                        }),
+                        TypeName::Literal("i64".into())
+                    ),
+                    TypeName::Literal("u8".into())
+                ))
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_cast_to_optional_or_in_ternary() {
+        let mut tokens = Lexer::new("x as Point?")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(cast(
+                id("x"),
+                TypeName::Optional(Box::new(TypeName::Literal("Point".into())))
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("x as bool ? a : b")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(ternary(
+                cast(id("x"), TypeName::Literal("bool".into())),
+                id("a"),
+                id("b")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    fn binary(
+        operation: fn(Expression, Expression) -> BinaryExpression,
+        lhs: Expression,
+        rhs: Expression,
+    ) -> Expression {
+        Expression::Binary(Box::new(operation(lhs, rhs)))
+    }
+
+    fn lazy_and(lhs: Expression, rhs: Expression) -> Expression {
+        Expression::LazyAnd {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        }
+    }
+
+    fn lazy_or(lhs: Expression, rhs: Expression) -> Expression {
+        Expression::LazyOr {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        }
+    }
+
+    #[test]
+    fn test_parse_bitwise_precedence() {
+        let mut tokens = Lexer::new("a | b ^ c & d == e")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Equal,
+                binary(
+                    BinaryExpression::BitOr,
+                    id("a"),
+                    binary(
+                        BinaryExpression::BitXor,
+                        id("b"),
+                        binary(BinaryExpression::BitAnd, id("c"), id("d"))
+                    )
+                ),
+                id("e")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("a + b & c || d")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(lazy_or(
+                binary(
+                    BinaryExpression::BitAnd,
+                    binary(BinaryExpression::Addition, id("a"), id("b")),
+                    id("c")
+                ),
+                id("d")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_shift() {
+        let mut tokens = Lexer::new("1 << n + 1 >> m & mask")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::BitAnd,
+                binary(
+                    BinaryExpression::ShiftRight,
+                    binary(
+                        BinaryExpression::ShiftLeft,
+                        Expression::Num(Num::Integer(1)),
+                        binary(
+                            BinaryExpression::Addition,
+                            id("n"),
+                            Expression::Num(Num::Integer(1))
+                        )
+                    ),
+                    id("m")
+                ),
+                id("mask")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_pass_functions_as_arguments() {
+        let mut tokens = Lexer::new("apply(inc, 1) apply(x => x * 2, a) apply(\\(x: i32) => x, a)")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let call = |function: Expression, arg: Expression| {
+            Expression::Postfix(Postfix::Call {
+                expr: Box::new(id("apply")),
+                type_args: vec![],
+                args: vec![function, arg],
+            })
+        };
+
+        assert_eq!(
+            Ok(call(id("inc"), Expression::Num(Num::Integer(1))).into()),
+            Expression::parse(&mut tokens)
+        );
+        assert_eq!(
+            Ok(call(
+                Expression::Lambda(Lambda {
+                    parameters: vec![Parameter {
+                        name: Id("x".into()),
+                        type_name: None
+                    }],
+                    expression: Box::new(binary(
+                        BinaryExpression::Multiplication,
+                        id("x"),
+                        Expression::Num(Num::Integer(2))
+                    ))
+                }),
+                id("a")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+        assert_eq!(
+            Ok(call(
+                Expression::Lambda(Lambda {
+                    parameters: vec![Parameter {
+                        name: Id("x".into()),
+                        type_name: Some(TypeName::Literal("i32".into()))
+                    }],
+                    expression: Box::new(id("x"))
+                }),
+                id("a")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_try() {
+        let parse = |input: &str| {
+            let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+            Expression::parse(&mut tokens)
+        };
+        let try_ = |expr: Expression| Expression::Try(Box::new(expr));
+
+        assert_eq!(
+            Ok(try_(Expression::Postfix(Postfix::Call {
+                expr: Box::new(id("parse")),
+                type_args: vec![],
+                args: vec![id("input")]
+            }))
+            .into()),
+            parse("parse(input)?")
+        );
+        assert_eq!(
+            Ok(
+                Expression::Postfix(Postfix::MethodCall(Box::new(MethodCall {
+                    receiver: try_(Expression::Postfix(Postfix::PropertyAccess {
+                        expr: Box::new(try_(id("config"))),
+                        property: Id("port".into())
+                    })),
+                    method: Id("value".into()),
+                    type_args: vec![],
+                    args: vec![]
+                })))
+                .into()
+            ),
+            parse("config?.port?.value()")
+        );
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(id("f")),
+                type_args: vec![],
+                args: vec![try_(id("x")), id("y")]
+            })
+            .into()),
+            parse("f(x?, y)")
+        );
+        assert_eq!(
+            Ok(Expression::Ternary {
+                condition: Box::new(try_(id("ready"))),
+                then_branch: Box::new(id("a")),
+                else_branch: Box::new(id("b"))
+            }
+            .into()),
+            parse("ready? ? a : b")
+        );
+    }
+
+    #[test]
+    fn test_parse_try_followed_by_operand() {
+        let parse = |input: &str| {
+            let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+            Expression::parse(&mut tokens)
+        };
+        let try_f = || {
+            Expression::Try(Box::new(Expression::Postfix(Postfix::Call {
+                expr: Box::new(id("f")),
+                type_args: vec![],
+                args: vec![],
+            })))
+        };
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Index {
+                expr: Box::new(try_f()),
+                index: Box::new(Expression::Num(Num::Integer(0)))
+            })
+            .into()),
+            parse("f()?[0]")
+        );
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(try_f()),
+                type_args: vec![],
+                args: vec![id("x")]
+            })
+            .into()),
+            parse("f()?(x)")
+        );
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Substraction,
+                try_f(),
+                Expression::Num(Num::Integer(1))
+            )
+            .into()),
+            parse("f()? - 1")
+        );
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Division,
+                try_f(),
+                Expression::Num(Num::Integer(2))
+            )
+            .into()),
+            parse("f()? / 2")
+        );
+        assert_eq!(
+            Ok(Expression::Ternary {
+                condition: Box::new(id("a")),
+                then_branch: Box::new(Expression::Try(Box::new(id("b")))),
+                else_branch: Box::new(id("c"))
+            }
+            .into()),
+            parse("a ? b? : c")
+        );
+        assert_eq!(
+            Ok(Expression::Ternary {
+                condition: Box::new(id("a")),
+                then_branch: Box::new(Expression::Array(Array::Literal {
+                    values: vec![Expression::Num(Num::Integer(0))]
+                })),
+                else_branch: Box::new(id("c"))
+            }
+            .into()),
+            parse("a ? [0] : c")
+        );
+    }
+
+    #[test]
+    fn test_parse_spread_arguments() {
+        let mut tokens = Lexer::new("f(a, ...args) ...xs")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(id("f")),
+                type_args: vec![],
+                args: vec![id("a"), Expression::Spread(Box::new(id("args")))]
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+        // a spread is only valid as an element or an argument
+        assert!(Expression::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_spread_in_tuple() {
+        let parse = |input: &str| {
+            let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+            Expression::parse(&mut tokens)
+        };
+
+        assert_eq!(
+            Ok(Expression::Tuple(vec![
+                Expression::Num(Num::Integer(1)),
+                Expression::Spread(Box::new(id("xs")))
+            ])
+            .into()),
+            parse("(1, ...xs)")
+        );
+        assert_eq!(
+            Ok(Expression::Tuple(vec![Expression::Spread(Box::new(id("xs")))]).into()),
+            parse("(...xs)")
+        );
+    }
+
+    #[test]
+    fn test_parse_variant_construction() {
+        let mut tokens = Lexer::new("Shape::Circle(1.0)")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(Expression::Path(vec![
+                    Id("Shape".into()),
+                    Id("Circle".into())
+                ])),
+                type_args: vec![],
+                args: vec![Expression::Num(Num::FloatingPoint(1.0))]
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("Shape::Empty == s")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Equal,
+                Expression::Path(vec![Id("Shape".into()), Id("Empty".into())]),
+                id("s")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let mut tokens = Lexer::new("geometry::shapes::origin()")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::Call {
+                expr: Box::new(Expression::Path(vec![
+                    Id("geometry".into()),
+                    Id("shapes".into()),
+                    Id("origin".into())
+                ])),
+                type_args: vec![],
+                args: vec![]
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_variant_construction() {
+        let mut tokens = Lexer::new("Shape::Rect { w: 1.0, h: 2.0 }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(
+                Expression::StructInitialisation(Box::new(StructInitialisation {
+                    path: vec![Id("Shape".into())],
+                    id: Id("Rect".into()),
+                    fields: vec![
+                        StructFieldInitialisation {
+                            name: Id("w".into()),
+                            value: Expression::Num(Num::FloatingPoint(1.0))
+                        },
+                        StructFieldInitialisation {
+                            name: Id("h".into()),
+                            value: Expression::Num(Num::FloatingPoint(2.0))
+                        }
+                    ]
+                }))
+                .into()
+            ),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_shift_after_generic_call() {
+        let mut tokens = Lexer::new("parse::<Vec<Vec<i32>>>(s) >> 1")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert!(
+            matches!(
+                &result,
+                Ok(AstNode::Expression(Expression::Binary(binary)))
+                    if matches!(**binary, BinaryExpression::ShiftRight(Expression::Postfix(Postfix::Call { .. }), _))
+            ),
+            "unexpected {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_parse_modulo() {
+        let mut tokens = Lexer::new("a % b + c")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Addition,
+                binary(BinaryExpression::Modulo, id("a"), id("b")),
+                id("c")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("a + b % c")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Addition,
+                id("a"),
+                binary(BinaryExpression::Modulo, id("b"), id("c"))
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_left_associative() {
+        let mut tokens = Lexer::new("a - b - c")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Substraction,
+                binary(BinaryExpression::Substraction, id("a"), id("b")),
+                id("c")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("a / b * c % d")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Modulo,
+                binary(
+                    BinaryExpression::Multiplication,
+                    binary(BinaryExpression::Division, id("a"), id("b")),
+                    id("c")
+                ),
+                id("d")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_mixed_precedence() {
+        let mut tokens = Lexer::new("a || b && c == d + e * f")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(lazy_or(
+                id("a"),
+                lazy_and(
+                    id("b"),
+                    binary(
+                        BinaryExpression::Equal,
+                        id("c"),
+                        binary(
+                            BinaryExpression::Addition,
+                            id("d"),
+                            binary(BinaryExpression::Multiplication, id("e"), id("f"))
+                        )
+                    )
+                )
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_pipeline() {
+        let mut tokens = Lexer::new("data |> filter(pred) |> map(f) |> sum || a + 1 |> print")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let pipe = |value: Expression, function: Expression| Expression::Pipe {
+            value: Box::new(value),
+            function: Box::new(function),
+        };
+        let call = |function: &str, arg: &str| {
+            Expression::Postfix(Postfix::Call {
+                expr: Box::new(id(function)),
+                type_args: vec![],
+                args: vec![id(arg)],
+            })
+        };
+
+        assert_eq!(
+            Ok(pipe(
+                pipe(
+                    pipe(pipe(id("data"), call("filter", "pred")), call("map", "f")),
+                    lazy_or(
+                        id("sum"),
+                        binary(
+                            BinaryExpression::Addition,
+                            id("a"),
+                            Expression::Num(Num::Integer(1))
+                        )
+                    )
+                ),
+                id("print")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_exponentiation() {
+        let two = || Expression::Num(Num::Integer(2));
+        let three = || Expression::Num(Num::Integer(3));
+
+        let mut tokens = Lexer::new("2 ** 3 ** 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Exponentiation,
+                two(),
+                binary(BinaryExpression::Exponentiation, three(), two())
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("2 ** 3 * 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::Multiplication,
+                binary(BinaryExpression::Exponentiation, two(), three()),
+                two()
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_negated_exponentiation() {
+        let two = || Expression::Num(Num::Integer(2));
+        let minus = |expr| {
+            Expression::Prefix(Prefix::Minus {
+                expr: Box::new(expr),
+            })
+        };
+
+        let mut tokens = Lexer::new("-x ** 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(minus(binary(BinaryExpression::Exponentiation, id("x"), two())).into()),
+            Expression::parse(&mut tokens)
+        );
+
+        // the literal is not folded, -2 ** 2 is -(2 ** 2)
+        let mut tokens = Lexer::new("-2 ** 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(minus(binary(BinaryExpression::Exponentiation, two(), two())).into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("-x * 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(BinaryExpression::Multiplication, minus(id("x")), two()).into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_bitwise_not() {
+        let mut tokens = Lexer::new("~a & b")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(binary(
+                BinaryExpression::BitAnd,
+                Expression::Prefix(Prefix::BitNot {
+                    expr: Box::new(id("a"))
+                }),
+                id("b")
+            )
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn parse_struct() {
+        let mut tokens = Lexer::new("Foo { bar: 42, baz: \\(x) => x + x }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(
+                Expression::StructInitialisation(Box::new(StructInitialisation {
+                    path: vec![],
+                    id: Id("Foo".into()),
+                    fields: vec![
+                        StructFieldInitialisation {
+                            name: Id("bar".into()),
+                            value: Expression::Num(Num::Integer(42))
+                        },
+                        StructFieldInitialisation {
+                            name: Id("baz".into()),
+                            value: Expression::Lambda(Lambda {
+                                parameters: vec![Parameter {
+                                    name: Id("x".into()),
+                                    type_name: None
+                                }],
+                                expression: Box::new(Expression::Binary(Box::new(
+                                    BinaryExpression::Addition(
+                                        Expression::Id(Id("x".into())),
+                                        Expression::Id(Id("x".into()))
+                                    )
+                                )))
+                            })
+                        }
+                    ]
+                }))
+                .into()
+            ),
+            result
+        );
+    }
+
+    #[test]
+    fn parse_property_access_simple() {
+        let mut tokens = Lexer::new("foo.bar")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::PropertyAccess {
+                expr: Box::new(Expression::Id(Id("foo".into()))),
+                property: Id("bar".into())
+            })
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn parse_property_access_complex() {
+        let mut tokens = Lexer::new("foo().bar")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
             Ok(Expression::Postfix(Postfix::PropertyAccess {
                 expr: Box::new(Expression::Postfix(Postfix::Call {
                     expr: Box::new(Expression::Id(Id("foo".into()))),
+                    type_args: vec![],
                     args: vec![]
                 })),
                 property: Id("bar".into())
@@ -596,21 +2438,215 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_property_access_chain() {
+        let mut tokens = Lexer::new("-a.b[0].c + 1")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let access = |expr, property: &str| {
+            Expression::Postfix(Postfix::PropertyAccess {
+                expr: Box::new(expr),
+                property: Id(property.into()),
+            })
+        };
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Addition(
+                Expression::Prefix(Prefix::Minus {
+                    expr: Box::new(access(
+                        Expression::Postfix(Postfix::Index {
+                            expr: Box::new(access(Expression::Id(Id("a".into())), "b")),
+                            index: Box::new(Expression::Num(Num::Integer(0)))
+                        }),
+                        "c"
+                    ))
+                }),
+                Expression::Num(Num::Integer(1))
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_chained_method_calls() {
+        let mut tokens = Lexer::new("a.b().c(1, x)")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(
+                Expression::Postfix(Postfix::MethodCall(Box::new(MethodCall {
+                    receiver: Expression::Postfix(Postfix::MethodCall(Box::new(MethodCall {
+                        receiver: Expression::Id(Id("a".into())),
+                        method: Id("b".into()),
+                        type_args: vec![],
+                        args: vec![]
+                    }))),
+                    method: Id("c".into()),
+                    type_args: vec![],
+                    args: vec![
+                        Expression::Num(Num::Integer(1)),
+                        Expression::Id(Id("x".into()))
+                    ]
+                })))
+                .into()
+            ),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_method_call_with_type_arguments() {
+        let mut tokens = Lexer::new("xs.map::<i32>(f).len")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::PropertyAccess {
+                expr: Box::new(Expression::Postfix(Postfix::MethodCall(Box::new(
+                    MethodCall {
+                        receiver: Expression::Id(Id("xs".into())),
+                        method: Id("map".into()),
+                        type_args: vec![TypeName::Literal("i32".into())],
+                        args: vec![Expression::Id(Id("f".into()))]
+                    }
+                )))),
+                property: Id("len".into())
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_tuple_access() {
+        let mut tokens = Lexer::new("(t, (1, true)).1.0")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::TupleAccess {
+                expr: Box::new(Expression::Postfix(Postfix::TupleAccess {
+                    expr: Box::new(Expression::Tuple(vec![
+                        Expression::Id(Id("t".into())),
+                        Expression::Tuple(vec![
+                            Expression::Num(Num::Integer(1)),
+                            Expression::Bool(Bool(true))
+                        ])
+                    ])),
+                    index: 1
+                })),
+                index: 0
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_single_parenthesized_expression_is_no_tuple() {
+        let mut tokens = Lexer::new("(1).0")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Postfix(Postfix::TupleAccess {
+                expr: Box::new(Expression::Parens(Box::new(Expression::Num(Num::Integer(
+                    1
+                ))))),
+                index: 0
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_tuple_with_trailing_comma() {
+        let parse = |input: &str| {
+            let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+            Expression::parse(&mut tokens)
+        };
+
+        assert_eq!(
+            Ok(Expression::Tuple(vec![
+                Expression::Num(Num::Integer(1)),
+                Expression::Num(Num::Integer(2))
+            ])
+            .into()),
+            parse("(1, 2,)")
+        );
+        assert_eq!(
+            Ok(Expression::Tuple(vec![Expression::Num(Num::Integer(1))]).into()),
+            parse("(1,)")
+        );
+        assert!(parse("(,)").is_err());
+    }
+
     #[test]
     fn test_simple_minus() {
-        let mut tokens = Lexer::new("-42").lex().expect("something is wrong").into();
+        let mut tokens = Lexer::new("-x").lex().expect("something is wrong").into();
 
         let result = Expression::parse(&mut tokens);
 
         assert_eq!(
             Ok(Expression::Prefix(Prefix::Minus {
-                expr: Box::new(Expression::Num(Num::Integer(42)))
+                expr: Box::new(Expression::Id(Id("x".into())))
             })
             .into()),
             result
         );
     }
 
+    #[test]
+    fn test_negative_literals() {
+        let mut tokens = Lexer::new("-42").lex().expect("something is wrong").into();
+
+        assert_eq!(
+            Ok(Expression::Num(Num::NegativeInteger(42)).into()),
+            Expression::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("-1.5 * 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(
+                Expression::Binary(Box::new(BinaryExpression::Multiplication(
+                    Expression::Num(Num::FloatingPoint(-1.5)),
+                    Expression::Num(Num::Integer(2))
+                )))
+                .into()
+            ),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_minus_without_space_is_subtraction() {
+        for input in ["a -5", "a - 5"] {
+            let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+
+            assert_eq!(
+                Ok(Expression::Binary(Box::new(BinaryExpression::Substraction(
+                    Expression::Id(Id("a".into())),
+                    Expression::Num(Num::Integer(5))
+                )))
+                .into()),
+                Expression::parse(&mut tokens)
+            );
+        }
+    }
+
     #[test]
     fn test_complex_minus() {
         let mut tokens = Lexer::new("-someFunction()")
@@ -624,6 +2660,7 @@ mod tests {
             Ok(Expression::Prefix(Prefix::Minus {
                 expr: Box::new(Expression::Postfix(Postfix::Call {
                     expr: Box::new(Expression::Id(Id("someFunction".into()))),
+                    type_args: vec![],
                     args: vec![]
                 }))
             })
@@ -660,6 +2697,7 @@ mod tests {
             Ok(Expression::Prefix(Prefix::Negation {
                 expr: Box::new(Expression::Postfix(Postfix::Call {
                     expr: Box::new(Expression::Id(Id("someFunction".into()))),
+                    type_args: vec![],
                     args: vec![]
                 }))
             })
@@ -667,4 +2705,189 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_minus_binds_stronger_than_binary_operators() {
+        let mut tokens = Lexer::new("-a * b + c")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Addition(
+                Expression::Binary(Box::new(BinaryExpression::Multiplication(
+                    Expression::Prefix(Prefix::Minus {
+                        expr: Box::new(Expression::Id(Id("a".into())))
+                    }),
+                    Expression::Id(Id("b".into()))
+                ))),
+                Expression::Id(Id("c".into()))
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_minus_of_parenthesized_expression() {
+        let mut tokens = Lexer::new("-(a + b)")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Prefix(Prefix::Minus {
+                expr: Box::new(Expression::Parens(Box::new(Expression::Binary(Box::new(
+                    BinaryExpression::Addition(
+                        Expression::Id(Id("a".into())),
+                        Expression::Id(Id("b".into()))
+                    )
+                )))))
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_nested_negation() {
+        let mut tokens = Lexer::new("!!x == y")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Equal(
+                Expression::Prefix(Prefix::Negation {
+                    expr: Box::new(Expression::Prefix(Prefix::Negation {
+                        expr: Box::new(Expression::Id(Id("x".into())))
+                    }))
+                }),
+                Expression::Id(Id("y".into()))
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_subtraction_of_negative_value() {
+        let mut tokens = Lexer::new("a - -b")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Substraction(
+                Expression::Id(Id("a".into())),
+                Expression::Prefix(Prefix::Minus {
+                    expr: Box::new(Expression::Id(Id("b".into())))
+                })
+            )))
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_division_and_regex() {
+        let mut tokens = Lexer::new("a / 2 == /2/").lex().unwrap().into();
+
+        let result = Expression::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Expression::Binary(Box::new(BinaryExpression::Equal(
+                Expression::Binary(Box::new(BinaryExpression::Division(
+                    Expression::Id(Id("a".into())),
+                    Expression::Num(Num::Integer(2))
+                ))),
+                Expression::Regex(RegexLiteral {
+                    pattern: "2".into(),
+                    flags: "".into()
+                })
+            )))
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let mut tokens = Lexer::new("0..10")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Range {
+                start: Box::new(Expression::Num(Num::Integer(0))),
+                end: Box::new(Expression::Num(Num::Integer(10))),
+                inclusive: false
+            }
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_range_binds_weaker_than_binary_operators() {
+        let mut tokens = Lexer::new("-a + 1..=n * 2")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Range {
+                start: Box::new(Expression::Binary(Box::new(BinaryExpression::Addition(
+                    Expression::Prefix(Prefix::Minus {
+                        expr: Box::new(Expression::Id(Id("a".into())))
+                    }),
+                    Expression::Num(Num::Integer(1))
+                )))),
+                end: Box::new(Expression::Binary(Box::new(
+                    BinaryExpression::Multiplication(
+                        Expression::Id(Id("n".into())),
+                        Expression::Num(Num::Integer(2))
+                    )
+                ))),
+                inclusive: true
+            }
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_range_as_index() {
+        let mut tokens = Lexer::new("arr[1..3]")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Expression::Slice {
+                expr: Box::new(Expression::Id(Id("arr".into()))),
+                start: Box::new(Expression::Num(Num::Integer(1))),
+                end: Box::new(Expression::Num(Num::Integer(3))),
+                inclusive: false
+            }
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_chained_range_fails() {
+        let mut tokens = Lexer::new("1..2..3")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Some(Position::new(4, 2)),
+            Expression::parse(&mut tokens)
+                .err()
+                .and_then(|error| error.position)
+        );
+    }
 }
@@ -1,27 +1,67 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
     lexer::{Token, Tokens},
     parser::{ast::AstNode, FromTokens, ParseError},
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Num {
     Integer(u64),
+    /// A negative integer (stored as its absolute value). Integer literals are unsigned, so this
+    /// only results from folding a prefix minus into a literal (e.g., `-5`).
+    NegativeInteger(u64),
     FloatingPoint(f64),
+    /// A malformed literal, which was already reported by the lexer. It is kept, so the rest of
+    /// the input can still be parsed.
+    Invalid(String),
 }
 
 impl Eq for Num {}
 
+impl Num {
+    /// The integer with the given value, if its absolute value fits into 64 bits.
+    pub fn from_signed(value: i128) -> Option<Num> {
+        let magnitude = u64::try_from(value.unsigned_abs()).ok()?;
+
+        match value < 0 {
+            true => Some(Num::NegativeInteger(magnitude)),
+            false => Some(Num::Integer(magnitude)),
+        }
+    }
+
+    /// The value of this number, if it is an integer.
+    pub fn as_signed(&self) -> Option<i128> {
+        match self {
+            Num::Integer(value) => Some(i128::from(*value)),
+            Num::NegativeInteger(value) => Some(-i128::from(*value)),
+            Num::FloatingPoint(_) | Num::Invalid(_) => None,
+        }
+    }
+
+    /// The negation of this number, which does not exist for invalid literals.
+    pub fn negate(&self) -> Option<Num> {
+        match self {
+            Num::FloatingPoint(value) => Some(Num::FloatingPoint(-value)),
+            Num::Invalid(_) => None,
+            integer => Num::from_signed(-integer.as_signed()?),
+        }
+    }
+}
+
 impl FromTokens<Token> for Num {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError>
     where
         Self: Sized,
     {
         match tokens.next() {
-            Some(Token::Integer { value, .. }) => Ok(Num::Integer(value).into()),
-            Some(Token::FloatingPoint { value, .. }) => Ok(Num::FloatingPoint(value).into()),
+            Some(Token::Integer { value, .. }) => Ok(Num::Integer(*value).into()),
+            Some(Token::FloatingPoint { value, .. }) => Ok(Num::FloatingPoint(*value).into()),
+            Some(Token::InvalidNumber { value, .. }) => Ok(Num::Invalid(value.clone()).into()),
             Some(token) => Err(ParseError {
                 message: "Tried to parse Num from non Num token".into(),
                 position: Some(token.position()),
+                notes: vec![],
             }),
             None => Err(ParseError::eof("Id")),
         }
@@ -36,15 +76,27 @@ impl From<Num> for AstNode {
 
 #[cfg(test)]
 mod tests {
-    use crate::lexer::Lexer;
+    use crate::lexer::{Lexer, Position};
 
     use super::*;
 
+    #[test]
+    fn test_negate() {
+        assert_eq!(Some(Num::NegativeInteger(5)), Num::Integer(5).negate());
+        assert_eq!(Some(Num::Integer(5)), Num::NegativeInteger(5).negate());
+        assert_eq!(Some(Num::Integer(0)), Num::Integer(0).negate());
+        assert_eq!(
+            Some(Num::NegativeInteger(u64::MAX)),
+            Num::Integer(u64::MAX).negate()
+        );
+        assert_eq!(None, Num::from_signed(-i128::from(u64::MAX) - 1));
+    }
+
     #[test]
     fn test_parse() {
         let tokens = vec![Token::Integer {
             value: 42,
-            position: 0,
+            position: Position::default(),
         }];
         assert_eq!(
             Num::parse(&mut tokens.into()),
@@ -56,7 +108,7 @@ mod tests {
     fn test_error_on_non_num() {
         let tokens = vec![Token::Id {
             value: "some_id".into(),
-            position: 0,
+            position: Position::default(),
         }];
         assert!(Num::parse(&mut tokens.into()).is_err());
     }
@@ -75,4 +127,14 @@ mod tests {
 
         assert_eq!(Ok(Num::FloatingPoint(1337.42).into()), result);
     }
+
+    #[test]
+    fn test_parse_invalid_number() {
+        let (tokens, errors) = Lexer::new("0xZZ").lex_recovering();
+
+        let result = Num::parse(&mut tokens.into());
+
+        assert_eq!(1, errors.len());
+        assert_eq!(Ok(Num::Invalid("0xZZ".into()).into()), result);
+    }
 }
@@ -0,0 +1,173 @@
+use crate::lexer::Token;
+
+use super::{BinaryExpression, Expression};
+
+/// How a chain of operators with the same precedence is grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Associativity {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+    /// `a ** b ** c` is `a ** (b ** c)`.
+    Right,
+    /// The operator can not be chained at all, which is reported with the given message.
+    None(&'static str),
+}
+
+/// What an infix operator builds from its operands.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Operation {
+    Binary(fn(Expression, Expression) -> BinaryExpression),
+    /// `&&`, which only evaluates its right operand if the left one holds.
+    LazyAnd,
+    /// `||`, which only evaluates its right operand if the left one does not hold.
+    LazyOr,
+    Range {
+        inclusive: bool,
+    },
+    /// `? then_branch : else_branch`, where the condition is the left operand.
+    Ternary,
+    /// `value |> function`, which passes the left operand to the right one.
+    Pipe,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Operator {
+    pub precedence: u8,
+    pub associativity: Associativity,
+    pub operation: Operation,
+}
+
+const CHAINED_COMPARISON: &str =
+    "comparison operators are non-associative and can not be chained, \
+    compare each pair separately instead (e.g., `(a < b) && (b < c)` instead of `a < b < c`)";
+
+const CHAINED_RANGE: &str = "ranges can not be chained, use parentheses to nest them";
+
+impl Operator {
+    /// The precedence of `**`, the only infix operator binding stronger than prefix operators,
+    /// e.g., `-x ** 2` is `-(x ** 2)`.
+    pub const POWER_PRECEDENCE: u8 = 12;
+
+    /// The infix operator starting with the given token (if any). Operators with a higher
+    /// precedence bind stronger, e.g., bitwise operators bind stronger than comparisons, so
+    /// `a & mask == 0` compares `a & mask`.
+    pub fn of(token: &Token) -> Option<Operator> {
+        use Associativity::*;
+        use BinaryExpression::*;
+
+        let (precedence, associativity, operation) = match token {
+            Token::QuestionMark { .. } => (0, Right, Operation::Ternary),
+            Token::DotDot { .. } => (
+                1,
+                None(CHAINED_RANGE),
+                Operation::Range { inclusive: false },
+            ),
+            Token::DotDotEqual { .. } => {
+                (1, None(CHAINED_RANGE), Operation::Range { inclusive: true })
+            }
+            Token::PipeRightArrow { .. } => (2, Left, Operation::Pipe),
+            Token::DoublePipe { .. } => (3, Left, Operation::LazyOr),
+            Token::DoubleAmpersand { .. } => (4, Left, Operation::LazyAnd),
+            Token::Equal { .. } => (5, None(CHAINED_COMPARISON), Operation::Binary(Equal)),
+            Token::GreaterThan { .. } => {
+                (5, None(CHAINED_COMPARISON), Operation::Binary(GreaterThan))
+            }
+            Token::LessThan { .. } => (5, None(CHAINED_COMPARISON), Operation::Binary(LessThen)),
+            Token::GreaterOrEqual { .. } => (
+                5,
+                None(CHAINED_COMPARISON),
+                Operation::Binary(GreaterOrEqual),
+            ),
+            Token::LessOrEqual { .. } => {
+                (5, None(CHAINED_COMPARISON), Operation::Binary(LessOrEqual))
+            }
+            Token::Pipe { .. } => (6, Left, Operation::Binary(BitOr)),
+            Token::Caret { .. } => (7, Left, Operation::Binary(BitXor)),
+            // after an operand, `&` can only be a bitwise and (instead of a reference)
+            Token::Ampersand { .. } => (8, Left, Operation::Binary(BitAnd)),
+            Token::ShiftLeft { .. } => (9, Left, Operation::Binary(ShiftLeft)),
+            Token::ShiftRight { .. } => (9, Left, Operation::Binary(ShiftRight)),
+            Token::Plus { .. } => (10, Left, Operation::Binary(Addition)),
+            Token::Minus { .. } => (10, Left, Operation::Binary(Substraction)),
+            Token::Times { .. } => (11, Left, Operation::Binary(Multiplication)),
+            Token::Slash { .. } => (11, Left, Operation::Binary(Division)),
+            Token::Percent { .. } => (11, Left, Operation::Binary(Modulo)),
+            Token::DoubleTimes { .. } => (
+                Self::POWER_PRECEDENCE,
+                Right,
+                Operation::Binary(Exponentiation),
+            ),
+            _ => return Option::None,
+        };
+
+        Some(Operator {
+            precedence,
+            associativity,
+            operation,
+        })
+    }
+
+    /// The minimal precedence of the operators within the right operand.
+    pub fn right_precedence(&self) -> u8 {
+        match self.associativity {
+            Associativity::Right => self.precedence,
+            Associativity::Left | Associativity::None(_) => self.precedence + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{Position, Token};
+
+    use super::{Associativity, Operator};
+
+    fn operator(token: Token) -> Operator {
+        Operator::of(&token).expect("should be an operator")
+    }
+
+    #[test]
+    fn test_precedence() {
+        let position = Position::new(0, 1);
+
+        let ternary = operator(Token::QuestionMark { position });
+        let range = operator(Token::DotDot { position });
+        let pipe = operator(Token::PipeRightArrow { position });
+        let or = operator(Token::DoublePipe { position });
+        let comparison = operator(Token::LessThan { position });
+        let bit_and = operator(Token::Ampersand { position });
+        let plus = operator(Token::Plus { position });
+        let times = operator(Token::Times { position });
+        let power = operator(Token::DoubleTimes { position });
+
+        assert!(ternary.precedence < range.precedence);
+        assert!(range.precedence < pipe.precedence);
+        assert!(pipe.precedence < or.precedence);
+        assert!(or.precedence < comparison.precedence);
+        assert!(comparison.precedence < bit_and.precedence);
+        assert!(bit_and.precedence < plus.precedence);
+        assert!(plus.precedence < times.precedence);
+        assert!(times.precedence < power.precedence);
+    }
+
+    #[test]
+    fn test_right_precedence() {
+        let position = Position::new(0, 1);
+
+        let minus = operator(Token::Minus { position });
+        assert_eq!(Associativity::Left, minus.associativity);
+        assert_eq!(minus.precedence + 1, minus.right_precedence());
+
+        let power = operator(Token::DoubleTimes { position });
+        assert_eq!(Associativity::Right, power.associativity);
+        assert_eq!(power.precedence, power.right_precedence());
+    }
+
+    #[test]
+    fn test_no_operator() {
+        let position = Position::new(0, 1);
+
+        assert!(Operator::of(&Token::LParen { position }).is_none());
+        assert!(Operator::of(&Token::Dot { position }).is_none());
+    }
+}
@@ -1,9 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::parser::ast::TypeName;
+
 use super::{Expression, Id};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Postfix {
     Call {
         expr: Box<Expression>,
+        /// Explicit generic arguments, e.g., `identity::<i32>(5)`.
+        type_args: Vec<TypeName>,
         args: Vec<Expression>,
     },
     Index {
@@ -14,4 +20,20 @@ pub enum Postfix {
         expr: Box<Expression>,
         property: Id,
     },
+    MethodCall(Box<MethodCall>),
+    /// Positional access of a tuple element, e.g., `t.0`.
+    TupleAccess {
+        expr: Box<Expression>,
+        index: u64,
+    },
+}
+
+/// `receiver.method(args)`, i.e., a call of a method (instead of a property holding a function).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodCall {
+    pub receiver: Expression,
+    pub method: Id,
+    /// Explicit generic arguments, e.g., `xs.map::<i32>(f)`.
+    pub type_args: Vec<TypeName>,
+    pub args: Vec<Expression>,
 }
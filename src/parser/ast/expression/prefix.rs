@@ -1,7 +1,35 @@
-use super::Expression;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use super::{Expression, Num};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Prefix {
-    Negation { expr: Box<Expression> },
-    Minus { expr: Box<Expression> },
+    Negation {
+        expr: Box<Expression>,
+    },
+    Minus {
+        expr: Box<Expression>,
+    },
+    /// Bitwise not (`~`).
+    BitNot {
+        expr: Box<Expression>,
+    },
+}
+
+impl Prefix {
+    /// Fold a minus in front of a number literal into a negative literal, e.g., `-5` is the
+    /// number -5 instead of the negation of 5.
+    pub(crate) fn fold_literal(self) -> Expression {
+        match self {
+            Prefix::Minus { expr } => match *expr {
+                Expression::Num(num @ (Num::Integer(_) | Num::FloatingPoint(_))) => {
+                    Expression::Num(num.negate().expect("valid numbers can be negated"))
+                }
+                expr => Expression::Prefix(Prefix::Minus {
+                    expr: Box::new(expr),
+                }),
+            },
+            prefix => Expression::Prefix(prefix),
+        }
+    }
 }
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lexer::{Token, Tokens},
+    parser::{ast::AstNode, FromTokens, ParseError},
+};
+
+/// `/pattern/flags`. The pattern is kept raw (i.e., escapes are not processed).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegexLiteral {
+    pub pattern: String,
+    pub flags: String,
+}
+
+impl FromTokens<Token> for RegexLiteral {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        match tokens.next() {
+            Some(Token::RegexLiteral { value, .. }) => {
+                // the lexer ensures the literal to be terminated by a `/`, which is the last one
+                let end = value.rfind('/').expect("regex literal is terminated");
+
+                Ok(RegexLiteral {
+                    pattern: value[1..end].to_string(),
+                    flags: value[end + 1..].to_string(),
+                }
+                .into())
+            }
+            Some(token) => Err(ParseError {
+                message: "Tried to parse RegexLiteral from non RegexLiteral token".into(),
+                position: Some(token.position()),
+                notes: vec![],
+            }),
+            None => Err(ParseError::eof("regex literal")),
+        }
+    }
+}
+
+impl From<RegexLiteral> for AstNode {
+    fn from(value: RegexLiteral) -> Self {
+        AstNode::RegexLiteral(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, parser::ast::Expression};
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mut tokens = Lexer::new("/a\\/b+/gi")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(RegexLiteral {
+                pattern: "a\\/b+".into(),
+                flags: "gi".into()
+            }
+            .into()),
+            RegexLiteral::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_as_expression() {
+        let mut tokens = Lexer::new("/x/ ").lex().expect("something is wrong").into();
+
+        assert_eq!(
+            Ok(Expression::Regex(RegexLiteral {
+                pattern: "x".into(),
+                flags: "".into()
+            })
+            .into()),
+            Expression::parse(&mut tokens)
+        );
+    }
+}
@@ -1,76 +1,100 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    lexer::{Token, Tokens},
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
     parser::{ast::AstNode, combinators::Comb, FromTokens, ParseError},
 };
 
 use super::{Expression, Id};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StructInitialisation {
+    /// The path in front of the name, e.g., `Shape` in `Shape::Rect { w: 1.0, h: 2.0 }`.
+    pub path: Vec<Id>,
     pub id: Id,
     pub fields: Vec<StructFieldInitialisation>,
 }
 
+impl StructInitialisation {
+    /// `Name { field: value, ... }` or `path::Name { field: value, ... }`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(
+            ((Comb::ID >> Comb::DOUBLE_COLON) ^ ())
+                >> Comb::ID
+                >> Comb::LBRACE
+                >> (Comb::STRUCT_FIELD_INITIALISATION % Comb::COMMA)
+                >> !Comb::COMMA
+                >> Comb::RBRACE
+        )
+    }
+}
+
 impl FromTokens<Token> for StructInitialisation {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::ID
-            >> Comb::LBRACE
-            >> (Comb::STRUCT_FIELD_INITIALISATION % Comb::COMMA)
-            >> Comb::RBRACE;
+        let matcher = Self::grammar();
 
-        let mut result = matcher.parse(tokens)?.into_iter();
-
-        let Some(AstNode::Id(id)) = result.next() else {
-            unreachable!();
-        };
+        let result = matcher.parse(tokens)?.into_iter();
 
+        let mut path = vec![];
         let mut fields = vec![];
 
-        while let Some(AstNode::StructFieldInitialisation(field)) = result.next() {
-            fields.push(field);
+        for node in result {
+            match node {
+                AstNode::Id(segment) => path.push(segment),
+                AstNode::StructFieldInitialisation(field) => fields.push(*field),
+                _ => unreachable!(),
+            }
         }
 
-        Ok(StructInitialisation { id, fields }.into())
+        let Some(id) = path.pop() else {
+            unreachable!();
+        };
+
+        Ok(StructInitialisation { path, id, fields }.into())
     }
 }
 
 impl From<StructInitialisation> for AstNode {
     fn from(value: StructInitialisation) -> Self {
-        Self::StructInitialisation(value)
+        Self::StructInitialisation(Box::new(value))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StructFieldInitialisation {
     pub name: Id,
     pub value: Expression,
 }
 
+impl StructFieldInitialisation {
+    /// `field: value`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::ID >> Comb::COLON >> Comb::EXPR)
+    }
+}
+
 impl FromTokens<Token> for StructFieldInitialisation {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::ID >> Comb::COLON >> Comb::EXPR;
+        let matcher = Self::grammar();
 
-        let result = matcher.parse(tokens)?;
+        let mut result = matcher.parse(tokens)?.into_iter();
 
-        let Some(AstNode::Id(name)) = result.first() else {
+        let Some(AstNode::Id(name)) = result.next() else {
             unreachable!();
         };
 
-        let Some(AstNode::Expression(value)) = result.get(1) else {
+        let Some(AstNode::Expression(value)) = result.next() else {
             unreachable!();
         };
 
-        Ok(StructFieldInitialisation {
-            name: name.clone(),
-            value: value.clone(),
-        }
-        .into())
+        Ok(StructFieldInitialisation { name, value }.into())
     }
 }
 
 impl From<StructFieldInitialisation> for AstNode {
     fn from(value: StructFieldInitialisation) -> Self {
-        Self::StructFieldInitialisation(value)
+        Self::StructFieldInitialisation(Box::new(value))
     }
 }
 
@@ -116,6 +140,7 @@ mod tests {
 
         assert_eq!(
             Ok(StructInitialisation {
+                path: vec![],
                 id: Id("Foo".into()),
                 fields: vec![]
             }
@@ -135,6 +160,7 @@ mod tests {
 
         assert_eq!(
             Ok(StructInitialisation {
+                path: vec![],
                 id: Id("Foo".into()),
                 fields: vec![StructFieldInitialisation {
                     name: Id("bar".into()),
@@ -157,6 +183,7 @@ mod tests {
 
         assert_eq!(
             Ok(StructInitialisation {
+                path: vec![],
                 id: Id("Foo".into()),
                 fields: vec![
                     StructFieldInitialisation {
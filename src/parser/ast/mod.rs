@@ -1,31 +1,82 @@
+mod attribute;
+mod doc;
 mod expression;
+mod pattern;
 mod statement;
 mod type_name;
+mod visibility;
 
+pub use self::attribute::*;
+pub(crate) use self::doc::*;
 pub use self::expression::*;
+pub use self::pattern::*;
 pub use self::statement::*;
 pub use self::type_name::*;
+pub use self::visibility::*;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use std::mem::size_of;
+
+use serde::{Deserialize, Serialize};
+
+/// Upper bound (in bytes) for the size of the AST enums, so a node fits into a single cache line.
+/// Large variants are boxed to stay below it.
+pub const MAX_NODE_SIZE: usize = 64;
+
+const _: () = assert!(size_of::<Expression>() <= MAX_NODE_SIZE);
+const _: () = assert!(size_of::<Statement>() <= MAX_NODE_SIZE);
+const _: () = assert!(size_of::<AstNode>() <= MAX_NODE_SIZE);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AstNode {
     Expression(Expression),
     Id(Id),
     Num(Num),
+    Bool(Bool),
+    RegexLiteral(RegexLiteral),
     Statement(Statement),
-    Initialization(Initialisation),
-    Constant(Constant),
-    Assignment(Assignment),
-    Function(Function),
+    Initialization(Box<Initialisation>),
+    Constant(Box<Constant>),
+    Assignment(Box<Assignment>),
+    Function(Box<Function>),
     Lambda(Lambda),
-    If(If),
-    WhileLoop(WhileLoop),
+    If(Box<If>),
+    WhileLoop(Box<WhileLoop>),
+    InfiniteLoop(Box<InfiniteLoop>),
+    Conditional(Box<Conditional>),
+    ImplBlock(Box<ImplBlock>),
+    TraitDeclaration(Box<TraitDeclaration>),
+    Module(Box<Module>),
     Parameter(Parameter),
     TypeName(TypeName),
     Block(Block),
     Array(Array),
     Declaration(Declaration),
-    StructDeclaration(StructDeclaration),
-    StructFieldDeclaration(StructFieldDeclaration),
-    StructInitialisation(StructInitialisation),
-    StructFieldInitialisation(StructFieldInitialisation),
+    StructDeclaration(Box<StructDeclaration>),
+    EnumDeclaration(Box<EnumDeclaration>),
+    TypeAlias(Box<TypeAlias>),
+    ExternBlock(Box<ExternBlock>),
+    StructFieldDeclaration(Box<StructFieldDeclaration>),
+    StructInitialisation(Box<StructInitialisation>),
+    StructFieldInitialisation(Box<StructFieldInitialisation>),
+    Import(Import),
+    Pattern(Pattern),
+    Match(Box<Match>),
+    MatchArm(Box<MatchArm>),
+    Property(Box<Property>),
+    Comprehension(Box<Comprehension>),
+    Asm(Box<Asm>),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use super::*;
+
+    #[test]
+    fn test_node_sizes() {
+        assert!(size_of::<Expression>() <= MAX_NODE_SIZE);
+        assert!(size_of::<Statement>() <= MAX_NODE_SIZE);
+        assert!(size_of::<AstNode>() <= MAX_NODE_SIZE);
+    }
 }
@@ -0,0 +1,508 @@
+use serde::{Deserialize, Serialize};
+
+use crate::lazy_comb;
+use crate::lexer::{Token, Tokens};
+use crate::parser::{combinators::Comb, ensure_unique, FromTokens, ParseError};
+
+use super::{AstNode, Id};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pattern {
+    /// `_`, matches everything without binding it.
+    Wildcard,
+    /// Matches everything and binds it to the given name.
+    Id(Id),
+    Literal(LiteralPattern),
+    /// `start..end` or `start..=end`.
+    Range {
+        start: LiteralPattern,
+        end: LiteralPattern,
+        inclusive: bool,
+    },
+    /// `(a, b, ...)`, destructures a tuple.
+    Tuple(Vec<Pattern>),
+    /// `name @ pattern`, binds the value to the given name if it matches the inner pattern.
+    Binding {
+        name: Id,
+        pattern: Box<Pattern>,
+    },
+    /// `Name { field, other: pattern }`, destructures a struct.
+    Struct {
+        name: Id,
+        fields: Vec<FieldPattern>,
+    },
+}
+
+/// A single field within a struct pattern. The shorthand `field` binds the field to a variable of
+/// the same name, i.e., it is the same as `field: field`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldPattern {
+    pub name: Id,
+    pub pattern: Pattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiteralPattern {
+    Bool(bool),
+    Integer(i64),
+    String(String),
+    Char(char),
+}
+
+impl FromTokens<Token> for Pattern {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        match tokens.peek() {
+            Some(Token::Id { value, .. }) if value.as_str() == "_" => {
+                tokens.next();
+                Ok(Pattern::Wildcard.into())
+            }
+            Some(Token::Id { value, .. }) => {
+                let id = Id(value.clone());
+                tokens.next();
+
+                match tokens.peek() {
+                    Some(Token::At { .. }) => {
+                        tokens.next();
+                    }
+                    Some(Token::LBrace { .. }) => {
+                        return Ok(Pattern::Struct {
+                            name: id,
+                            fields: Self::parse_fields(tokens)?,
+                        }
+                        .into())
+                    }
+                    _ => return Ok(Pattern::Id(id).into()),
+                }
+
+                let mut result = Comb::PATTERN.parse(tokens)?.into_iter();
+                let Some(AstNode::Pattern(pattern)) = result.next() else {
+                    unreachable!()
+                };
+
+                Ok(Pattern::Binding {
+                    name: id,
+                    pattern: Box::new(pattern),
+                }
+                .into())
+            }
+            Some(Token::LParen { .. }) => {
+                let matcher = lazy_comb!(Comb::LPAREN >> (Comb::PATTERN % Comb::COMMA));
+                let mut elements = matcher
+                    .parse(tokens)?
+                    .into_iter()
+                    .map(|element| {
+                        let AstNode::Pattern(element) = element else {
+                            unreachable!()
+                        };
+                        element
+                    })
+                    .collect::<Vec<_>>();
+
+                let trailing_comma = matches!(tokens.peek(), Some(Token::Comma { .. }));
+                if trailing_comma {
+                    tokens.next();
+                }
+                Comb::RPAREN.parse(tokens)?;
+
+                // a single parenthesized pattern is only a tuple with a trailing comma, e.g., `(a,)`
+                if elements.len() == 1 && !trailing_comma {
+                    return Ok(elements.remove(0).into());
+                }
+
+                Ok(Pattern::Tuple(elements).into())
+            }
+            Some(token) => {
+                // `true` and `false` are keywords, but also literals
+                if let Some(error) = Id::keyword_error(token).filter(|_| {
+                    !matches!(
+                        token,
+                        Token::TrueKeyword { .. } | Token::FalseKeyword { .. }
+                    )
+                }) {
+                    return Err(error);
+                }
+
+                let start = Self::parse_literal(tokens)?;
+
+                let inclusive = match tokens.peek() {
+                    Some(Token::DotDot { .. }) => false,
+                    Some(Token::DotDotEqual { .. }) => true,
+                    _ => return Ok(Pattern::Literal(start).into()),
+                };
+                tokens.next();
+
+                let end = Self::parse_literal(tokens)?;
+
+                Ok(Pattern::Range {
+                    start,
+                    end,
+                    inclusive,
+                }
+                .into())
+            }
+            None => Err(ParseError::eof("pattern")),
+        }
+    }
+}
+
+impl Pattern {
+    /// Whether this pattern matches every value (and can, therefore, be used in a `let`).
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Pattern::Wildcard | Pattern::Id(_) => true,
+            Pattern::Literal(_) | Pattern::Range { .. } => false,
+            Pattern::Tuple(elements) => elements.iter().all(Pattern::is_irrefutable),
+            Pattern::Binding { pattern, .. } => pattern.is_irrefutable(),
+            Pattern::Struct { fields, .. } => {
+                fields.iter().all(|field| field.pattern.is_irrefutable())
+            }
+        }
+    }
+
+    /// Parse the fields of a struct pattern, i.e., `{ field, other: pattern }` (with an optional
+    /// trailing comma).
+    fn parse_fields(tokens: &mut Tokens<Token>) -> Result<Vec<FieldPattern>, ParseError> {
+        Comb::LBRACE.parse(tokens)?;
+
+        let mut fields = vec![];
+        let mut names = vec![];
+
+        while !matches!(tokens.peek(), Some(Token::RBrace { .. })) {
+            let position = tokens.peek().map(Token::position).unwrap_or_default();
+
+            let Some(AstNode::Id(name)) = Comb::ID.parse(tokens)?.into_iter().next() else {
+                unreachable!()
+            };
+
+            let pattern = match tokens.peek() {
+                Some(Token::Colon { .. }) => {
+                    tokens.next();
+
+                    let Some(AstNode::Pattern(pattern)) =
+                        Comb::PATTERN.parse(tokens)?.into_iter().next()
+                    else {
+                        unreachable!()
+                    };
+                    pattern
+                }
+                _ => Pattern::Id(name.clone()),
+            };
+
+            names.push((name.0.clone(), position));
+            fields.push(FieldPattern { name, pattern });
+
+            if !matches!(tokens.peek(), Some(Token::Comma { .. })) {
+                break;
+            }
+            tokens.next();
+        }
+
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique("field", names)?;
+
+        Ok(fields)
+    }
+
+    fn parse_literal(tokens: &mut Tokens<Token>) -> Result<LiteralPattern, ParseError> {
+        let negative = matches!(tokens.peek(), Some(Token::Minus { .. }));
+        if negative {
+            tokens.next();
+        }
+
+        let literal = match tokens.next() {
+            Some(Token::Integer { value, position }) => {
+                // negate before the conversion, since `i64::MIN` has no positive counterpart
+                let value = match negative {
+                    true => -i128::from(*value),
+                    false => i128::from(*value),
+                };
+                let value = i64::try_from(value).map_err(|_| ParseError {
+                    message: format!("integer pattern {value} is too large"),
+                    position: Some(*position),
+                    notes: vec![],
+                })?;
+                LiteralPattern::Integer(value)
+            }
+            Some(Token::TrueKeyword { .. }) if !negative => LiteralPattern::Bool(true),
+            Some(Token::FalseKeyword { .. }) if !negative => LiteralPattern::Bool(false),
+            Some(Token::StringLiteral { value, .. }) if !negative => {
+                LiteralPattern::String(value.clone())
+            }
+            Some(Token::CharLiteral { value, position }) if !negative => {
+                let value = single_char(value).ok_or_else(|| ParseError {
+                    message: format!("invalid character literal '{value}'"),
+                    position: Some(*position),
+                    notes: vec![],
+                })?;
+                LiteralPattern::Char(value)
+            }
+            Some(token) => {
+                return Err(ParseError {
+                    message: format!("Unexpected {token:?} while trying to parse pattern"),
+                    position: Some(token.position()),
+                    notes: vec![],
+                })
+            }
+            None => return Err(ParseError::eof("pattern")),
+        };
+
+        Ok(literal)
+    }
+}
+
+/// The character of a (decoded) character literal, if it consists of exactly one.
+fn single_char(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    let c = chars.next()?;
+
+    chars.next().is_none().then_some(c)
+}
+
+impl From<Pattern> for AstNode {
+    fn from(value: Pattern) -> Self {
+        AstNode::Pattern(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::{Lexer, Position},
+        parser::FromTokens,
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<AstNode, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+        Pattern::parse(&mut tokens)
+    }
+
+    #[test]
+    fn test_parse_wildcard() {
+        assert_eq!(Ok(Pattern::Wildcard.into()), parse("_"));
+    }
+
+    #[test]
+    fn test_parse_id() {
+        assert_eq!(Ok(Pattern::Id(Id("n".into())).into()), parse("n"));
+    }
+
+    #[test]
+    fn test_parse_integer_literal() {
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Integer(42)).into()),
+            parse("42")
+        );
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Integer(-1)).into()),
+            parse("-1")
+        );
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Integer(i64::MIN)).into()),
+            parse("-9223372036854775808")
+        );
+        assert!(parse("9223372036854775808").is_err());
+    }
+
+    #[test]
+    fn test_parse_string_literal() {
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::String("hello world".into())).into()),
+            parse("\"hello world\"")
+        );
+    }
+
+    #[test]
+    fn test_parse_char_literal() {
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Char('a')).into()),
+            parse("'a'")
+        );
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Char('\n')).into()),
+            parse("'\\n'")
+        );
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Char('A')).into()),
+            parse("'\\u{41}'")
+        );
+    }
+
+    #[test]
+    fn test_parse_bool_literal() {
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Bool(true)).into()),
+            parse("true")
+        );
+        assert_eq!(
+            Ok(Pattern::Literal(LiteralPattern::Bool(false)).into()),
+            parse("false")
+        );
+        assert!(parse("-true").is_err());
+    }
+
+    #[test]
+    fn test_parse_inclusive_range() {
+        assert_eq!(
+            Ok(Pattern::Range {
+                start: LiteralPattern::Integer(1),
+                end: LiteralPattern::Integer(9),
+                inclusive: true
+            }
+            .into()),
+            parse("1..=9")
+        );
+    }
+
+    #[test]
+    fn test_parse_exclusive_char_range() {
+        assert_eq!(
+            Ok(Pattern::Range {
+                start: LiteralPattern::Char('a'),
+                end: LiteralPattern::Char('z'),
+                inclusive: false
+            }
+            .into()),
+            parse("'a'..'z'")
+        );
+    }
+
+    #[test]
+    fn test_parse_negative_string_fails() {
+        assert!(parse("-\"foo\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_tuple() {
+        assert_eq!(
+            Ok(Pattern::Tuple(vec![
+                Pattern::Id(Id("q".into())),
+                Pattern::Tuple(vec![
+                    Pattern::Wildcard,
+                    Pattern::Literal(LiteralPattern::Integer(1))
+                ])
+            ])
+            .into()),
+            parse("(q, (_, 1))")
+        );
+    }
+
+    #[test]
+    fn test_parse_parenthesized() {
+        assert_eq!(Ok(Pattern::Id(Id("a".into())).into()), parse("(a)"));
+        assert_eq!(
+            Ok(Pattern::Tuple(vec![Pattern::Id(Id("a".into()))]).into()),
+            parse("(a,)")
+        );
+        assert_eq!(Ok(Pattern::Tuple(vec![]).into()), parse("()"));
+    }
+
+    #[test]
+    fn test_is_irrefutable() {
+        assert!(
+            Pattern::Tuple(vec![Pattern::Wildcard, Pattern::Id(Id("a".into()))]).is_irrefutable()
+        );
+        assert!(!Pattern::Tuple(vec![
+            Pattern::Wildcard,
+            Pattern::Literal(LiteralPattern::Integer(1))
+        ])
+        .is_irrefutable());
+    }
+
+    #[test]
+    fn test_parse_binding() {
+        assert_eq!(
+            Ok(Pattern::Binding {
+                name: Id("n".into()),
+                pattern: Box::new(Pattern::Range {
+                    start: LiteralPattern::Integer(1),
+                    end: LiteralPattern::Integer(9),
+                    inclusive: true
+                })
+            }
+            .into()),
+            parse("n @ 1..=9")
+        );
+    }
+
+    #[test]
+    fn test_parse_struct() {
+        assert_eq!(
+            Ok(Pattern::Struct {
+                name: Id("Line".into()),
+                fields: vec![
+                    FieldPattern {
+                        name: Id("start".into()),
+                        pattern: Pattern::Struct {
+                            name: Id("Point".into()),
+                            fields: vec![
+                                FieldPattern {
+                                    name: Id("x".into()),
+                                    pattern: Pattern::Id(Id("x".into()))
+                                },
+                                FieldPattern {
+                                    name: Id("y".into()),
+                                    pattern: Pattern::Wildcard
+                                }
+                            ]
+                        }
+                    },
+                    FieldPattern {
+                        name: Id("end".into()),
+                        pattern: Pattern::Id(Id("to".into()))
+                    }
+                ]
+            }
+            .into()),
+            parse("Line { start: Point { x, y: _ }, end: to, }")
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_struct() {
+        assert_eq!(
+            Ok(Pattern::Struct {
+                name: Id("Unit".into()),
+                fields: vec![]
+            }
+            .into()),
+            parse("Unit {}")
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_without_closing_brace_fails() {
+        assert!(parse("Point { x y }").is_err());
+    }
+
+    #[test]
+    fn test_parse_struct_with_duplicate_fields_fails() {
+        let Err(error) = parse("Point { x, x: _ }") else {
+            panic!("expected error");
+        };
+
+        assert_eq!("duplicate field 'x'", error.message);
+        assert_eq!(Some(Position::new(11, 1)), error.position);
+        assert_eq!(Position::new(8, 1), error.notes[0].position);
+    }
+
+    #[test]
+    fn test_struct_is_irrefutable() {
+        let Ok(AstNode::Pattern(pattern)) = parse("Point { x, y: _ }") else {
+            panic!("expected pattern");
+        };
+        assert!(pattern.is_irrefutable());
+
+        let Ok(AstNode::Pattern(pattern)) = parse("Point { x: 0, y }") else {
+            panic!("expected pattern");
+        };
+        assert!(!pattern.is_irrefutable());
+    }
+
+    #[test]
+    fn test_parse_binding_without_pattern_fails() {
+        assert!(parse("n @").is_err());
+    }
+}
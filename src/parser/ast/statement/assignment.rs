@@ -1,35 +1,150 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    lexer::{Token, Tokens},
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
     parser::{
-        ast::{AstNode, Expression, Id},
+        ast::{AstNode, Expression, Id, Postfix},
         combinators::Comb,
         FromTokens, ParseError,
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Assignment {
-    pub id: Id,
-    pub value: Expression,
+    pub lvalue: Lvalue,
+    /// The operator of a compound assignment (e.g., `+=`), which is `None` for a plain `=`. It is
+    /// kept instead of desugaring to `place = place + value`, since that would evaluate `place`
+    /// twice.
+    pub operator: Option<CompoundOperator>,
+    pub rvalue: Expression,
+}
+
+/// The place a value is assigned to, i.e., the subset of expressions which may occur on the left
+/// hand side of an assignment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Lvalue {
+    Id(Id),
+    /// An indexed element, e.g., `arr[0]`.
+    Index {
+        expr: Box<Expression>,
+        index: Box<Expression>,
+    },
+    /// A property, e.g., `p.x`.
+    PropertyAccess {
+        expr: Box<Expression>,
+        property: Id,
+    },
+    /// An element of a tuple, e.g., `t.0`.
+    TupleAccess {
+        expr: Box<Expression>,
+        index: u64,
+    },
+}
+
+impl Lvalue {
+    /// Convert the parsed left hand side of an assignment, describing why it can not be assigned
+    /// to otherwise.
+    fn from_expression(expr: Expression) -> Result<Lvalue, &'static str> {
+        match expr {
+            Expression::Id(id) => Ok(Lvalue::Id(id)),
+            Expression::Postfix(Postfix::Index { expr, index }) => Ok(Lvalue::Index { expr, index }),
+            Expression::Postfix(Postfix::PropertyAccess { expr, property }) => {
+                Ok(Lvalue::PropertyAccess { expr, property })
+            }
+            Expression::Postfix(Postfix::TupleAccess { expr, index }) => {
+                Ok(Lvalue::TupleAccess { expr, index })
+            }
+            Expression::Postfix(Postfix::Call { .. } | Postfix::MethodCall(_)) => Err(
+                "can not assign to the result of a call, only to variables, indexed elements and properties",
+            ),
+            Expression::Unit
+            | Expression::Num(_)
+            | Expression::Bool(_)
+            | Expression::String(_)
+            | Expression::FormatString(_) => Err(
+                "can not assign to a literal, only to variables, indexed elements and properties",
+            ),
+            _ => Err("can only assign to variables, indexed elements and properties"),
+        }
+    }
+}
+
+/// The binary operation of a compound assignment like `counter += 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompoundOperator {
+    /// `+=`
+    Add,
+    /// `-=`
+    Sub,
+    /// `*=`
+    Mul,
+    /// `/=`
+    Div,
+    /// `%=`
+    Rem,
+}
+
+impl CompoundOperator {
+    fn from_token(token: &Token) -> Option<CompoundOperator> {
+        match token {
+            Token::PlusAssign { .. } => Some(CompoundOperator::Add),
+            Token::MinusAssign { .. } => Some(CompoundOperator::Sub),
+            Token::TimesAssign { .. } => Some(CompoundOperator::Mul),
+            Token::SlashAssign { .. } => Some(CompoundOperator::Div),
+            Token::PercentAssign { .. } => Some(CompoundOperator::Rem),
+            _ => None,
+        }
+    }
+}
+
+impl Assignment {
+    /// `place = value` or `place += value` (and the other compound operators)
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::EXPR >> Assignment::operator() >> Comb::EXPR)
+    }
+
+    fn operator() -> Comb<'static, Token, Terminal, AstNode> {
+        Comb::ASSIGN
+            | Comb::PLUS_ASSIGN
+            | Comb::MINUS_ASSIGN
+            | Comb::TIMES_ASSIGN
+            | Comb::SLASH_ASSIGN
+            | Comb::PERCENT_ASSIGN
+    }
+
+    /// Whether the token starts the value of an assignment, i.e., is `=` or a compound operator.
+    pub(crate) fn is_operator(token: &Token) -> bool {
+        matches!(token, Token::Assign { .. }) || CompoundOperator::from_token(token).is_some()
+    }
 }
 
 impl FromTokens<Token> for Assignment {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::ID >> Comb::ASSIGN >> Comb::EXPR;
-
-        let result = matcher.parse(tokens)?;
+        let position = tokens.peek().map(Token::position);
 
-        let Some(AstNode::Id(id)) = result.first() else {
+        let Some(AstNode::Expression(lvalue)) = Comb::EXPR.parse(tokens)?.into_iter().next() else {
             unreachable!()
         };
 
-        let Some(AstNode::Expression(value)) = result.get(1) else {
+        let lvalue = Lvalue::from_expression(lvalue).map_err(|message| ParseError {
+            message: message.into(),
+            position,
+            notes: vec![],
+        })?;
+
+        let operator = tokens.peek().and_then(CompoundOperator::from_token);
+
+        let matcher = lazy_comb!(Assignment::operator() >> Comb::EXPR);
+
+        let Some(AstNode::Expression(rvalue)) = matcher.parse(tokens)?.into_iter().next() else {
             unreachable!()
         };
 
         Ok(Assignment {
-            id: id.clone(),
-            value: value.clone(),
+            lvalue,
+            operator,
+            rvalue,
         }
         .into())
     }
@@ -37,6 +152,6 @@ impl FromTokens<Token> for Assignment {
 
 impl From<Assignment> for AstNode {
     fn from(value: Assignment) -> Self {
-        AstNode::Assignment(value)
+        AstNode::Assignment(Box::new(value))
     }
 }
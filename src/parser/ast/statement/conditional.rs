@@ -0,0 +1,467 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{Array, AstNode, Block, Expression, Function, Id, If, Postfix, Prefix, Segment},
+        combinators::Comb,
+        FromTokens, ParseError,
+    },
+};
+
+use super::{ImplItem, Lvalue, Property, Statement};
+
+/// Conditional compilation, e.g.:
+///
+/// ```text
+/// #if DEBUG {
+///     log(x);
+/// } #else {
+///     ...
+/// }
+/// ```
+///
+/// Both branches are kept in the AST until they get resolved with [`prune_conditionals`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conditional {
+    pub feature: Id,
+    pub statements: Vec<Statement>,
+    pub else_statements: Vec<Statement>,
+}
+
+impl FromTokens<Token> for Conditional {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = lazy_comb!(
+            Comb::HASH_IF >> Comb::ID >> Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE
+        );
+
+        let mut result = matcher.parse(tokens)?.into_iter().peekable();
+
+        let Some(AstNode::Id(feature)) = result.next() else {
+            unreachable!()
+        };
+
+        let mut statements = vec![];
+
+        while let Some(AstNode::Statement(statement)) =
+            result.next_if(|item| matches!(item, AstNode::Statement(_)))
+        {
+            statements.push(statement);
+        }
+
+        let matcher = lazy_comb!(
+            !(Comb::HASH_ELSE >> Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE)
+        );
+
+        let mut result = matcher.parse(tokens)?.into_iter().peekable();
+
+        let mut else_statements = vec![];
+
+        while let Some(AstNode::Statement(statement)) =
+            result.next_if(|item| matches!(item, AstNode::Statement(_)))
+        {
+            else_statements.push(statement);
+        }
+
+        Ok(Conditional {
+            feature,
+            statements,
+            else_statements,
+        }
+        .into())
+    }
+}
+
+impl From<Conditional> for AstNode {
+    fn from(value: Conditional) -> Self {
+        AstNode::Conditional(Box::new(value))
+    }
+}
+
+/// Replace every conditional with the statements of its active branch, given the set of defined
+/// features. Nested conditionals are resolved as well, e.g., within functions, impl blocks,
+/// modules or blocks within expressions (like lambdas and match arms). Trait and extern blocks
+/// only contain signatures, so they can not contain any conditionals.
+pub fn prune_conditionals(statements: Vec<Statement>, features: &[&str]) -> Vec<Statement> {
+    let mut pruned = Vec::with_capacity(statements.len());
+
+    for statement in statements {
+        match statement {
+            Statement::Conditional(conditional) => {
+                let Conditional {
+                    feature,
+                    statements,
+                    else_statements,
+                } = *conditional;
+
+                let active = match features.contains(&feature.0.as_str()) {
+                    true => statements,
+                    false => else_statements,
+                };
+
+                pruned.extend(prune_conditionals(active, features));
+            }
+            mut statement => {
+                prune_statement(&mut statement, features);
+                pruned.push(statement);
+            }
+        }
+    }
+
+    pruned
+}
+
+fn prune_statements(statements: &mut Vec<Statement>, features: &[&str]) {
+    *statements = prune_conditionals(std::mem::take(statements), features);
+}
+
+fn prune_statement(statement: &mut Statement, features: &[&str]) {
+    match statement {
+        Statement::Function(function) => prune_function(function, features),
+        Statement::If(if_statement) => prune_if(if_statement, features),
+        Statement::WhileLoop(while_loop) => {
+            prune_expression(&mut while_loop.condition, features);
+            prune_block(&mut while_loop.block, features);
+        }
+        Statement::InfiniteLoop(infinite_loop) => prune_block(&mut infinite_loop.block, features),
+        Statement::Initialization(initialisation) => {
+            prune_expression(&mut initialisation.value, features)
+        }
+        Statement::Constant(constant) => prune_expression(&mut constant.value, features),
+        Statement::Assignment(assignment) => {
+            match &mut assignment.lvalue {
+                Lvalue::Id(_) => {}
+                Lvalue::Index { expr, index } => {
+                    prune_expression(expr, features);
+                    prune_expression(index, features);
+                }
+                Lvalue::PropertyAccess { expr, .. } | Lvalue::TupleAccess { expr, .. } => {
+                    prune_expression(expr, features)
+                }
+            }
+            prune_expression(&mut assignment.rvalue, features);
+        }
+        Statement::Expression(expression)
+        | Statement::YieldingExpression(expression)
+        | Statement::Return(expression)
+        | Statement::Yield(expression) => prune_expression(expression, features),
+        Statement::StructDeclaration(declaration) => {
+            for field in &mut declaration.fields {
+                if let Some(value) = &mut field.default_value {
+                    prune_expression(value, features);
+                }
+            }
+            for property in &mut declaration.properties {
+                prune_property(property, features);
+            }
+        }
+        Statement::ImplBlock(impl_block) => {
+            for item in &mut impl_block.items {
+                match item {
+                    ImplItem::Constant(constant) => prune_expression(&mut constant.value, features),
+                    ImplItem::Function(function) | ImplItem::Method(function) => {
+                        prune_function(function, features)
+                    }
+                    ImplItem::Property(property) => prune_property(property, features),
+                }
+            }
+        }
+        Statement::Module(module) => prune_statements(&mut module.statements, features),
+        Statement::Conditional(_)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Comment(_)
+        | Statement::Declaration(_)
+        | Statement::EnumDeclaration(_)
+        | Statement::TypeAlias(_)
+        | Statement::ExternBlock(_)
+        | Statement::TraitDeclaration(_)
+        | Statement::Import(_) => {}
+    }
+}
+
+fn prune_function(function: &mut Function, features: &[&str]) {
+    prune_statements(&mut function.statements, features);
+}
+
+fn prune_property(property: &mut Property, features: &[&str]) {
+    if let Some(getter) = &mut property.getter {
+        prune_block(getter, features);
+    }
+    if let Some(setter) = &mut property.setter {
+        prune_block(&mut setter.body, features);
+    }
+}
+
+fn prune_if(if_statement: &mut If, features: &[&str]) {
+    prune_expression(&mut if_statement.condition, features);
+    prune_statements(&mut if_statement.statements, features);
+    if let Some(else_statements) = &mut if_statement.else_statements {
+        prune_statements(else_statements, features);
+    }
+}
+
+fn prune_block(block: &mut Block, features: &[&str]) {
+    prune_statements(&mut block.statements, features);
+    if let Some(tail) = &mut block.tail {
+        prune_expression(tail, features);
+    }
+}
+
+fn prune_expression(expression: &mut Expression, features: &[&str]) {
+    let mut prune = |expression: &mut Expression| prune_expression(expression, features);
+
+    match expression {
+        Expression::Unit
+        | Expression::Id(_)
+        | Expression::Num(_)
+        | Expression::Bool(_)
+        | Expression::String(_)
+        | Expression::Path(_)
+        | Expression::Regex(_) => {}
+        Expression::FormatString(segments) => {
+            for segment in segments {
+                if let Segment::Expression(expression) = segment {
+                    prune(expression);
+                }
+            }
+        }
+        Expression::Function(function) => prune_function(function, features),
+        Expression::Lambda(lambda) => prune(&mut lambda.expression),
+        Expression::If(if_expression) => prune_if(if_expression, features),
+        Expression::Block(block) | Expression::Comptime(block) => prune_block(block, features),
+        Expression::Parens(expression)
+        | Expression::Spread(expression)
+        | Expression::Try(expression)
+        | Expression::Cast {
+            expr: expression, ..
+        } => prune(expression),
+        Expression::Postfix(postfix) => match postfix {
+            Postfix::Call { expr, args, .. } => {
+                prune(expr);
+                args.iter_mut().for_each(prune);
+            }
+            Postfix::Index { expr, index } => {
+                prune(expr);
+                prune(index);
+            }
+            Postfix::PropertyAccess { expr, .. } | Postfix::TupleAccess { expr, .. } => prune(expr),
+            Postfix::MethodCall(method_call) => {
+                prune(&mut method_call.receiver);
+                method_call.args.iter_mut().for_each(prune);
+            }
+        },
+        Expression::Prefix(
+            Prefix::Negation { expr } | Prefix::Minus { expr } | Prefix::BitNot { expr },
+        ) => prune(expr),
+        Expression::Binary(binary) => {
+            let (lhs, rhs) = binary.operands_mut();
+            prune(lhs);
+            prune(rhs);
+        }
+        Expression::LazyAnd { lhs, rhs }
+        | Expression::LazyOr { lhs, rhs }
+        | Expression::Pipe {
+            value: lhs,
+            function: rhs,
+        }
+        | Expression::Range {
+            start: lhs,
+            end: rhs,
+            ..
+        } => {
+            prune(lhs);
+            prune(rhs);
+        }
+        Expression::Ternary {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            prune(condition);
+            prune(then_branch);
+            prune(else_branch);
+        }
+        Expression::Slice {
+            expr, start, end, ..
+        } => {
+            prune(expr);
+            prune(start);
+            prune(end);
+        }
+        Expression::Array(Array::Literal { values }) | Expression::Tuple(values) => {
+            values.iter_mut().for_each(prune)
+        }
+        Expression::Array(Array::Default { initial_value, .. }) => prune(initial_value),
+        Expression::StructInitialisation(initialisation) => {
+            for field in &mut initialisation.fields {
+                prune(&mut field.value);
+            }
+        }
+        Expression::Match(match_expression) => {
+            prune(&mut match_expression.value);
+            for arm in &mut match_expression.arms {
+                prune(&mut arm.body);
+            }
+        }
+        Expression::Comprehension(comprehension) => {
+            prune(&mut comprehension.expression);
+            prune(&mut comprehension.iterable);
+            if let Some(filter) = &mut comprehension.filter {
+                prune(filter);
+            }
+        }
+        Expression::Asm(asm) => {
+            asm.outputs.iter_mut().for_each(&mut prune);
+            asm.inputs.iter_mut().for_each(prune);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::ast::{Expression, Num},
+    };
+
+    use super::*;
+
+    fn parse_statements(input: &str) -> Vec<Statement> {
+        let mut tokens: Tokens<Token> = Lexer::new(input).lex().expect("should work").into();
+        let mut statements = vec![];
+
+        while tokens.peek().is_some() {
+            let Ok(AstNode::Statement(statement)) = Statement::parse(&mut tokens) else {
+                panic!("expected statement");
+            };
+            statements.push(statement);
+        }
+
+        statements
+    }
+
+    #[test]
+    fn test_parse_conditional() {
+        let mut tokens = Lexer::new("#if DEBUG { 1; } #else { 2; }")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(Conditional {
+                feature: Id("DEBUG".into()),
+                statements: vec![Statement::Expression(Expression::Num(Num::Integer(1)))],
+                else_statements: vec![Statement::Expression(Expression::Num(Num::Integer(2)))]
+            }
+            .into()),
+            Conditional::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_conditional_without_else() {
+        let mut tokens = Lexer::new("#if DEBUG {}")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(Conditional {
+                feature: Id("DEBUG".into()),
+                statements: vec![],
+                else_statements: vec![]
+            }
+            .into()),
+            Conditional::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals() {
+        let statements = parse_statements(
+            "#if DEBUG { 1; } #else { 2; } fn foo(): void { #if RELEASE { 3; } #else { 4; } }",
+        );
+
+        assert_eq!(
+            parse_statements("1; fn foo(): void { 4; }"),
+            prune_conditionals(statements.clone(), &["DEBUG"])
+        );
+        assert_eq!(
+            parse_statements("2; fn foo(): void { 3; }"),
+            prune_conditionals(statements, &["RELEASE"])
+        );
+    }
+
+    fn assert_pruned(input: &str, expected: &str) {
+        assert_eq!(
+            parse_statements(expected),
+            prune_conditionals(parse_statements(input), &["DEBUG"])
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_impl_block() {
+        assert_pruned(
+            "impl Foo { fn bar(&self): void { #if DEBUG { 1; } #else { 2; } } }",
+            "impl Foo { fn bar(&self): void { 1; } }",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_module() {
+        assert_pruned(
+            "mod foo { #if DEBUG { fn bar(): void {} } }",
+            "mod foo { fn bar(): void {} }",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_property() {
+        assert_pruned(
+            "struct Foo { prop bar: i32 { get { #if DEBUG { 1; } 2 } } };",
+            "struct Foo { prop bar: i32 { get { 1; 2 } } };",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_lambda() {
+        assert_pruned(
+            "let f = \\(x) => { #if DEBUG { log(x); } x };",
+            "let f = \\(x) => { log(x); x };",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_block_expression() {
+        assert_pruned(
+            "let x = { #if RELEASE { 1; } #else { 2; } 3 };",
+            "let x = { 2; 3 };",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_comptime_expression() {
+        assert_pruned(
+            "let x = comptime { #if DEBUG { 1; } 2 };",
+            "let x = comptime { 1; 2 };",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_match_arm() {
+        assert_pruned(
+            "let x = match y { 1 => { #if DEBUG { log(y); } 2 }, _ => 3 };",
+            "let x = match y { 1 => { log(y); 2 }, _ => 3 };",
+        );
+    }
+
+    #[test]
+    fn test_prune_conditionals_in_call_argument() {
+        assert_pruned(
+            "foo(fn (): void { #if DEBUG { 1; } });",
+            "foo(fn (): void { 1; });",
+        );
+    }
+}
@@ -1,14 +1,20 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{
-        ast::{AstNode, Expression, Id, TypeName},
+        ast::{parse_doc, AstNode, Expression, Id, TypeName, Visibility},
         combinators::Comb,
         FromTokens, ParseError,
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Constant {
+    /// The doc comments (`/// ...`) in front of the declaration.
+    pub doc: Option<String>,
+    pub visibility: Visibility,
     pub id: Id,
     pub type_name: TypeName,
     pub value: Expression,
@@ -19,27 +25,33 @@ impl FromTokens<Token> for Constant {
     where
         Self: Sized,
     {
+        let doc = parse_doc(tokens);
+        let visibility = Visibility::parse(tokens);
+
         Comb::CONST_KEYWORD.parse(tokens)?;
 
-        let matcher = Comb::ID >> Comb::COLON >> Comb::TYPE_NAME >> Comb::ASSIGN >> Comb::EXPR;
+        let matcher =
+            lazy_comb!(Comb::ID >> Comb::COLON >> Comb::TYPE_NAME >> Comb::ASSIGN >> Comb::EXPR);
 
-        let result = matcher.parse(tokens)?;
+        let mut result = matcher.parse(tokens)?.into_iter();
 
-        let Some(AstNode::Id(id)) = result.first() else {
+        let Some(AstNode::Id(id)) = result.next() else {
             unreachable!()
         };
 
-        let Some(AstNode::TypeName(type_name)) = result.get(1).cloned() else {
+        let Some(AstNode::TypeName(type_name)) = result.next() else {
             unreachable!()
         };
 
-        let Some(AstNode::Expression(value)) = result.get(2).cloned() else {
+        let Some(AstNode::Expression(value)) = result.next() else {
             unreachable!()
         };
 
         Ok(Constant {
-            id: id.clone(),
-            value: value.clone(),
+            doc,
+            visibility,
+            id,
+            value,
             type_name,
         }
         .into())
@@ -48,7 +60,7 @@ impl FromTokens<Token> for Constant {
 
 impl From<Constant> for AstNode {
     fn from(value: Constant) -> Self {
-        AstNode::Constant(value)
+        AstNode::Constant(Box::new(value))
     }
 }
 
@@ -69,6 +81,8 @@ mod tests {
 
         assert_eq!(
             Ok(Constant {
+                doc: None,
+                visibility: Visibility::Private,
                 id: Id("foo".into()),
                 type_name: TypeName::Literal("i32".into()),
                 value: Expression::Num(Num::Integer(42))
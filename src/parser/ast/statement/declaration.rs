@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    lexer::{Token, Tokens},
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
     parser::{
         ast::{AstNode, Id, TypeName},
         combinators::Comb,
@@ -7,15 +10,22 @@ use crate::{
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Declaration {
     pub name: Id,
     pub type_name: TypeName,
 }
 
+impl Declaration {
+    /// `declare name: type`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::DECLARE_KEYWORD >> Comb::ID >> Comb::COLON >> Comb::TYPE_NAME)
+    }
+}
+
 impl FromTokens<Token> for Declaration {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::DECLARE_KEYWORD >> Comb::ID >> Comb::COLON >> Comb::TYPE_NAME;
+        let matcher = Self::grammar();
 
         let result = matcher.parse(tokens)?;
 
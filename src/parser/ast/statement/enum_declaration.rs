@@ -0,0 +1,329 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{parse_doc, AstNode, Id, TypeName, Visibility},
+        combinators::Comb,
+        ensure_unique, FromTokens, ParseError,
+    },
+};
+
+/// An enum, whose variants might carry a payload, e.g.:
+///
+/// ```text
+/// enum Shape {
+///     Empty,
+///     Circle(f64),
+///     Rect { w: f64, h: f64 },
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumDeclaration {
+    /// The doc comments (`/// ...`) in front of the declaration.
+    pub doc: Option<String>,
+    pub visibility: Visibility,
+    pub id: Id,
+    /// Names of the generic type parameters, e.g., `T` in `enum Option<T> { ... }`.
+    pub type_params: Vec<Id>,
+    pub variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumVariant {
+    /// The doc comments (`/// ...`) in front of the variant.
+    pub doc: Option<String>,
+    pub name: Id,
+    pub payload: VariantPayload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VariantPayload {
+    /// A variant without any data, e.g., `Empty`.
+    Unit,
+    /// `Circle(f64)`, whose data is accessed by position.
+    Tuple(Vec<TypeName>),
+    /// `Rect { w: f64, h: f64 }`, whose data is accessed by name.
+    Struct(Vec<VariantField>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantField {
+    pub name: Id,
+    pub type_name: TypeName,
+}
+
+impl FromTokens<Token> for EnumDeclaration {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let doc = parse_doc(tokens);
+        let visibility = Visibility::parse(tokens);
+
+        let matcher = lazy_comb!(
+            Comb::ENUM_KEYWORD
+                >> Comb::ID
+                >> !(Comb::LESS_THAN >> (Comb::ID % Comb::COMMA) >> Comb::GREATER_THAN)
+                >> Comb::LBRACE
+        );
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(id)) = result.next() else {
+            unreachable!()
+        };
+
+        let type_params = result
+            .map(|param| {
+                let AstNode::Id(param) = param else {
+                    unreachable!()
+                };
+                param
+            })
+            .collect::<Vec<_>>();
+
+        let mut variants = vec![];
+        let mut names = vec![];
+
+        // variants are separated by commas (with an optional trailing one)
+        while !matches!(tokens.peek(), Some(Token::RBrace { .. }) | None) {
+            let mut offset = 0;
+            while let Some(Token::DocComment { .. }) = tokens.peek_at(offset) {
+                offset += 1;
+            }
+            let position = tokens
+                .peek_at(offset)
+                .map(Token::position)
+                .unwrap_or_default();
+            let variant = EnumVariant::parse(tokens)?;
+
+            names.push((variant.name.0.clone(), position));
+            variants.push(variant);
+
+            if !matches!(tokens.peek(), Some(Token::Comma { .. })) {
+                break;
+            }
+            tokens.next();
+        }
+
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique("variant", names)?;
+
+        Ok(EnumDeclaration {
+            doc,
+            visibility,
+            id,
+            type_params,
+            variants,
+        }
+        .into())
+    }
+}
+
+impl EnumVariant {
+    /// Parse `Name`, `Name(types...)` or `Name { field: type, ... }`.
+    fn parse(tokens: &mut Tokens<Token>) -> Result<EnumVariant, ParseError> {
+        let doc = parse_doc(tokens);
+        let Some(AstNode::Id(name)) = Comb::ID.parse(tokens)?.into_iter().next() else {
+            unreachable!()
+        };
+
+        let payload = match tokens.peek() {
+            Some(Token::LParen { .. }) => {
+                let matcher =
+                    lazy_comb!(Comb::LPAREN >> (Comb::TYPE_NAME % Comb::COMMA) >> Comb::RPAREN);
+                let types = matcher
+                    .parse(tokens)?
+                    .into_iter()
+                    .map(|type_name| {
+                        let AstNode::TypeName(type_name) = type_name else {
+                            unreachable!()
+                        };
+                        type_name
+                    })
+                    .collect();
+
+                VariantPayload::Tuple(types)
+            }
+            Some(Token::LBrace { .. }) => VariantPayload::Struct(VariantField::parse_all(tokens)?),
+            _ => VariantPayload::Unit,
+        };
+
+        Ok(EnumVariant { doc, name, payload })
+    }
+}
+
+impl VariantField {
+    /// Parse `{ name: type, ... }` (with an optional trailing comma).
+    fn parse_all(tokens: &mut Tokens<Token>) -> Result<Vec<VariantField>, ParseError> {
+        Comb::LBRACE.parse(tokens)?;
+
+        let mut fields = vec![];
+        let mut names = vec![];
+
+        while !matches!(tokens.peek(), Some(Token::RBrace { .. }) | None) {
+            let position = tokens.peek().map(Token::position).unwrap_or_default();
+
+            let matcher = lazy_comb!(Comb::ID >> Comb::COLON >> Comb::TYPE_NAME);
+            let mut result = matcher.parse(tokens)?.into_iter();
+
+            let (Some(AstNode::Id(name)), Some(AstNode::TypeName(type_name))) =
+                (result.next(), result.next())
+            else {
+                unreachable!()
+            };
+
+            names.push((name.0.clone(), position));
+            fields.push(VariantField { name, type_name });
+
+            if !matches!(tokens.peek(), Some(Token::Comma { .. })) {
+                break;
+            }
+            tokens.next();
+        }
+
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique("field", names)?;
+
+        Ok(fields)
+    }
+}
+
+impl From<EnumDeclaration> for AstNode {
+    fn from(value: EnumDeclaration) -> Self {
+        Self::EnumDeclaration(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::{Lexer, Position},
+        parser::{
+            ast::{AstNode, Id, TypeName, Visibility},
+            FromTokens,
+        },
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<AstNode, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+        EnumDeclaration::parse(&mut tokens)
+    }
+
+    #[test]
+    fn parse_enum_with_payloads() {
+        assert_eq!(
+            Ok(EnumDeclaration {
+                doc: None,
+                visibility: Visibility::Private,
+                id: Id("Shape".into()),
+                type_params: vec![],
+                variants: vec![
+                    EnumVariant {
+                        doc: None,
+                        name: Id("Empty".into()),
+                        payload: VariantPayload::Unit
+                    },
+                    EnumVariant {
+                        doc: None,
+                        name: Id("Circle".into()),
+                        payload: VariantPayload::Tuple(vec![TypeName::Literal("f64".into())])
+                    },
+                    EnumVariant {
+                        doc: None,
+                        name: Id("Rect".into()),
+                        payload: VariantPayload::Struct(vec![
+                            VariantField {
+                                name: Id("w".into()),
+                                type_name: TypeName::Literal("f64".into())
+                            },
+                            VariantField {
+                                name: Id("h".into()),
+                                type_name: TypeName::Literal("f64".into())
+                            }
+                        ])
+                    }
+                ]
+            }
+            .into()),
+            parse("enum Shape { Empty, Circle(f64), Rect { w: f64, h: f64 } }")
+        );
+    }
+
+    #[test]
+    fn parse_generic_enum() {
+        let result = parse("/// Maybe a value.\npub enum Option<T> { Some(T), None, }");
+
+        let Ok(AstNode::EnumDeclaration(declaration)) = result else {
+            panic!("expected enum, got {result:?}");
+        };
+        assert_eq!(Some("Maybe a value."), declaration.doc.as_deref());
+        assert_eq!(Visibility::Public, declaration.visibility);
+        assert_eq!(vec![Id("T".into())], declaration.type_params);
+        assert_eq!(2, declaration.variants.len());
+    }
+
+    #[test]
+    fn parse_duplicate_variant_fails() {
+        let Err(error) = parse("enum Color { Red, Green, Red }") else {
+            panic!("expected error");
+        };
+
+        assert_eq!("duplicate variant 'Red'", error.message);
+        assert_eq!(Some(Position::new(25, 3)), error.position);
+        assert_eq!(Position::new(13, 3), error.notes[0].position);
+    }
+
+    #[test]
+    fn parse_duplicate_field_fails() {
+        assert!(parse("enum Shape { Rect { w: f64, w: f64 } }").is_err());
+    }
+
+    #[test]
+    fn parse_missing_comma_fails() {
+        assert!(parse("enum Color { Red Green }").is_err());
+    }
+
+    #[test]
+    fn parse_recursive_enum() {
+        let result = parse("enum Tree { Leaf(i32), Node { left: Tree, right: Tree? } }");
+
+        let Ok(AstNode::EnumDeclaration(declaration)) = result else {
+            panic!("expected enum, got {result:?}");
+        };
+        assert_eq!(
+            VariantPayload::Struct(vec![
+                VariantField {
+                    name: Id("left".into()),
+                    type_name: TypeName::Literal("Tree".into())
+                },
+                VariantField {
+                    name: Id("right".into()),
+                    type_name: TypeName::Optional(Box::new(TypeName::Literal("Tree".into())))
+                }
+            ]),
+            declaration.variants[1].payload
+        );
+    }
+
+    #[test]
+    fn parse_documented_variants() {
+        let result = parse(
+            "enum Color {\n    /// Like blood.\n    Red,\n    /// Like grass.\n    Green,\n}",
+        );
+
+        let Ok(AstNode::EnumDeclaration(declaration)) = result else {
+            panic!("expected enum, got {result:?}");
+        };
+        assert_eq!(
+            vec![Some("Like blood.".into()), Some("Like grass.".into())],
+            declaration
+                .variants
+                .iter()
+                .map(|variant| variant.doc.clone())
+                .collect::<Vec<Option<String>>>()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{AstNode, Id, Parameter, TypeName},
+        combinators::Comb,
+        ensure_unique, FromTokens, ParseError,
+    },
+};
+
+/// Functions which are implemented outside of the program (e.g., in C). They are either declared
+/// one at a time (`extern fn puts(s: str): i32;`) or grouped within a block:
+///
+/// ```text
+/// extern {
+///     fn puts(s: str): i32;
+///     fn exit(code: i32): void;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternBlock {
+    pub functions: Vec<ExternFunction>,
+}
+
+/// Signature of a foreign function (without a body).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternFunction {
+    pub name: Id,
+    pub parameters: Vec<Parameter>,
+    pub return_type: TypeName,
+}
+
+impl FromTokens<Token> for ExternBlock {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        Comb::EXTERN_KEYWORD.parse(tokens)?;
+
+        if let Some(Token::FnKeyword { .. }) = tokens.peek() {
+            let function = ExternFunction::parse(tokens)?;
+
+            return Ok(ExternBlock {
+                functions: vec![function],
+            }
+            .into());
+        }
+
+        Comb::LBRACE.parse(tokens)?;
+
+        let mut functions = vec![];
+        let mut names = vec![];
+
+        loop {
+            match (tokens.peek(), tokens.peek_at(1)) {
+                (Some(Token::RBrace { .. }) | None, _) => break,
+                (Some(Token::FnKeyword { .. }), Some(token)) => {
+                    let position = token.position();
+                    let function = ExternFunction::parse(tokens)?;
+
+                    names.push((function.name.0.clone(), position));
+                    functions.push(function);
+                }
+                (Some(token), _) => {
+                    return Err(ParseError {
+                        message: format!(
+                            "Unexpected {token:?} in extern block, expected a function signature"
+                        ),
+                        position: Some(token.position()),
+                        notes: vec![],
+                    })
+                }
+            }
+        }
+
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique("function", names)?;
+
+        Ok(ExternBlock { functions }.into())
+    }
+}
+
+impl ExternFunction {
+    /// Parse `fn name(parameters): return_type;`.
+    fn parse(tokens: &mut Tokens<Token>) -> Result<ExternFunction, ParseError> {
+        let matcher = lazy_comb!(Comb::FN_KEYWORD >> Comb::ID);
+        let Some(AstNode::Id(name)) = matcher.parse(tokens)?.into_iter().next() else {
+            unreachable!()
+        };
+
+        let parameters = Parameter::parse_list(tokens, false)?;
+
+        let matcher = lazy_comb!(Comb::COLON >> Comb::TYPE_NAME >> Comb::SEMI);
+        let Some(AstNode::TypeName(return_type)) = matcher.parse(tokens)?.into_iter().next() else {
+            unreachable!()
+        };
+
+        Ok(ExternFunction {
+            name,
+            parameters,
+            return_type,
+        })
+    }
+}
+
+impl From<ExternBlock> for AstNode {
+    fn from(value: ExternBlock) -> Self {
+        AstNode::ExternBlock(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{Lexer, Position};
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<AstNode, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+        ExternBlock::parse(&mut tokens)
+    }
+
+    #[test]
+    fn test_parse_single_function() {
+        assert_eq!(
+            Ok(ExternBlock {
+                functions: vec![ExternFunction {
+                    name: Id("puts".into()),
+                    parameters: vec![Parameter {
+                        name: Id("s".into()),
+                        type_name: Some(TypeName::Literal("str".into()))
+                    }],
+                    return_type: TypeName::Literal("i32".into())
+                }]
+            }
+            .into()),
+            parse("extern fn puts(s: str): i32;")
+        );
+    }
+
+    #[test]
+    fn test_parse_block() {
+        let result = parse("extern { fn puts(s: str): i32; fn exit(code: i32): void; }");
+
+        let Ok(AstNode::ExternBlock(block)) = result else {
+            panic!("expected extern block, got {result:?}");
+        };
+        assert_eq!(
+            vec![Id("puts".into()), Id("exit".into())],
+            block
+                .functions
+                .iter()
+                .map(|function| function.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_function_with_body_fails() {
+        assert!(parse("extern fn puts(s: str): i32 { 0 }").is_err());
+    }
+
+    #[test]
+    fn test_parse_duplicate_function_fails() {
+        let Err(error) = parse("extern { fn exit(): void; fn exit(): void; }") else {
+            panic!("expected error");
+        };
+
+        assert_eq!("duplicate function 'exit'", error.message);
+        assert_eq!(Some(Position::new(29, 4)), error.position);
+        assert_eq!(Position::new(12, 4), error.notes[0].position);
+    }
+
+    #[test]
+    fn test_parse_other_item_in_block_fails() {
+        assert!(parse("extern { let x = 1; }").is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Symbol, Token, Tokens},
+    parser::{
+        ast::{AstNode, Attribute, Function, TypeName},
+        combinators::Comb,
+        ensure_unique, FromTokens, ParseError,
+    },
+};
+
+use super::{Constant, Property};
+
+/// Items implemented for a type, e.g.:
+///
+/// ```text
+/// impl Circle {
+///     const PI: f64 = 3.1415;
+///     fn unit(): Circle { ... }
+///     fn area(&self): f64 { ... }
+///     prop diameter: f64 { get { ... } }
+/// }
+/// ```
+///
+/// An implementation of a trait names it before the type, e.g., `impl Shape for Circle { ... }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplBlock {
+    pub trait_name: Option<TypeName>,
+    pub type_name: TypeName,
+    pub items: Vec<ImplItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImplItem {
+    /// An associated constant, e.g., `const PI: f64 = 3.1415;`.
+    Constant(Constant),
+    /// An associated function, i.e., a function without a `self` receiver.
+    Function(Function),
+    /// A function with a `self` receiver as its first parameter.
+    Method(Function),
+    /// A property with a getter and/or a setter, e.g., `prop diameter: f64 { get { ... } }`.
+    Property(Property),
+}
+
+impl FromTokens<Token> for ImplBlock {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = lazy_comb!(
+            Comb::IMPL_KEYWORD
+                >> Comb::TYPE_NAME
+                >> !(Comb::FOR_KEYWORD >> Comb::TYPE_NAME)
+                >> Comb::LBRACE
+        );
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::TypeName(first)) = result.next() else {
+            unreachable!()
+        };
+
+        // with a trait, the implementing type only follows after `for`
+        let (trait_name, type_name) = match result.next() {
+            Some(AstNode::TypeName(type_name)) => (Some(first), type_name),
+            _ => (None, first),
+        };
+
+        let mut items = vec![];
+        let mut names = vec![];
+
+        loop {
+            // doc comments, attributes and a leading `pub` are parsed by the item they belong to
+            let (mut offset, _) = Attribute::skip_with_doc(tokens, 0);
+            if let Some(Token::PubKeyword { .. }) = tokens.peek_at(offset) {
+                offset += 1;
+            }
+
+            let item = match tokens.peek_at(offset) {
+                Some(Token::RBrace { .. }) | None => break,
+                // `prop` is only a keyword when followed by the name of the property
+                Some(Token::Id { value, .. })
+                    if value.as_str() == "prop"
+                        && matches!(tokens.peek_at(offset + 1), Some(Token::Id { .. })) =>
+                {
+                    names.extend(tokens.peek_at(offset + 1).map(Token::position));
+
+                    let Some(AstNode::Property(property)) =
+                        Comb::PROPERTY.parse(tokens)?.into_iter().next()
+                    else {
+                        unreachable!()
+                    };
+                    ImplItem::Property(*property)
+                }
+                Some(Token::Const { .. }) => {
+                    names.extend(tokens.peek_at(offset + 1).map(Token::position));
+
+                    let matcher = lazy_comb!(Comb::CONSTANT >> Comb::SEMI);
+                    let Some(AstNode::Constant(constant)) =
+                        matcher.parse(tokens)?.into_iter().next()
+                    else {
+                        unreachable!()
+                    };
+                    ImplItem::Constant(*constant)
+                }
+                Some(Token::FnKeyword { position }) => {
+                    let position = *position;
+                    names.extend(tokens.peek_at(offset + 1).map(Token::position));
+
+                    let AstNode::Function(function) = Function::parse_method(tokens)? else {
+                        unreachable!()
+                    };
+
+                    if function.id.is_none() {
+                        return Err(ParseError {
+                            message: "functions within an impl block need a name".into(),
+                            position: Some(position),
+                            notes: vec![],
+                        });
+                    }
+
+                    match function.parameters.first() {
+                        Some(parameter) if parameter.is_receiver() => ImplItem::Method(*function),
+                        _ => ImplItem::Function(*function),
+                    }
+                }
+                Some(token) => {
+                    return Err(ParseError {
+                        message: format!(
+                            "Unexpected {token:?} in impl block, expected a constant, a function or a property"
+                        ),
+                        position: Some(token.position()),
+                        notes: vec![],
+                    })
+                }
+            };
+            items.push(item);
+        }
+
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique("item", items.iter().map(ImplItem::name).zip(names))?;
+
+        Ok(ImplBlock {
+            trait_name,
+            type_name,
+            items,
+        }
+        .into())
+    }
+}
+
+impl ImplItem {
+    /// Name of this item (constants, functions and properties share the same namespace).
+    pub fn name(&self) -> Symbol {
+        match self {
+            ImplItem::Constant(constant) => constant.id.0.clone(),
+            ImplItem::Property(property) => property.name.0.clone(),
+            ImplItem::Function(function) | ImplItem::Method(function) => function
+                .id
+                .as_ref()
+                .expect("functions in impl blocks are named")
+                .0
+                .clone(),
+        }
+    }
+}
+
+impl From<ImplBlock> for AstNode {
+    fn from(value: ImplBlock) -> Self {
+        AstNode::ImplBlock(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::ast::{Expression, Id, Num, Visibility},
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<AstNode, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+        ImplBlock::parse(&mut tokens)
+    }
+
+    #[test]
+    fn test_parse_empty_impl_block() {
+        assert_eq!(
+            Ok(ImplBlock {
+                trait_name: None,
+                type_name: TypeName::Literal("Circle".into()),
+                items: vec![]
+            }
+            .into()),
+            parse("impl Circle {}")
+        );
+    }
+
+    #[test]
+    fn test_parse_associated_items() {
+        let result = parse(
+            "impl Circle { const SCALE: f64 = 2.5; fn unit(): Circle { 42 } fn area(&self): f64 { 42 } }",
+        );
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        let [ImplItem::Constant(constant), ImplItem::Function(function), ImplItem::Method(method)] =
+            impl_block.items.as_slice()
+        else {
+            panic!("unexpected items {:?}", impl_block.items);
+        };
+
+        assert_eq!(
+            &Constant {
+                doc: None,
+                visibility: Visibility::Private,
+                id: Id("SCALE".into()),
+                type_name: TypeName::Literal("f64".into()),
+                value: Expression::Num(Num::FloatingPoint(2.5))
+            },
+            constant
+        );
+        assert_eq!(Some(Id("unit".into())), function.id);
+        assert_eq!(Some(Id("area".into())), method.id);
+    }
+
+    #[test]
+    fn test_parse_trait_impl() {
+        let result = parse("impl Printable for Point { fn print(self): void { } }");
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        assert_eq!(
+            Some(TypeName::Literal("Printable".into())),
+            impl_block.trait_name
+        );
+        assert_eq!(TypeName::Literal("Point".into()), impl_block.type_name);
+        assert!(matches!(
+            impl_block.items.as_slice(),
+            [ImplItem::Method(Function { id: Some(id), .. })] if id == &Id("print".into())
+        ));
+    }
+
+    #[test]
+    fn test_parse_generic_trait_impl() {
+        let result = parse("impl Into<Vec<i32>> for Point {}");
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        assert_eq!(
+            Some(TypeName::Generic {
+                name: "Into".into(),
+                args: vec![TypeName::Generic {
+                    name: "Vec".into(),
+                    args: vec![TypeName::Literal("i32".into())]
+                }]
+            }),
+            impl_block.trait_name
+        );
+        assert_eq!(TypeName::Literal("Point".into()), impl_block.type_name);
+    }
+
+    #[test]
+    fn test_parse_documented_method() {
+        let result = parse("impl Circle {\n/// The area.\nfn area(&self): f64 { 42 } }");
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        assert!(matches!(
+            impl_block.items.as_slice(),
+            [ImplItem::Method(Function { doc: Some(doc), .. })] if doc == "The area."
+        ));
+    }
+
+    #[test]
+    fn test_parse_attributed_and_documented_method() {
+        let result = parse("impl Circle {\n#[inline]\n/// The area.\nfn area(&self): f64 { 42 } }");
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        assert!(matches!(
+            impl_block.items.as_slice(),
+            [ImplItem::Method(Function { doc: Some(doc), attributes, .. })]
+                if doc == "The area." && attributes.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_public_items() {
+        let result = parse(
+            "impl Circle { pub const SCALE: f64 = 2.5; /// The area.\npub fn area(&self): f64 { 42 } }",
+        );
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        assert!(matches!(
+            impl_block.items.as_slice(),
+            [
+                ImplItem::Constant(Constant {
+                    visibility: Visibility::Public,
+                    ..
+                }),
+                ImplItem::Method(Function {
+                    visibility: Visibility::Public,
+                    doc: Some(_),
+                    ..
+                })
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_parse_property() {
+        let result = parse(
+            "impl Circle { /// The diameter.\nprop diameter: f64 { get { 2 * self.radius } } fn prop(): i32 { 1 } }",
+        );
+
+        let Ok(AstNode::ImplBlock(impl_block)) = result else {
+            panic!("expected impl block, got {result:?}");
+        };
+
+        let [ImplItem::Property(property), ImplItem::Function(function)] =
+            impl_block.items.as_slice()
+        else {
+            panic!("unexpected items {:?}", impl_block.items);
+        };
+        assert_eq!(Id("diameter".into()), property.name);
+        assert_eq!(Some("The diameter."), property.doc.as_deref());
+        assert!(property.getter.is_some() && property.setter.is_none());
+        assert_eq!(Some(Id("prop".into())), function.id);
+    }
+
+    #[test]
+    fn test_parse_duplicate_item_fails() {
+        let result = parse("impl Circle { const unit: i32 = 1; fn unit(): i32 { 1 } }");
+
+        assert!(matches!(
+            result,
+            Err(ParseError { message, .. }) if message == "duplicate item 'unit'"
+        ));
+    }
+
+    #[test]
+    fn test_parse_anonymous_function_fails() {
+        assert!(parse("impl Circle { fn (): i32 { 1 } }").is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
+    parser::{
+        ast::{AstNode, Id},
+        combinators::Comb,
+        FromTokens, ParseError,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Import {
+    pub path: Vec<Id>,
+    /// The items imported from the module at `path`. Without them, the last segment of the path
+    /// is the imported item itself.
+    pub items: Option<ImportItems>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportItems {
+    /// `use std::io::{read, write}`
+    Group(Vec<Id>),
+    /// `use std::io::*`
+    Glob,
+}
+
+impl Import {
+    /// `use a::b::c`, `use a::b::{c, d}` or `use a::b::*`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(
+            Comb::USE_KEYWORD
+                >> (Comb::ID % Comb::DOUBLE_COLON)
+                >> !(Comb::DOUBLE_COLON >> (Comb::TIMES | Import::group_grammar()))
+        )
+    }
+
+    fn group_grammar() -> Comb<'static, Token, Terminal, AstNode> {
+        Comb::LBRACE >> (Comb::ID % Comb::COMMA) >> !Comb::COMMA >> Comb::RBRACE
+    }
+
+    fn parse_items(tokens: &mut Tokens<Token>) -> Result<Option<ImportItems>, ParseError> {
+        let Some(Token::DoubleColon { .. }) = tokens.peek() else {
+            return Ok(None);
+        };
+
+        match tokens.peek_at(1) {
+            Some(Token::Times { .. }) => {
+                tokens.next();
+                tokens.next();
+
+                Ok(Some(ImportItems::Glob))
+            }
+            Some(Token::LBrace { position }) => {
+                let position = *position;
+                tokens.next();
+
+                let matcher = lazy_comb!(Import::group_grammar());
+
+                let mut items = vec![];
+
+                for item in matcher.parse(tokens)? {
+                    let AstNode::Id(item) = item else {
+                        unreachable!()
+                    };
+                    items.push(item);
+                }
+
+                if items.is_empty() {
+                    return Err(ParseError {
+                        message: "expected at least one item to import".into(),
+                        position: Some(position),
+                        notes: vec![],
+                    });
+                }
+
+                Ok(Some(ImportItems::Group(items)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl FromTokens<Token> for Import {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let position = tokens.peek().map(|token| token.position());
+
+        // the path is parsed on its own, since the (optional) items are identifiers as well
+        let matcher = lazy_comb!(Comb::USE_KEYWORD >> (Comb::ID % Comb::DOUBLE_COLON));
+        let result = matcher.parse(tokens)?;
+
+        let mut path = vec![];
+
+        for segment in result {
+            let AstNode::Id(segment) = segment else {
+                unreachable!()
+            };
+            path.push(segment);
+        }
+
+        if path.is_empty() {
+            return Err(ParseError {
+                message: "expected a path after 'use'".into(),
+                position,
+                notes: vec![],
+            });
+        }
+
+        let items = Self::parse_items(tokens)?;
+
+        Ok(Import { path, items }.into())
+    }
+}
+
+impl Display for Import {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, segment) in self.path.iter().enumerate() {
+            if index > 0 {
+                f.write_str("::")?;
+            }
+            f.write_str(segment.0.as_str())?;
+        }
+
+        match &self.items {
+            None => Ok(()),
+            Some(ImportItems::Glob) => f.write_str("::*"),
+            Some(ImportItems::Group(items)) => {
+                f.write_str("::{")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(item.0.as_str())?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl From<Import> for AstNode {
+    fn from(value: Import) -> Self {
+        AstNode::Import(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::{
+            ast::{AstNode, Id},
+            FromTokens,
+        },
+    };
+
+    use super::{Import, ImportItems};
+
+    #[test]
+    fn test_parse_simple_import() {
+        let mut tokens = Lexer::new("use geometry")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Import::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Import {
+                path: vec![Id("geometry".into())],
+                items: None
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_import() {
+        let mut tokens = Lexer::new("use geometry::shapes::Point")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Import::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Import {
+                path: vec![
+                    Id("geometry".into()),
+                    Id("shapes".into()),
+                    Id("Point".into())
+                ],
+                items: None
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_grouped_import() {
+        let mut tokens = Lexer::new("use std::io::{read, write,}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Import::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Import {
+                path: vec![Id("std".into()), Id("io".into())],
+                items: Some(ImportItems::Group(vec![
+                    Id("read".into()),
+                    Id("write".into())
+                ]))
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_glob_import() {
+        let mut tokens = Lexer::new("use std::io::*")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Import::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Import {
+                path: vec![Id("std".into()), Id("io".into())],
+                items: Some(ImportItems::Glob)
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_group_fails() {
+        let mut tokens = Lexer::new("use std::io::{}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Import::parse(&mut tokens);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_display_import() {
+        let mut tokens = Lexer::new("use std::io::{read, write}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let Ok(AstNode::Import(import)) = Import::parse(&mut tokens) else {
+            panic!("expected import");
+        };
+
+        assert_eq!("std::io::{read, write}", import.to_string());
+    }
+
+    #[test]
+    fn test_parse_import_without_path() {
+        let mut tokens = Lexer::new("use").lex().expect("something is wrong").into();
+
+        let result = Import::parse(&mut tokens);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
+    parser::{
+        ast::{AstNode, Block},
+        combinators::Comb,
+        FromTokens, ParseError,
+    },
+};
+
+/// A loop without a condition, which can only be left by `break` (or `return`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InfiniteLoop {
+    pub block: Block,
+}
+
+impl InfiniteLoop {
+    /// `loop { ... }`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::LOOP_KEYWORD >> Comb::BLOCK)
+    }
+}
+
+impl FromTokens<Token> for InfiniteLoop {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = Self::grammar();
+
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Block(block)) = result.next() else {
+            unreachable!()
+        };
+
+        Ok(InfiniteLoop { block }.into())
+    }
+}
+
+impl From<InfiniteLoop> for AstNode {
+    fn from(value: InfiniteLoop) -> Self {
+        AstNode::InfiniteLoop(Box::new(value))
+    }
+}
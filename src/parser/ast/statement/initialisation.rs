@@ -1,15 +1,20 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{
-        ast::{AstNode, Expression, Id, TypeName},
+        ast::{AstNode, Expression, Pattern, TypeName},
         combinators::Comb,
-        FromTokens, ParseError,
+        FromTokens, Note, ParseError,
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Initialisation {
-    pub id: Id,
+    /// The binding, either a single identifier or a (nested) tuple or struct pattern like `(q, r)`
+    /// or `Point { x, y }`.
+    pub pattern: Pattern,
     pub mutable: bool,
     pub type_name: Option<TypeName>,
     pub value: Expression,
@@ -24,41 +29,59 @@ impl FromTokens<Token> for Initialisation {
 
         let mutable = matches!(tokens.peek(), Some(Token::Mut { .. }));
 
-        let matcher = !Comb::MUT
-            >> Comb::ID
-            >> !(Comb::COLON >> Comb::TYPE_NAME)
-            >> Comb::ASSIGN
-            >> Comb::EXPR;
+        let matcher = lazy_comb!(!Comb::MUT >> Comb::PATTERN);
 
-        let result = matcher.parse(tokens)?;
+        let pattern_position = tokens.peek_at(usize::from(mutable)).map(Token::position);
 
-        let Some(AstNode::Id(id)) = result.first() else {
+        let Some(AstNode::Pattern(pattern)) = matcher.parse(tokens)?.into_iter().next() else {
             unreachable!()
         };
 
-        let mut type_name = None;
-
-        let value: Expression;
+        if !pattern.is_irrefutable() {
+            return Err(ParseError {
+                message:
+                    "only identifiers, `_` and tuples or structs of them can be bound by `let`"
+                        .into(),
+                position: pattern_position,
+                notes: vec![],
+            });
+        }
 
-        match result.get(1) {
-            Some(AstNode::TypeName(type_)) => {
-                type_name = Some(type_.clone());
+        let type_name = match tokens.peek() {
+            Some(Token::Colon { position }) => {
+                let position = *position;
+                tokens.next();
 
-                let Some(AstNode::Expression(expr)) = result.get(2) else {
+                let AstNode::TypeName(type_name) = TypeName::parse(tokens).map_err(|error| {
+                    let mut notes = error.notes;
+                    notes.push(Note {
+                        message: "expected a type after `:`".into(),
+                        position,
+                    });
+                    ParseError {
+                        position: error.position.or(Some(position)),
+                        notes,
+                        ..error
+                    }
+                })?
+                else {
                     unreachable!()
                 };
-                value = expr.clone();
-            }
-            Some(AstNode::Expression(expr)) => {
-                value = expr.clone();
+                Some(type_name)
             }
-            _ => unreachable!(),
-        }
+            _ => None,
+        };
+
+        let matcher = lazy_comb!(Comb::ASSIGN >> Comb::EXPR);
+
+        let Some(AstNode::Expression(value)) = matcher.parse(tokens)?.into_iter().next() else {
+            unreachable!()
+        };
 
         Ok(Initialisation {
-            id: id.clone(),
+            pattern,
             mutable,
-            value: value.clone(),
+            value,
             type_name,
         }
         .into())
@@ -67,13 +90,16 @@ impl FromTokens<Token> for Initialisation {
 
 impl From<Initialisation> for AstNode {
     fn from(value: Initialisation) -> Self {
-        AstNode::Initialization(value)
+        AstNode::Initialization(Box::new(value))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{lexer::Lexer, parser::ast::Num};
+    use crate::{
+        lexer::{Lexer, Position},
+        parser::ast::{Bool, FieldPattern, Id, If, Num, Postfix, Statement},
+    };
 
     use super::*;
 
@@ -88,7 +114,7 @@ mod tests {
 
         assert_eq!(
             Ok(Initialisation {
-                id: Id("foo".into()),
+                pattern: Pattern::Id(Id("foo".into())),
                 mutable: false,
                 type_name: None,
                 value: Expression::Num(Num::Integer(42))
@@ -109,7 +135,7 @@ mod tests {
 
         assert_eq!(
             Ok(Initialisation {
-                id: Id("foo".into()),
+                pattern: Pattern::Id(Id("foo".into())),
                 mutable: false,
                 type_name: Some(TypeName::Literal("i32".into())),
                 value: Expression::Num(Num::Integer(42))
@@ -119,6 +145,27 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_unit_initialisation() {
+        let mut tokens = Lexer::new("let foo: void = ();")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Id(Id("foo".into())),
+                mutable: false,
+                type_name: Some(TypeName::Void),
+                value: Expression::Unit
+            }
+            .into()),
+            result
+        )
+    }
+
     #[test]
     fn test_mutable_initialisation() {
         let mut tokens = Lexer::new("let mut foo = 42;")
@@ -130,7 +177,7 @@ mod tests {
 
         assert_eq!(
             Ok(Initialisation {
-                id: Id("foo".into()),
+                pattern: Pattern::Id(Id("foo".into())),
                 mutable: true,
                 type_name: None,
                 value: Expression::Num(Num::Integer(42))
@@ -139,4 +186,307 @@ mod tests {
             result
         )
     }
+
+    #[test]
+    fn test_mutable_initialisation_with_typename() {
+        let mut tokens = Lexer::new("let mut foo: i32 = 42;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Id(Id("foo".into())),
+                mutable: true,
+                type_name: Some(TypeName::Literal("i32".into())),
+                value: Expression::Num(Num::Integer(42))
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_mut_without_binding_fails() {
+        let mut tokens = Lexer::new("let mut = 42;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert!(Initialisation::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_tuple_destructuring() {
+        let mut tokens = Lexer::new("let (q, r) = divmod(7, 2);")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Tuple(vec![
+                    Pattern::Id(Id("q".into())),
+                    Pattern::Id(Id("r".into()))
+                ]),
+                mutable: false,
+                type_name: None,
+                value: Expression::Postfix(Postfix::Call {
+                    expr: Box::new(Expression::Id(Id("divmod".into()))),
+                    type_args: vec![],
+                    args: vec![
+                        Expression::Num(Num::Integer(7)),
+                        Expression::Num(Num::Integer(2))
+                    ]
+                })
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_struct_destructuring() {
+        let mut tokens = Lexer::new("let Point { x, y: (a, _) } = p;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Struct {
+                    name: Id("Point".into()),
+                    fields: vec![
+                        FieldPattern {
+                            name: Id("x".into()),
+                            pattern: Pattern::Id(Id("x".into()))
+                        },
+                        FieldPattern {
+                            name: Id("y".into()),
+                            pattern: Pattern::Tuple(vec![
+                                Pattern::Id(Id("a".into())),
+                                Pattern::Wildcard
+                            ])
+                        }
+                    ]
+                },
+                mutable: false,
+                type_name: None,
+                value: Expression::Id(Id("p".into()))
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_refutable_struct_destructuring_fails() {
+        let mut tokens = Lexer::new("let Point { x: 0, y } = p;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Some(Position::new(4, 5)),
+            result.err().and_then(|error| error.position)
+        );
+    }
+
+    #[test]
+    fn test_initialisation_with_function_type() {
+        let mut tokens = Lexer::new("let f: (i32, i32) -> i32 = add;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Id(Id("f".into())),
+                mutable: false,
+                type_name: Some(TypeName::Fn {
+                    params: vec![
+                        TypeName::Literal("i32".into()),
+                        TypeName::Literal("i32".into())
+                    ],
+                    return_type: Box::new(TypeName::Literal("i32".into()))
+                }),
+                value: Expression::Id(Id("add".into()))
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_initialisation_with_tuple_type() {
+        let mut tokens = Lexer::new("let t: (i32, bool) = (1, true);")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Id(Id("t".into())),
+                mutable: false,
+                type_name: Some(TypeName::Tuple(vec![
+                    TypeName::Literal("i32".into()),
+                    TypeName::Literal("bool".into())
+                ])),
+                value: Expression::Tuple(vec![
+                    Expression::Num(Num::Integer(1)),
+                    Expression::Bool(Bool(true))
+                ])
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_missing_type_after_colon_fails() {
+        let mut tokens = Lexer::new("let foo: = 42;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Err(ParseError {
+                message: "could not parse type name".into(),
+                position: Some(Position::new(7, 1)),
+                notes: vec![Note {
+                    message: "expected a type after `:`".into(),
+                    position: Position::new(7, 1),
+                }],
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn test_initialisation_with_if() {
+        let mut tokens = Lexer::new("let x = if (cond) { 1 } else { 2 };")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Id(Id("x".into())),
+                mutable: false,
+                type_name: None,
+                value: Expression::If(Box::new(If {
+                    condition: Box::new(Expression::Id(Id("cond".into()))),
+                    statements: vec![Statement::YieldingExpression(Expression::Num(
+                        Num::Integer(1)
+                    ))],
+                    else_statements: Some(vec![Statement::YieldingExpression(Expression::Num(
+                        Num::Integer(2)
+                    ))])
+                }))
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_refutable_pattern_fails() {
+        let mut tokens = Lexer::new("let (a, 1) = x;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Some(4),
+            result
+                .err()
+                .and_then(|error| error.position)
+                .map(|position| position.offset)
+        );
+    }
+
+    #[test]
+    fn test_soft_keywords_as_names() {
+        for name in ["get", "set", "default", "prop"] {
+            let mut tokens = Lexer::new(&format!("let {name} = 42;"))
+                .lex()
+                .expect("should work")
+                .into();
+
+            let result = Initialisation::parse(&mut tokens);
+
+            assert_eq!(
+                Ok(Initialisation {
+                    pattern: Pattern::Id(Id(name.into())),
+                    mutable: false,
+                    type_name: None,
+                    value: Expression::Num(Num::Integer(42))
+                }
+                .into()),
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialisation_with_try() {
+        let mut tokens = Lexer::new("let v = parse(input)?;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Initialisation {
+                pattern: Pattern::Id(Id("v".into())),
+                mutable: false,
+                type_name: None,
+                value: Expression::Try(Box::new(Expression::Postfix(Postfix::Call {
+                    expr: Box::new(Expression::Id(Id("parse".into()))),
+                    type_args: vec![],
+                    args: vec![Expression::Id(Id("input".into()))]
+                })))
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_keyword_as_name_fails() {
+        let mut tokens = Lexer::new("let let = 3;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Initialisation::parse(&mut tokens);
+
+        assert_eq!(
+            Err(ParseError {
+                message: "keyword `let` cannot be used as a name".into(),
+                position: Some(Position::new(4, 3)),
+                notes: vec![],
+            }),
+            result
+        );
+    }
 }
@@ -1,38 +1,77 @@
 mod assignment;
+mod conditional;
 mod constant;
 mod declaration;
+mod enum_declaration;
+mod extern_block;
+mod impl_block;
+mod import;
+mod infinite_loop;
 mod initialisation;
+mod module;
+mod property;
 mod struct_declaration;
+mod trait_declaration;
+mod type_alias;
 mod while_loop;
 
 pub use self::assignment::*;
+pub use self::conditional::*;
 pub use self::constant::*;
 pub use self::declaration::*;
+pub use self::enum_declaration::*;
+pub use self::extern_block::*;
+pub use self::impl_block::*;
+pub use self::import::*;
+pub use self::infinite_loop::*;
 pub use self::initialisation::*;
+pub use self::module::*;
+pub use self::property::*;
 pub use self::struct_declaration::*;
+pub use self::trait_declaration::*;
+pub use self::type_alias::*;
 pub use self::while_loop::*;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{combinators::Comb, FromTokens, ParseError},
 };
 
-use super::{AstNode, Expression, Function, If};
+use super::{AstNode, Attribute, Expression, Function, If};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Statement {
-    Function(Function),
-    If(If),
-    WhileLoop(WhileLoop),
-    Initialization(Initialisation),
-    Constant(Constant),
-    Assignment(Assignment),
+    Function(Box<Function>),
+    If(Box<If>),
+    WhileLoop(Box<WhileLoop>),
+    InfiniteLoop(Box<InfiniteLoop>),
+    Conditional(Box<Conditional>),
+    Initialization(Box<Initialisation>),
+    Constant(Box<Constant>),
+    Assignment(Box<Assignment>),
     Expression(Expression),
     YieldingExpression(Expression),
     Return(Expression),
+    /// `yield expr;`, which is only checked syntactically (i.e., it might occur outside of a
+    /// generator).
+    Yield(Expression),
+    /// `break;`, which is only checked syntactically (i.e., it might occur outside of a loop).
+    Break,
+    /// `continue;`, which is only checked syntactically (i.e., it might occur outside of a loop).
+    Continue,
     Comment(String),
     Declaration(Declaration),
-    StructDeclaration(StructDeclaration),
+    StructDeclaration(Box<StructDeclaration>),
+    EnumDeclaration(Box<EnumDeclaration>),
+    TypeAlias(Box<TypeAlias>),
+    ExternBlock(Box<ExternBlock>),
+    ImplBlock(Box<ImplBlock>),
+    TraitDeclaration(Box<TraitDeclaration>),
+    Module(Box<Module>),
+    Import(Import),
 }
 
 impl FromTokens<Token> for Statement {
@@ -40,101 +79,246 @@ impl FromTokens<Token> for Statement {
     where
         Self: Sized,
     {
+        // comments are skipped within statements, but retained in between them
+        if let Some(comment) = tokens.next_comment() {
+            return Ok(Statement::Comment(comment).into());
+        }
+
         let Some(next) = tokens.peek() else {
             todo!();
         };
 
+        // leading doc comments, attributes and a leading `pub` are parsed by the item they belong to
+        let (mut offset, attributed) = Attribute::skip_with_doc(tokens, 0);
+        if let Some(Token::PubKeyword { .. }) = tokens.peek_at(offset) {
+            offset += 1;
+        }
+
+        let next = match (offset, tokens.peek_at(offset)) {
+            (0, _) => next,
+            (
+                _,
+                Some(
+                    item @ (Token::FnKeyword { .. }
+                    | Token::StructKeyword { .. }
+                    | Token::EnumKeyword { .. }
+                    | Token::Const { .. }),
+                ),
+            ) if !attributed
+                || matches!(item, Token::FnKeyword { .. } | Token::StructKeyword { .. }) =>
+            {
+                item
+            }
+            // traits can not be documented, so only a `pub` may precede them
+            (1, Some(item @ Token::TraitKeyword { .. }))
+                if matches!(next, Token::PubKeyword { .. }) =>
+            {
+                item
+            }
+            // doc comments without a documented item are ordinary comments
+            (_, _) if !attributed && matches!(next, Token::DocComment { .. }) => {
+                let Some(Token::DocComment { value, position }) = tokens.next() else {
+                    unreachable!()
+                };
+                // the lexer strips the `///` and a single space from the value
+                let space = if position.length as usize > value.len() + 3 {
+                    " "
+                } else {
+                    ""
+                };
+                return Ok(Statement::Comment(format!("///{space}{value}")).into());
+            }
+            _ => {
+                let message = match next {
+                    _ if attributed => "attributes can only be used before functions and structs",
+                    _ => {
+                        "`pub` can only be used before functions, structs, enums, traits and constants"
+                    }
+                };
+                return Err(ParseError {
+                    message: message.into(),
+                    position: Some(next.position()),
+                    notes: vec![],
+                });
+            }
+        };
+
         match next {
             Token::IfKeyword { .. } => {
-                let matcher = Comb::IF >> !Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::IF >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let [AstNode::If(if_statement)] = result.as_slice() else {
+                let Some(AstNode::If(if_statement)) = result.next() else {
                     unreachable!()
                 };
-                Ok(Statement::If(if_statement.clone()).into())
+                Ok(Statement::If(if_statement).into())
             }
             Token::FnKeyword { .. } => {
-                let matcher = Comb::FUNCTION >> !Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::FUNCTION >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let [AstNode::Function(function)] = result.as_slice() else {
+                let Some(AstNode::Function(function)) = result.next() else {
                     unreachable!()
                 };
-                Ok(Statement::Function(function.clone()).into())
+                Ok(Statement::Function(function).into())
             }
             Token::WhileKeyword { .. } => {
-                let matcher = Comb::WHILE_LOOP >> !Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::WHILE_LOOP >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::WhileLoop(while_loop_statement)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::WhileLoop(while_loop_statement).into())
+            }
+            Token::LoopKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::INFINITE_LOOP >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::InfiniteLoop(infinite_loop)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::InfiniteLoop(infinite_loop).into())
+            }
+            Token::HashIf { .. } => {
+                let matcher = lazy_comb!(Comb::CONDITIONAL >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let [AstNode::WhileLoop(while_loop_statement)] = result.as_slice() else {
+                let Some(AstNode::Conditional(conditional)) = result.next() else {
                     unreachable!()
                 };
-                Ok(Statement::WhileLoop(while_loop_statement.clone()).into())
+                Ok(Statement::Conditional(conditional).into())
             }
             Token::Let { .. } => {
-                let matcher = Comb::INITIALISATION >> Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::INITIALISATION >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let [AstNode::Initialization(init)] = result.as_slice() else {
+                let Some(AstNode::Initialization(init)) = result.next() else {
                     unreachable!()
                 };
-                Ok(Statement::Initialization(init.clone()).into())
+                Ok(Statement::Initialization(init).into())
             }
-            Token::Const { .. } => {
-                let matcher = Comb::CONSTANT >> Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+            Token::Const { .. } if !matches!(tokens.peek_at(1), Some(Token::LBrace { .. })) => {
+                let matcher = lazy_comb!(Comb::CONSTANT >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let [AstNode::Constant(constant)] = result.as_slice() else {
+                let Some(AstNode::Constant(constant)) = result.next() else {
                     unreachable!()
                 };
-                Ok(Statement::Constant(constant.clone()).into())
+                Ok(Statement::Constant(constant).into())
             }
             Token::ReturnKeyword { .. } => {
-                let matcher = Comb::RETURN_KEYWORD >> Comb::EXPR >> Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::RETURN_KEYWORD >> Comb::EXPR >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let [AstNode::Expression(expr)] = result.as_slice() else {
+                let Some(AstNode::Expression(expr)) = result.next() else {
                     unreachable!()
                 };
-                Ok(Statement::Return(expr.clone()).into())
+                Ok(Statement::Return(expr).into())
+            }
+            Token::YieldKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::YIELD_KEYWORD >> Comb::EXPR >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::Expression(expr)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::Yield(expr).into())
+            }
+            Token::BreakKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::BREAK_KEYWORD >> Comb::SEMI);
+                matcher.parse(tokens)?;
+
+                Ok(Statement::Break.into())
+            }
+            Token::ContinueKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::CONTINUE_KEYWORD >> Comb::SEMI);
+                matcher.parse(tokens)?;
+
+                Ok(Statement::Continue.into())
             }
             Token::DeclareKeyword { .. } => {
-                let matcher = Comb::DECLARATION >> Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::DECLARATION >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let Some(AstNode::Declaration(declaration)) = result.first().cloned() else {
+                let Some(AstNode::Declaration(declaration)) = result.next() else {
                     unreachable!()
                 };
                 Ok(Statement::Declaration(declaration).into())
             }
-            Token::Comment { value, .. } => {
-                tokens.next();
-                Ok(Statement::Comment(value).into())
-            }
             Token::StructKeyword { .. } => {
-                let matcher = Comb::STRUCT_DECLARATION >> Comb::SEMI;
-                let result = matcher.parse(tokens)?;
+                let matcher = lazy_comb!(Comb::STRUCT_DECLARATION >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                let Some(AstNode::StructDeclaration(declaration)) = result.first().cloned() else {
+                let Some(AstNode::StructDeclaration(declaration)) = result.next() else {
                     unreachable!()
                 };
                 Ok(Statement::StructDeclaration(declaration).into())
             }
-            _ => {
-                if let Ok(assignment) = Self::parse_assignment(tokens) {
-                    return Ok(assignment);
+            Token::EnumKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::ENUM_DECLARATION >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::EnumDeclaration(declaration)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::EnumDeclaration(declaration).into())
+            }
+            Token::Id { .. } if TypeAlias::starts(tokens) => {
+                let matcher = lazy_comb!(Comb::TYPE_ALIAS >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::TypeAlias(type_alias)) = result.next() else {
+                    unreachable!()
                 };
+                Ok(Statement::TypeAlias(type_alias).into())
+            }
+            Token::ExternKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::EXTERN_BLOCK >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                if let Ok(expr) = Self::parse_expression(tokens) {
-                    return Ok(expr);
+                let Some(AstNode::ExternBlock(extern_block)) = result.next() else {
+                    unreachable!()
                 };
+                Ok(Statement::ExternBlock(extern_block).into())
+            }
+            Token::ImplKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::IMPL_BLOCK >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
 
-                Err(ParseError {
-                    message: "could not parse statement".into(),
-                    position: None,
-                })
+                let Some(AstNode::ImplBlock(impl_block)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::ImplBlock(impl_block).into())
+            }
+            Token::TraitKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::TRAIT_DECLARATION >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::TraitDeclaration(declaration)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::TraitDeclaration(declaration).into())
+            }
+            Token::ModKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::MODULE >> !Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::Module(module)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::Module(module).into())
+            }
+            Token::UseKeyword { .. } => {
+                let matcher = lazy_comb!(Comb::IMPORT >> Comb::SEMI);
+                let mut result = matcher.parse(tokens)?.into_iter();
+
+                let Some(AstNode::Import(import)) = result.next() else {
+                    unreachable!()
+                };
+                Ok(Statement::Import(import).into())
             }
+            _ => Self::parse_expression(tokens),
         }
     }
 }
@@ -143,37 +327,46 @@ impl Statement {
     fn parse_assignment(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let index = tokens.get_index();
 
-        let matcher = Comb::ASSIGNMENT >> Comb::SEMI;
-        let result = matcher.parse(tokens).map_err(|e| {
-            tokens.set_index(index);
-            e
-        })?;
+        let matcher = lazy_comb!(Comb::ASSIGNMENT >> Comb::SEMI);
+        let mut result = matcher
+            .parse(tokens)
+            .inspect_err(|_| {
+                tokens.set_index(index);
+            })?
+            .into_iter();
 
-        let [AstNode::Assignment(assignment)] = result.as_slice() else {
+        let Some(AstNode::Assignment(assignment)) = result.next() else {
             unreachable!()
         };
 
-        Ok(Statement::Assignment(assignment.clone()).into())
+        Ok(Statement::Assignment(assignment).into())
     }
 
     fn parse_expression(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let index = tokens.get_index();
 
         let matcher = Comb::EXPR;
-        let result = matcher.parse(tokens).map_err(|e| {
-            tokens.set_index(index);
-            e
-        })?;
+        let mut result = matcher
+            .parse(tokens)
+            .inspect_err(|_| {
+                tokens.set_index(index);
+            })?
+            .into_iter();
 
-        let [AstNode::Expression(expr)] = result.as_slice() else {
+        let Some(AstNode::Expression(expr)) = result.next() else {
             unreachable!()
         };
         match tokens.peek() {
+            // the expression was the assigned place (e.g., `arr[0]` in `arr[0] = 5;`)
+            Some(token) if Assignment::is_operator(token) => {
+                tokens.set_index(index);
+                Self::parse_assignment(tokens)
+            }
             Some(Token::Semicolon { .. }) => {
                 tokens.next();
-                Ok(Statement::Expression(expr.clone()).into())
+                Ok(Statement::Expression(expr).into())
             }
-            _ => Ok(Statement::YieldingExpression(expr.clone()).into()),
+            _ => Ok(Statement::YieldingExpression(expr).into()),
         }
     }
 }
@@ -187,8 +380,8 @@ impl From<Statement> for AstNode {
 #[cfg(test)]
 mod tests {
     use crate::{
-        lexer::Lexer,
-        parser::ast::{BinaryExpression, Id, Num, TypeName},
+        lexer::{Directive, Lexer, Position},
+        parser::ast::{BinaryExpression, Block, Id, Num, Pattern, TypeName, Visibility},
     };
 
     use super::*;
@@ -203,16 +396,243 @@ mod tests {
         let result = Statement::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Statement::Constant(Constant {
+            Ok(Statement::Constant(Box::new(Constant {
+                doc: None,
+                visibility: Visibility::Private,
                 id: Id("foo".into()),
                 type_name: TypeName::Literal("i32".into()),
                 value: Expression::Num(Num::Integer(42))
-            })
+            }))
             .into()),
             result
         )
     }
 
+    #[test]
+    fn test_public_items() {
+        let mut tokens = Lexer::new("pub const FOO: i32 = 42; pub fn foo(): i32 { FOO }")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Constant(constant)))
+                if constant.visibility == Visibility::Public
+        ));
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Function(function)))
+                if function.visibility == Visibility::Public
+        ));
+    }
+
+    #[test]
+    fn test_parse_attributed_function() {
+        let mut tokens = Lexer::new("#[inline] @since(version = 2) pub fn foo(): void {}")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Statement::parse(&mut tokens);
+
+        let Ok(AstNode::Statement(Statement::Function(function))) = result else {
+            panic!("expected function, got {result:?}");
+        };
+        assert_eq!(Visibility::Public, function.visibility);
+        assert_eq!(
+            vec![Id("inline".into()), Id("since".into())],
+            function
+                .attributes
+                .iter()
+                .map(|attribute| attribute.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_attributes_and_doc_comments_interleaved() {
+        let mut tokens = Lexer::new("/// a\n#[inline]\n/// b\n@cold\nfn f(): void {}")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Statement::parse(&mut tokens);
+
+        let Ok(AstNode::Statement(Statement::Function(function))) = result else {
+            panic!("expected function, got {result:?}");
+        };
+        assert_eq!(Some("a\nb".into()), function.doc);
+        assert_eq!(2, function.attributes.len());
+    }
+
+    #[test]
+    fn test_attribute_before_other_statement_fails() {
+        let mut tokens = Lexer::new("#[inline] let x = 1;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Err(ParseError {
+                message: "attributes can only be used before functions and structs".into(),
+                position: Some(Position::new(0, 2)),
+                notes: vec![],
+            }),
+            Statement::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_pub_before_other_statement_fails() {
+        let mut tokens = Lexer::new("pub let x = 1;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Err(ParseError {
+                message:
+                    "`pub` can only be used before functions, structs, enums, traits and constants"
+                        .into(),
+                position: Some(Position::new(0, 3)),
+                notes: vec![],
+            }),
+            Statement::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_public_trait() {
+        let mut tokens = Lexer::new("pub trait Marker {}")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::TraitDeclaration(declaration)))
+                if declaration.visibility == Visibility::Public
+        ));
+    }
+
+    #[test]
+    fn test_enum_declaration() {
+        let mut tokens = Lexer::new(
+            "pub enum Shape { Circle(f64), Rect { w: f64, h: f64 } }\nlet s = Shape::Circle(1.0);",
+        )
+        .lex()
+        .expect("should work")
+        .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::EnumDeclaration(declaration)))
+                if declaration.visibility == Visibility::Public && declaration.variants.len() == 2
+        ));
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Initialization(_)))
+        ));
+    }
+
+    #[test]
+    fn test_type_alias() {
+        let mut tokens = Lexer::new("type Pair<T> = (T, T);\ntype = 2;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::TypeAlias(alias))) if alias.type_params.len() == 1
+        ));
+        // `type` is still a valid name
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Assignment(_)))
+        ));
+    }
+
+    #[test]
+    fn test_extern_declarations() {
+        let mut tokens = Lexer::new(
+            "extern fn puts(s: str): i32;\nextern {\n    fn exit(code: i32): void;\n};\nputs(\"hi\");",
+        )
+        .lex()
+        .expect("should work")
+        .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::ExternBlock(block))) if block.functions.len() == 1
+        ));
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::ExternBlock(block)))
+                if block.functions[0].name == Id("exit".into())
+        ));
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Expression(_)))
+        ));
+    }
+
+    #[test]
+    fn test_documented_items() {
+        let mut tokens = Lexer::new(
+            "/// The answer.\nconst FOO: i32 = 42;\n/// Returns the answer.\n/// Always.\npub fn foo(): i32 { FOO }",
+        )
+        .lex()
+        .expect("should work")
+        .into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Constant(constant)))
+                if constant.doc.as_deref() == Some("The answer.")
+        ));
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Function(function)))
+                if function.doc.as_deref() == Some("Returns the answer.\nAlways.")
+                    && function.visibility == Visibility::Public
+        ));
+    }
+
+    #[test]
+    fn test_doc_comment_without_item_is_comment() {
+        for (input, expected) in [
+            ("/// The answer.\nlet x = 42;", "/// The answer."),
+            ("/// module\nmod foo {}", "/// module"),
+            ("/// trait\ntrait Foo {}", "/// trait"),
+            ("///", "///"),
+        ] {
+            let mut tokens = Lexer::new(input).lex().expect("should work").into();
+
+            assert_eq!(
+                Ok(Statement::Comment(expected.into()).into()),
+                Statement::parse(&mut tokens),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_doc_comment_in_function_body() {
+        let mut tokens = Lexer::new("fn foo(): void {\n    /// x\n    let x = 1;\n    /// y\n}")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert!(
+            matches!(result, Ok(AstNode::Statement(Statement::Function(_)))),
+            "{result:?}"
+        );
+    }
+
     #[test]
     fn test_basic_return() {
         let mut tokens = Lexer::new("return 42;").lex().expect("should work").into();
@@ -225,6 +645,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_yield() {
+        let mut tokens = Lexer::new("yield 42;").lex().expect("should work").into();
+
+        assert_eq!(
+            Ok(Statement::Yield(Expression::Num(Num::Integer(42))).into()),
+            Statement::parse(&mut tokens)
+        );
+
+        let mut tokens = Lexer::new("yield;").lex().expect("should work").into();
+        assert!(Statement::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_block_as_initial_value() {
+        let mut tokens = Lexer::new("let x = { let y = 2; y * 2 };")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(Statement::Initialization(Box::new(Initialisation {
+                pattern: Pattern::Id(Id("x".into())),
+                mutable: false,
+                type_name: None,
+                value: Expression::Block(Block {
+                    statements: vec![Statement::Initialization(Box::new(Initialisation {
+                        pattern: Pattern::Id(Id("y".into())),
+                        mutable: false,
+                        type_name: None,
+                        value: Expression::Num(Num::Integer(2))
+                    }))],
+                    tail: Some(Box::new(Expression::Binary(Box::new(
+                        BinaryExpression::Multiplication(
+                            Expression::Id(Id("y".into())),
+                            Expression::Num(Num::Integer(2))
+                        )
+                    ))))
+                })
+            }))
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_break_and_continue() {
+        let mut tokens = Lexer::new("break; continue;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(Ok(Statement::Break.into()), Statement::parse(&mut tokens));
+        assert_eq!(
+            Ok(Statement::Continue.into()),
+            Statement::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_break_needs_semicolon() {
+        let mut tokens = Lexer::new("break }").lex().expect("should work").into();
+
+        assert!(Statement::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_break_in_while_loop() {
+        let mut tokens = Lexer::new("while (x) { if (y) { break; }; continue; }")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let Ok(AstNode::Statement(Statement::WhileLoop(while_loop))) =
+            Statement::parse(&mut tokens)
+        else {
+            panic!("expected while loop");
+        };
+        assert!(matches!(
+            while_loop.block.statements.as_slice(),
+            [Statement::If(_), Statement::Continue]
+        ));
+    }
+
+    #[test]
+    fn test_break_in_infinite_loop() {
+        let mut tokens = Lexer::new("loop { if (y) { break; }; x = x + 1; }")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let Ok(AstNode::Statement(Statement::InfiniteLoop(infinite_loop))) =
+            Statement::parse(&mut tokens)
+        else {
+            panic!("expected infinite loop");
+        };
+        assert!(matches!(
+            infinite_loop.block.statements.as_slice(),
+            [Statement::If(_), Statement::Assignment(_)]
+        ));
+    }
+
+    #[test]
+    fn test_infinite_loop_needs_block() {
+        let mut tokens = Lexer::new("loop;").lex().expect("should work").into();
+
+        assert!(Statement::parse(&mut tokens).is_err());
+    }
+
     #[test]
     fn test_if_else_without_semicolon() {
         let mut tokens = Lexer::new("if (x) { 3 + 4 } else { 42 + 1337 }")
@@ -235,7 +766,7 @@ mod tests {
         let result = Statement::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Statement::If(If {
+            Ok(Statement::If(Box::new(If {
                 condition: Box::new(Expression::Id(Id("x".into()))),
                 statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
                     BinaryExpression::Addition(
@@ -243,13 +774,13 @@ mod tests {
                         Expression::Num(Num::Integer(4))
                     )
                 )))],
-                else_statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
-                    BinaryExpression::Addition(
+                else_statements: Some(vec![Statement::YieldingExpression(Expression::Binary(
+                    Box::new(BinaryExpression::Addition(
                         Expression::Num(Num::Integer(42)),
                         Expression::Num(Num::Integer(1337))
-                    )
-                )))],
-            })
+                    ))
+                ))]),
+            }))
             .into()),
             result
         )
@@ -265,7 +796,7 @@ mod tests {
         let result = Statement::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Statement::If(If {
+            Ok(Statement::If(Box::new(If {
                 condition: Box::new(Expression::Id(Id("x".into()))),
                 statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
                     BinaryExpression::Addition(
@@ -273,13 +804,13 @@ mod tests {
                         Expression::Num(Num::Integer(4))
                     )
                 )))],
-                else_statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
-                    BinaryExpression::Addition(
+                else_statements: Some(vec![Statement::YieldingExpression(Expression::Binary(
+                    Box::new(BinaryExpression::Addition(
                         Expression::Num(Num::Integer(42)),
                         Expression::Num(Num::Integer(1337))
-                    )
-                )))],
-            })
+                    ))
+                ))]),
+            }))
             .into()),
             result
         )
@@ -295,7 +826,7 @@ mod tests {
         let result = Statement::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Statement::If(If {
+            Ok(Statement::If(Box::new(If {
                 condition: Box::new(Expression::Id(Id("x".into()))),
                 statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
                     BinaryExpression::Addition(
@@ -303,13 +834,13 @@ mod tests {
                         Expression::Num(Num::Integer(4))
                     )
                 )))],
-                else_statements: vec![Statement::YieldingExpression(Expression::Binary(Box::new(
-                    BinaryExpression::Addition(
+                else_statements: Some(vec![Statement::YieldingExpression(Expression::Binary(
+                    Box::new(BinaryExpression::Addition(
                         Expression::Num(Num::Integer(42)),
                         Expression::Num(Num::Integer(1337))
-                    )
-                )))],
-            })
+                    ))
+                ))]),
+            }))
             .into()),
             result
         )
@@ -322,10 +853,11 @@ mod tests {
         let result = Statement::parse(&mut tokens);
 
         assert_eq!(
-            Ok(Statement::Assignment(Assignment {
-                id: Id("x".into()),
-                value: Expression::Num(Num::Integer(42))
-            })
+            Ok(Statement::Assignment(Box::new(Assignment {
+                lvalue: Lvalue::Id(Id("x".into())),
+                operator: None,
+                rvalue: Expression::Num(Num::Integer(42))
+            }))
             .into()),
             result
         )
@@ -339,4 +871,234 @@ mod tests {
 
         assert!(result.is_err())
     }
+
+    #[test]
+    fn test_assignment_to_index_and_property() {
+        let mut tokens = Lexer::new("arr[0] = 5; p.x = x + 1;")
+            .lex()
+            .expect("should work")
+            .into();
+
+        assert_eq!(
+            Ok(Statement::Assignment(Box::new(Assignment {
+                lvalue: Lvalue::Index {
+                    expr: Box::new(Expression::Id(Id("arr".into()))),
+                    index: Box::new(Expression::Num(Num::Integer(0)))
+                },
+                operator: None,
+                rvalue: Expression::Num(Num::Integer(5))
+            }))
+            .into()),
+            Statement::parse(&mut tokens)
+        );
+        assert_eq!(
+            Ok(Statement::Assignment(Box::new(Assignment {
+                lvalue: Lvalue::PropertyAccess {
+                    expr: Box::new(Expression::Id(Id("p".into()))),
+                    property: Id("x".into())
+                },
+                operator: None,
+                rvalue: Expression::Binary(Box::new(BinaryExpression::Addition(
+                    Expression::Id(Id("x".into())),
+                    Expression::Num(Num::Integer(1))
+                )))
+            }))
+            .into()),
+            Statement::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment() {
+        for (source, operator) in [
+            ("counter += 1;", CompoundOperator::Add),
+            ("counter -= 1;", CompoundOperator::Sub),
+            ("counter *= 1;", CompoundOperator::Mul),
+            ("counter /= 1;", CompoundOperator::Div),
+            ("counter %= 1;", CompoundOperator::Rem),
+        ] {
+            let mut tokens = Lexer::new(source).lex().expect("should work").into();
+
+            assert_eq!(
+                Ok(Statement::Assignment(Box::new(Assignment {
+                    lvalue: Lvalue::Id(Id("counter".into())),
+                    operator: Some(operator),
+                    rvalue: Expression::Num(Num::Integer(1))
+                }))
+                .into()),
+                Statement::parse(&mut tokens)
+            );
+        }
+    }
+
+    #[test]
+    fn test_assignment_to_non_place_fails() {
+        let mut tokens = Lexer::new("x + 1 = 5;").lex().expect("should work").into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert_eq!(
+            Err(ParseError {
+                message: "can only assign to variables, indexed elements and properties".into(),
+                position: Some(Position::new(0, 1)),
+                notes: vec![],
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn test_assignment_to_value_fails() {
+        for (source, message) in [
+            (
+                "f() = 3;",
+                "can not assign to the result of a call, only to variables, indexed elements and properties",
+            ),
+            (
+                "p.get(0) += 1;",
+                "can not assign to the result of a call, only to variables, indexed elements and properties",
+            ),
+            (
+                "4 = x;",
+                "can not assign to a literal, only to variables, indexed elements and properties",
+            ),
+        ] {
+            let mut tokens = Lexer::new(source).lex().expect("should work").into();
+
+            assert_eq!(
+                Err(ParseError {
+                    message: message.into(),
+                    position: Some(Position::new(0, 1)),
+                    notes: vec![],
+                }),
+                Statement::parse(&mut tokens)
+            );
+        }
+    }
+
+    #[test]
+    fn test_assignment_to_tuple_element() {
+        let mut tokens = Lexer::new("t.0 = 1;").lex().expect("should work").into();
+
+        assert!(matches!(
+            Statement::parse(&mut tokens),
+            Ok(AstNode::Statement(Statement::Assignment(assignment)))
+                if matches!(assignment.lvalue, Lvalue::TupleAccess { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_id_without_assignment_as_expression() {
+        let mut tokens = Lexer::new("x == 42;").lex().expect("should work").into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Statement(Statement::Expression(
+                Expression::Binary(_)
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_error_points_to_unexpected_token() {
+        let mut tokens = Lexer::new("x = ;").lex().expect("should work").into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert_eq!(
+            Some(4),
+            result
+                .err()
+                .and_then(|error| error.position)
+                .map(|position| position.offset)
+        );
+    }
+
+    #[test]
+    fn test_directive_is_trivia() {
+        let mut tokens = Lexer::new("let x = foo(\n// pesca:off\n1);")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Statement(Statement::Initialization(_)))
+        ));
+        assert_eq!(
+            vec![(
+                &Directive {
+                    name: "off".into(),
+                    arguments: vec![]
+                },
+                Position::new(13, 12)
+            )],
+            tokens.directives().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_comments_within_expressions() {
+        for input in [
+            "let x = /* c */ 4;",
+            "let x = a /* c */ / b;",
+            "let x = a // c\n / b;",
+        ] {
+            let mut tokens = Lexer::new(input).lex().expect("should work").into();
+
+            let result = Statement::parse(&mut tokens);
+
+            assert!(
+                matches!(result, Ok(AstNode::Statement(Statement::Initialization(_)))),
+                "{input}: {result:?}"
+            );
+            assert_eq!(None, tokens.peek());
+        }
+    }
+
+    #[test]
+    fn test_comment_between_statements() {
+        let mut tokens = Lexer::new("{\n    // c\n    foo();\n    /* d */\n}")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Comb::BLOCK.parse(&mut tokens);
+
+        let Ok(block) = result else {
+            panic!("expected block, got {result:?}");
+        };
+        let [AstNode::Block(Block { statements, .. })] = block.as_slice() else {
+            panic!("expected block, got {block:?}");
+        };
+        assert!(matches!(
+            statements.as_slice(),
+            [
+                Statement::Comment(c),
+                Statement::Expression(_),
+                Statement::Comment(d)
+            ] if c == "// c" && d == "/* d */"
+        ));
+    }
+
+    #[test]
+    fn test_const_block_is_expression() {
+        let mut tokens = Lexer::new("const { 42 };")
+            .lex()
+            .expect("should work")
+            .into();
+
+        let result = Statement::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Statement(Statement::Expression(
+                Expression::Comptime(_)
+            )))
+        ))
+    }
 }
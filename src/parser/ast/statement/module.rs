@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{AstNode, Id, Statement},
+        combinators::Comb,
+        FromTokens, ParseError,
+    },
+};
+
+/// A named group of top-level items, e.g.:
+///
+/// ```text
+/// mod geometry {
+///     struct Point { x: f64; y: f64; };
+///     fn origin(): Point { ... }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Module {
+    pub name: Id,
+    pub statements: Vec<Statement>,
+}
+
+impl FromTokens<Token> for Module {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let matcher = lazy_comb!(
+            Comb::MOD_KEYWORD >> Comb::ID >> Comb::LBRACE >> (Comb::STATEMENT ^ ()) >> Comb::RBRACE
+        );
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
+            unreachable!()
+        };
+
+        let mut statements = vec![];
+
+        while let Some(AstNode::Statement(statement)) = result.next() {
+            statements.push(statement);
+        }
+
+        Ok(Module { name, statements }.into())
+    }
+}
+
+impl From<Module> for AstNode {
+    fn from(value: Module) -> Self {
+        AstNode::Module(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::ast::{Constant, Expression, Num, TypeName, Visibility},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parse_empty_module() {
+        let mut tokens = Lexer::new("mod geometry {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Module {
+                name: Id("geometry".into()),
+                statements: vec![]
+            }
+            .into()),
+            Module::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_modules() {
+        let mut tokens = Lexer::new("mod geometry { const SCALE: f64 = 2.5; mod shapes {} }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(Module {
+                name: Id("geometry".into()),
+                statements: vec![
+                    Statement::Constant(Box::new(Constant {
+                        doc: None,
+                        visibility: Visibility::Private,
+                        id: Id("SCALE".into()),
+                        type_name: TypeName::Literal("f64".into()),
+                        value: Expression::Num(Num::FloatingPoint(2.5))
+                    })),
+                    Statement::Module(Box::new(Module {
+                        name: Id("shapes".into()),
+                        statements: vec![]
+                    }))
+                ]
+            }
+            .into()),
+            Module::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_module_without_name_fails() {
+        let mut tokens = Lexer::new("mod {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Module::parse(&mut tokens).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{parse_doc, AstNode, Block, Id, TypeName},
+        combinators::Comb,
+        FromTokens, ParseError,
+    },
+};
+
+/// A computed property of a struct, e.g.:
+///
+/// ```text
+/// prop length: f64 {
+///     get { ... }
+///     set(value) { ... }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Property {
+    /// The doc comments (`/// ...`) in front of the property.
+    pub doc: Option<String>,
+    pub name: Id,
+    pub type_name: TypeName,
+    pub getter: Option<Block>,
+    pub setter: Option<PropertySetter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PropertySetter {
+    /// Name of the parameter holding the new value.
+    pub parameter: Id,
+    pub body: Block,
+}
+
+impl FromTokens<Token> for Property {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let doc = parse_doc(tokens);
+
+        // `prop` is a contextual keyword, so it is lexed as an identifier
+        match tokens.next() {
+            Some(Token::Id { value, .. }) if value.as_str() == "prop" => {}
+            Some(token) => {
+                return Err(ParseError {
+                    message: format!("Unexpected {token:?} while trying to parse property"),
+                    position: Some(token.position()),
+                    notes: vec![],
+                })
+            }
+            None => return Err(ParseError::eof("property")),
+        }
+
+        let matcher = lazy_comb!(Comb::ID >> Comb::COLON >> Comb::TYPE_NAME);
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
+            unreachable!()
+        };
+
+        let Some(AstNode::TypeName(type_name)) = result.next() else {
+            unreachable!()
+        };
+
+        Comb::LBRACE.parse(tokens)?;
+
+        let mut getter = None;
+        let mut setter = None;
+
+        // `get` and `set` are only keywords within a property, so they are matched as identifiers
+        loop {
+            match tokens.peek() {
+                Some(Token::Id { value, position }) if value.as_str() == "get" => {
+                    let position = *position;
+                    if getter.is_some() {
+                        return Err(ParseError {
+                            message: format!("duplicate getter for property '{}'", name.0),
+                            position: Some(position),
+                            notes: vec![],
+                        });
+                    }
+                    tokens.next();
+
+                    let mut result = Comb::BLOCK.parse(tokens)?.into_iter();
+                    let Some(AstNode::Block(block)) = result.next() else {
+                        unreachable!()
+                    };
+                    getter = Some(block);
+                }
+                Some(Token::Id { value, position }) if value.as_str() == "set" => {
+                    let position = *position;
+                    if setter.is_some() {
+                        return Err(ParseError {
+                            message: format!("duplicate setter for property '{}'", name.0),
+                            position: Some(position),
+                            notes: vec![],
+                        });
+                    }
+                    tokens.next();
+
+                    let matcher =
+                        lazy_comb!(Comb::LPAREN >> Comb::ID >> Comb::RPAREN >> Comb::BLOCK);
+                    let mut result = matcher.parse(tokens)?.into_iter();
+
+                    let Some(AstNode::Id(parameter)) = result.next() else {
+                        unreachable!()
+                    };
+                    let Some(AstNode::Block(body)) = result.next() else {
+                        unreachable!()
+                    };
+                    setter = Some(PropertySetter { parameter, body });
+                }
+                _ => break,
+            }
+        }
+
+        let closing = tokens.peek().map(Token::position);
+        Comb::RBRACE.parse(tokens)?;
+
+        if getter.is_none() && setter.is_none() {
+            return Err(ParseError {
+                message: format!("property '{}' needs a getter or a setter", name.0),
+                position: closing,
+                notes: vec![],
+            });
+        }
+
+        Ok(Property {
+            doc,
+            name,
+            type_name,
+            getter,
+            setter,
+        }
+        .into())
+    }
+}
+
+impl From<Property> for AstNode {
+    fn from(value: Property) -> Self {
+        AstNode::Property(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, parser::ast::Expression};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_property_with_getter_and_setter() {
+        let mut tokens = Lexer::new("prop length: f64 { get { len } set(value) { len = value; } }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Property::parse(&mut tokens);
+
+        let Ok(AstNode::Property(property)) = result else {
+            panic!("expected property, got {result:?}");
+        };
+        assert_eq!(Id("length".into()), property.name);
+        assert_eq!(TypeName::Literal("f64".into()), property.type_name);
+        assert_eq!(
+            Some(Block {
+                statements: vec![],
+                tail: Some(Box::new(Expression::Id(Id("len".into()))))
+            }),
+            property.getter
+        );
+        assert_eq!(
+            Some(Id("value".into())),
+            property.setter.map(|setter| setter.parameter)
+        );
+    }
+
+    #[test]
+    fn test_parse_readonly_property() {
+        let mut tokens = Lexer::new("prop area: f64 { get { 42 } }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = Property::parse(&mut tokens);
+
+        assert!(matches!(
+            result,
+            Ok(AstNode::Property(property)) if property.getter.is_some() && property.setter.is_none()
+        ));
+    }
+
+    #[test]
+    fn test_parse_property_without_accessors_fails() {
+        let mut tokens = Lexer::new("prop area: f64 {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Property::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_property_with_duplicate_getter_fails() {
+        let mut tokens = Lexer::new("prop area: f64 { get { 1 } get { 2 } }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(Property::parse(&mut tokens).is_err());
+    }
+}
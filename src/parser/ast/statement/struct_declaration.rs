@@ -1,70 +1,174 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    lazy_comb,
     lexer::{Token, Tokens},
     parser::{
-        ast::{AstNode, Id, TypeName},
+        ast::{AstNode, Attribute, Expression, Id, Property, TypeName, Visibility},
         combinators::Comb,
-        FromTokens, ParseError,
+        ensure_unique, FromTokens, ParseError,
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StructDeclaration {
+    /// The doc comments (`/// ...`) in front of the declaration.
+    doc: Option<String>,
+    /// The attributes in front of the declaration, e.g., `@deprecated("use Point3 instead")`.
+    attributes: Vec<Attribute>,
+    visibility: Visibility,
     id: Id,
-    fields: Vec<StructFieldDeclaration>,
+    /// Names of the generic type parameters, e.g., `A` and `B` in `struct Pair<A, B> { ... }`.
+    type_params: Vec<Id>,
+    pub(crate) fields: Vec<StructFieldDeclaration>,
+    pub(crate) properties: Vec<Property>,
 }
 
 impl FromTokens<Token> for StructDeclaration {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::STRUCT_KEYWORD
-            >> Comb::ID
-            >> Comb::LBRACE
-            >> (Comb::STRUCT_FIELD_DECLARATION ^ ())
-            >> Comb::RBRACE;
+        let (doc, attributes) = Attribute::parse_with_doc(tokens)?;
+        let visibility = Visibility::parse(tokens);
 
+        let matcher = lazy_comb!(
+            Comb::STRUCT_KEYWORD
+                >> Comb::ID
+                >> !(Comb::LESS_THAN >> (Comb::ID % Comb::COMMA) >> Comb::GREATER_THAN)
+                >> Comb::LBRACE
+        );
         let mut result = matcher.parse(tokens)?.into_iter();
 
         let Some(AstNode::Id(id)) = result.next() else {
             unreachable!()
         };
 
+        let type_params = result
+            .map(|param| {
+                let AstNode::Id(param) = param else {
+                    unreachable!()
+                };
+                param
+            })
+            .collect::<Vec<_>>();
+
         let mut fields = vec![];
+        let mut properties = vec![];
+        // fields and properties share the same namespace
+        let mut names = vec![];
+
+        loop {
+            // doc comments and attributes are parsed by the field or property they belong to
+            let (mut offset, _) = Attribute::skip_with_doc(tokens, 0);
 
-        while let Some(AstNode::StructFieldDeclaration(field)) = result.next() {
-            fields.push(field);
+            // `prop` is only a keyword when followed by the name of the property, so it can still
+            // be used as the name of a field
+            let (matcher, position) = match (tokens.peek_at(offset), tokens.peek_at(offset + 1)) {
+                (Some(Token::RBrace { .. }) | None, _) => break,
+                (Some(Token::Id { value, .. }), Some(name @ Token::Id { .. }))
+                    if value.as_str() == "prop" =>
+                {
+                    (&Comb::PROPERTY, Some(name.position()))
+                }
+                _ => {
+                    // the name follows the doc comments, attributes and `pub` of the field
+                    if let Some(Token::PubKeyword { .. }) = tokens.peek_at(offset) {
+                        offset += 1;
+                    }
+                    (
+                        &Comb::STRUCT_FIELD_DECLARATION,
+                        tokens.peek_at(offset).map(Token::position),
+                    )
+                }
+            };
+
+            match matcher.parse(tokens)?.into_iter().next() {
+                Some(AstNode::StructFieldDeclaration(field)) => {
+                    names.push((field.name.0.clone(), position));
+                    fields.push(*field);
+                }
+                Some(AstNode::Property(property)) => {
+                    names.push((property.name.0.clone(), position));
+                    properties.push(*property);
+                }
+                _ => unreachable!(),
+            }
         }
 
-        Ok(StructDeclaration { id, fields }.into())
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique(
+            "field",
+            names
+                .into_iter()
+                .map(|(name, position)| (name, position.unwrap_or_default())),
+        )?;
+
+        Ok(StructDeclaration {
+            doc,
+            attributes,
+            visibility,
+            id,
+            type_params,
+            fields,
+            properties,
+        }
+        .into())
     }
 }
 
 impl From<StructDeclaration> for AstNode {
     fn from(value: StructDeclaration) -> Self {
-        Self::StructDeclaration(value)
+        Self::StructDeclaration(Box::new(value))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StructFieldDeclaration {
+    /// The doc comments (`/// ...`) in front of the field.
+    doc: Option<String>,
+    attributes: Vec<Attribute>,
+    visibility: Visibility,
     name: Id,
     type_name: TypeName,
+    /// Value used for this field if it is omitted in an initialisation (e.g., `retries: i32 = 3;`).
+    pub(crate) default_value: Option<Expression>,
 }
 
 impl FromTokens<Token> for StructFieldDeclaration {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher = Comb::ID >> Comb::COLON >> Comb::TYPE_NAME >> Comb::SEMI;
-        let result = matcher.parse(tokens)?;
+        let (doc, attributes) = Attribute::parse_with_doc(tokens)?;
+        let visibility = Visibility::parse(tokens);
 
-        let Some(AstNode::Id(name)) = result.first() else {
+        let matcher = lazy_comb!(
+            Comb::ID
+                >> Comb::COLON
+                >> Comb::TYPE_NAME
+                >> !(Comb::ASSIGN >> Comb::EXPR)
+                >> Comb::SEMI
+        );
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
             unreachable!()
         };
 
-        let Some(AstNode::TypeName(type_name)) = result.get(1) else {
+        let Some(AstNode::TypeName(type_name)) = result.next() else {
             unreachable!()
         };
 
+        let default_value = result.next().map(|value| {
+            let AstNode::Expression(value) = value else {
+                unreachable!()
+            };
+            value
+        });
+
         Ok(StructFieldDeclaration {
-            name: name.clone(),
-            type_name: type_name.clone(),
+            doc,
+            attributes,
+            visibility,
+            name,
+            type_name,
+            default_value,
         }
         .into())
     }
@@ -72,16 +176,19 @@ impl FromTokens<Token> for StructFieldDeclaration {
 
 impl From<StructFieldDeclaration> for AstNode {
     fn from(value: StructFieldDeclaration) -> Self {
-        Self::StructFieldDeclaration(value)
+        Self::StructFieldDeclaration(Box::new(value))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        lexer::Lexer,
+        lexer::{Lexer, Position},
         parser::{
-            ast::{Id, StructFieldDeclaration, TypeName},
+            ast::{
+                AstNode, Attribute, AttributeArgument, Expression, Id, Num, StructFieldDeclaration,
+                TypeName, Visibility,
+            },
             FromTokens,
         },
     };
@@ -99,8 +206,159 @@ mod tests {
 
         assert_eq!(
             Ok(StructDeclaration {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                id: Id("Foo".into()),
+                type_params: vec![],
+                fields: vec![],
+                properties: vec![]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn parse_documented_struct() {
+        let mut tokens = Lexer::new("/// A point.\n///\n/// In 2D.\npub struct Point {}")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(StructDeclaration {
+                doc: Some("A point.\n\nIn 2D.".into()),
+                attributes: vec![],
+                visibility: Visibility::Public,
+                id: Id("Point".into()),
+                type_params: vec![],
+                fields: vec![],
+                properties: vec![]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn parse_documented_fields() {
+        let mut tokens = Lexer::new(
+            "struct Circle {
+            /// The radius.
+            #[positive]
+            pub radius: f64;
+            /// The area.
+            prop area: f64 { get { radius * radius * 3.14 } }
+        }",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Ok(AstNode::StructDeclaration(declaration)) = result else {
+            panic!("expected struct, got {result:?}");
+        };
+        assert_eq!(Some("The radius.".into()), declaration.fields[0].doc);
+        assert_eq!(Some("The area.".into()), declaration.properties[0].doc);
+    }
+
+    #[test]
+    fn test_generic_struct() {
+        let mut tokens = Lexer::new(
+            "struct Pair<A, B> {
+            first: A;
+            rest: Vec<Vec<B>>;
+        }",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(StructDeclaration {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                id: Id("Pair".into()),
+                type_params: vec![Id("A".into()), Id("B".into())],
+                fields: vec![
+                    StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
+                        name: Id("first".into()),
+                        type_name: TypeName::Literal("A".into()),
+                        default_value: None
+                    },
+                    StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
+                        name: Id("rest".into()),
+                        type_name: TypeName::Generic {
+                            name: "Vec".into(),
+                            args: vec![TypeName::Generic {
+                                name: "Vec".into(),
+                                args: vec![TypeName::Literal("B".into())]
+                            }]
+                        },
+                        default_value: None
+                    }
+                ],
+                properties: vec![]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn parse_public_struct() {
+        let mut tokens = Lexer::new(
+            "pub struct Foo {
+            pub foo: u32;
+            bar: u32;
+        }",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(StructDeclaration {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Public,
                 id: Id("Foo".into()),
-                fields: vec![]
+                type_params: vec![],
+                fields: vec![
+                    StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Public,
+                        name: Id("foo".into()),
+                        type_name: TypeName::Literal("u32".into()),
+                        default_value: None
+                    },
+                    StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
+                        name: Id("bar".into()),
+                        type_name: TypeName::Literal("u32".into()),
+                        default_value: None
+                    }
+                ],
+                properties: vec![]
             }
             .into()),
             result
@@ -122,11 +380,20 @@ mod tests {
 
         assert_eq!(
             Ok(StructDeclaration {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
                 id: Id("Foo".into()),
+                type_params: vec![],
                 fields: vec![StructFieldDeclaration {
+                    doc: None,
+                    attributes: vec![],
+                    visibility: Visibility::Private,
                     name: Id("foo".into()),
-                    type_name: TypeName::Literal("u32".into())
-                }]
+                    type_name: TypeName::Literal("u32".into()),
+                    default_value: None
+                }],
+                properties: vec![]
             }
             .into()),
             result
@@ -149,20 +416,226 @@ mod tests {
 
         assert_eq!(
             Ok(StructDeclaration {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
                 id: Id("Foo".into()),
+                type_params: vec![],
                 fields: vec![
                     StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
                         name: Id("foo".into()),
-                        type_name: TypeName::Literal("u32".into())
+                        type_name: TypeName::Literal("u32".into()),
+                        default_value: None
                     },
                     StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
                         name: Id("baz".into()),
-                        type_name: TypeName::Array(Box::new(TypeName::Literal("f64".into())))
+                        type_name: TypeName::Array(Box::new(TypeName::Literal("f64".into()))),
+                        default_value: None
+                    }
+                ],
+                properties: vec![]
+            }
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn parse_struct_with_default_values() {
+        let mut tokens = Lexer::new(
+            "struct Config {
+            retries: i32 = 3;
+            verbose: bool;
+        }",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(StructDeclaration {
+                doc: None,
+                attributes: vec![],
+                visibility: Visibility::Private,
+                id: Id("Config".into()),
+                type_params: vec![],
+                fields: vec![
+                    StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
+                        name: Id("retries".into()),
+                        type_name: TypeName::Literal("i32".into()),
+                        default_value: Some(Expression::Num(Num::Integer(3)))
+                    },
+                    StructFieldDeclaration {
+                        doc: None,
+                        attributes: vec![],
+                        visibility: Visibility::Private,
+                        name: Id("verbose".into()),
+                        type_name: TypeName::Literal("bool".into()),
+                        default_value: None
                     }
-                ]
+                ],
+                properties: vec![]
             }
             .into()),
             result
         );
     }
+
+    #[test]
+    fn parse_struct_with_property() {
+        let mut tokens = Lexer::new(
+            "struct Circle {
+            radius: f64;
+            prop diameter: f64 { get { radius * 2 } }
+        }",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Ok(AstNode::StructDeclaration(declaration)) = result else {
+            panic!("expected struct declaration, got {result:?}");
+        };
+        assert_eq!(1, declaration.fields.len());
+        assert_eq!(1, declaration.properties.len());
+        assert_eq!(Id("diameter".into()), declaration.properties[0].name);
+    }
+
+    #[test]
+    fn parse_struct_with_duplicate_fields() {
+        let mut tokens = Lexer::new("struct Foo { foo: u32; prop foo: u32 { get { 1 } } }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Err(error) = result else {
+            panic!("expected error, got {result:?}");
+        };
+        assert_eq!("duplicate field 'foo'", error.message);
+        assert_eq!(Some(Position::new(28, 3)), error.position);
+        assert_eq!(Position::new(13, 3), error.notes[0].position);
+    }
+
+    #[test]
+    fn test_struct_with_attributes() {
+        let mut tokens =
+            Lexer::new("@deprecated(\"use Point3\") struct Point { #[packed] x: i32; }")
+                .lex()
+                .expect("something is wrong")
+                .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Ok(AstNode::StructDeclaration(declaration)) = result else {
+            panic!("expected struct declaration, got {result:?}");
+        };
+        assert_eq!(
+            vec![Attribute {
+                name: Id("deprecated".into()),
+                args: vec![AttributeArgument::Expression(Expression::String(
+                    "use Point3".into()
+                ))]
+            }],
+            declaration.attributes
+        );
+        assert_eq!(
+            vec![Attribute {
+                name: Id("packed".into()),
+                args: vec![]
+            }],
+            declaration.fields[0].attributes
+        );
+    }
+
+    #[test]
+    fn test_duplicate_attributed_fields_fail() {
+        let mut tokens = Lexer::new(
+            "@deprecated(\"use Point3\") struct Point { #[packed] pub x: i32; #[packed] x: i32; }",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Err(error) = result else {
+            panic!("expected error, got {result:?}");
+        };
+        assert_eq!("duplicate field 'x'", error.message);
+        assert_eq!(Some(Position::new(73, 1)), error.position);
+        assert_eq!(Position::new(55, 1), error.notes[0].position);
+    }
+
+    #[test]
+    fn test_attributes_and_doc_comments_interleaved() {
+        let mut tokens = Lexer::new(
+            "#[packed]\n/// A point.\nstruct Point {\n    #[positive]\n    /// The x.\n    x: i32;\n}",
+        )
+        .lex()
+        .expect("something is wrong")
+        .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Ok(AstNode::StructDeclaration(declaration)) = result else {
+            panic!("expected struct declaration, got {result:?}");
+        };
+        assert_eq!(Some("A point.".into()), declaration.doc);
+        assert_eq!(1, declaration.attributes.len());
+        assert_eq!(Some("The x.".into()), declaration.fields[0].doc);
+        assert_eq!(1, declaration.fields[0].attributes.len());
+    }
+
+    #[test]
+    fn parse_field_named_prop() {
+        let mut tokens = Lexer::new("struct Foo { prop: i32; prop bar: i32 { get { prop } } }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Ok(AstNode::StructDeclaration(declaration)) = result else {
+            panic!("expected struct declaration, got {result:?}");
+        };
+        assert_eq!(Id("prop".into()), declaration.fields[0].name);
+        assert_eq!(Id("bar".into()), declaration.properties[0].name);
+    }
+
+    #[test]
+    fn parse_self_referential_struct() {
+        let mut tokens = Lexer::new("struct Node { value: i32; next: Node?; children: Node[]; }")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        let result = StructDeclaration::parse(&mut tokens);
+
+        let Ok(AstNode::StructDeclaration(declaration)) = result else {
+            panic!("expected struct declaration, got {result:?}");
+        };
+        assert_eq!(
+            TypeName::Optional(Box::new(TypeName::Literal("Node".into()))),
+            declaration.fields[1].type_name
+        );
+        assert_eq!(
+            TypeName::Array(Box::new(TypeName::Literal("Node".into()))),
+            declaration.fields[2].type_name
+        );
+    }
 }
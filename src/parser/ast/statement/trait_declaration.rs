@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{AstNode, Id, Parameter, TypeName, Visibility},
+        combinators::Comb,
+        ensure_unique, FromTokens, ParseError,
+    },
+};
+
+/// A trait with associated types and method signatures, e.g.:
+///
+/// ```text
+/// trait Container {
+///     type Item;
+///     fn get(&self, i: i32): Self::Item;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitDeclaration {
+    pub visibility: Visibility,
+    pub name: Id,
+    pub associated_types: Vec<Id>,
+    pub methods: Vec<TraitMethod>,
+}
+
+/// Signature of a method within a trait (without a body).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitMethod {
+    pub name: Id,
+    pub parameters: Vec<Parameter>,
+    pub return_type: TypeName,
+}
+
+impl FromTokens<Token> for TraitDeclaration {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let visibility = Visibility::parse(tokens);
+
+        let matcher = lazy_comb!(Comb::TRAIT_KEYWORD >> Comb::ID >> Comb::LBRACE);
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
+            unreachable!()
+        };
+
+        let mut associated_types = vec![];
+        let mut methods = vec![];
+        // associated types and methods share the same namespace
+        let mut names = vec![];
+
+        loop {
+            match (tokens.peek(), tokens.peek_at(1)) {
+                (Some(Token::RBrace { .. }) | None, _) => break,
+                // `type` is only a keyword within a trait
+                (Some(Token::Id { value, .. }), Some(Token::Id { position, .. }))
+                    if value.as_str() == "type" =>
+                {
+                    let position = *position;
+                    tokens.next();
+
+                    let matcher = lazy_comb!(Comb::ID >> Comb::SEMI);
+                    let Some(AstNode::Id(associated_type)) =
+                        matcher.parse(tokens)?.into_iter().next()
+                    else {
+                        unreachable!()
+                    };
+
+                    names.push((associated_type.0.clone(), position));
+                    associated_types.push(associated_type);
+                }
+                (Some(Token::FnKeyword { .. }), Some(token)) => {
+                    let position = token.position();
+                    let method = TraitMethod::parse(tokens)?;
+
+                    names.push((method.name.0.clone(), position));
+                    methods.push(method);
+                }
+                (Some(token), _) => {
+                    return Err(ParseError {
+                        message: format!(
+                            "Unexpected {token:?} in trait, expected an associated type or a method"
+                        ),
+                        position: Some(token.position()),
+                        notes: vec![],
+                    })
+                }
+            }
+        }
+
+        Comb::RBRACE.parse(tokens)?;
+
+        ensure_unique("item", names)?;
+
+        Ok(TraitDeclaration {
+            visibility,
+            name,
+            associated_types,
+            methods,
+        }
+        .into())
+    }
+}
+
+impl TraitMethod {
+    /// Parse `fn name(parameters): return_type;`.
+    fn parse(tokens: &mut Tokens<Token>) -> Result<TraitMethod, ParseError> {
+        let matcher = lazy_comb!(Comb::FN_KEYWORD >> Comb::ID);
+        let Some(AstNode::Id(name)) = matcher.parse(tokens)?.into_iter().next() else {
+            unreachable!()
+        };
+
+        let parameters = Parameter::parse_list(tokens, true)?;
+
+        let matcher = lazy_comb!(Comb::COLON >> Comb::TYPE_NAME >> Comb::SEMI);
+        let Some(AstNode::TypeName(return_type)) = matcher.parse(tokens)?.into_iter().next() else {
+            unreachable!()
+        };
+
+        Ok(TraitMethod {
+            name,
+            parameters,
+            return_type,
+        })
+    }
+}
+
+impl From<TraitDeclaration> for AstNode {
+    fn from(value: TraitDeclaration) -> Self {
+        AstNode::TraitDeclaration(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<AstNode, ParseError> {
+        let mut tokens = Lexer::new(input).lex().expect("something is wrong").into();
+        TraitDeclaration::parse(&mut tokens)
+    }
+
+    #[test]
+    fn test_parse_trait_with_associated_type() {
+        assert_eq!(
+            Ok(TraitDeclaration {
+                visibility: Visibility::Private,
+                name: Id("Container".into()),
+                associated_types: vec![Id("Item".into())],
+                methods: vec![TraitMethod {
+                    name: Id("get".into()),
+                    parameters: vec![
+                        Parameter {
+                            name: Id("self".into()),
+                            type_name: Some(TypeName::Reference(Box::new(TypeName::Literal(
+                                "Self".into()
+                            ))))
+                        },
+                        Parameter {
+                            name: Id("i".into()),
+                            type_name: Some(TypeName::Literal("i32".into()))
+                        }
+                    ],
+                    return_type: TypeName::Associated {
+                        base: Box::new(TypeName::Literal("Self".into())),
+                        name: "Item".into()
+                    }
+                }]
+            }
+            .into()),
+            parse("trait Container { type Item; fn get(&self, i: i32): Self::Item; }")
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_trait() {
+        assert_eq!(
+            Ok(TraitDeclaration {
+                visibility: Visibility::Private,
+                name: Id("Marker".into()),
+                associated_types: vec![],
+                methods: vec![]
+            }
+            .into()),
+            parse("trait Marker {}")
+        );
+    }
+
+    #[test]
+    fn test_parse_public_trait() {
+        assert_eq!(
+            Ok(TraitDeclaration {
+                visibility: Visibility::Public,
+                name: Id("Marker".into()),
+                associated_types: vec![],
+                methods: vec![]
+            }
+            .into()),
+            parse("pub trait Marker {}")
+        );
+    }
+
+    #[test]
+    fn test_parse_duplicate_item_fails() {
+        let result = parse("trait Container { type Item; fn Item(): i32; }");
+
+        assert!(matches!(
+            result,
+            Err(ParseError { message, .. }) if message == "duplicate item 'Item'"
+        ));
+    }
+
+    #[test]
+    fn test_parse_method_with_body_fails() {
+        assert!(parse("trait Container { fn get(): i32 { 42 } }").is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lazy_comb,
+    lexer::{Token, Tokens},
+    parser::{
+        ast::{AstNode, Id, TypeName},
+        combinators::Comb,
+        FromTokens, Note, ParseError,
+    },
+};
+
+/// Another name for a type, e.g., `type Meters = i32;` or `type Pair<T> = (T, T);`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeAlias {
+    pub name: Id,
+    /// Names of the generic type parameters, e.g., `T` in `type Pair<T> = (T, T);`.
+    pub type_params: Vec<Id>,
+    pub type_name: TypeName,
+}
+
+impl TypeAlias {
+    /// Whether the tokens start a type alias. `type` is a contextual keyword, so it can still be
+    /// used as a name (e.g., `let type = 1;`).
+    pub(crate) fn starts(tokens: &Tokens<Token>) -> bool {
+        matches!(
+            (tokens.peek(), tokens.peek_at(1)),
+            (Some(Token::Id { value, .. }), Some(Token::Id { .. })) if value.as_str() == "type"
+        )
+    }
+}
+
+impl FromTokens<Token> for TypeAlias {
+    fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        // `type` is lexed as an identifier
+        match tokens.next() {
+            Some(Token::Id { value, .. }) if value.as_str() == "type" => {}
+            Some(token) => {
+                return Err(ParseError {
+                    message: format!("Unexpected {token:?} while trying to parse type alias"),
+                    position: Some(token.position()),
+                    notes: vec![],
+                })
+            }
+            None => return Err(ParseError::eof("type alias")),
+        }
+
+        let matcher = lazy_comb!(
+            Comb::ID >> !(Comb::LESS_THAN >> (Comb::ID % Comb::COMMA) >> Comb::GREATER_THAN)
+        );
+        let mut result = matcher.parse(tokens)?.into_iter();
+
+        let Some(AstNode::Id(name)) = result.next() else {
+            unreachable!()
+        };
+
+        let type_params = result
+            .map(|param| {
+                let AstNode::Id(param) = param else {
+                    unreachable!()
+                };
+                param
+            })
+            .collect::<Vec<_>>();
+
+        let position = tokens.peek().map(Token::position);
+        Comb::ASSIGN.parse(tokens)?;
+
+        let AstNode::TypeName(type_name) = TypeName::parse(tokens).map_err(|error| {
+            let mut notes = error.notes;
+            notes.extend(position.map(|position| Note {
+                message: "expected a type after `=`".into(),
+                position,
+            }));
+            ParseError {
+                position: error.position.or(position),
+                notes,
+                ..error
+            }
+        })?
+        else {
+            unreachable!()
+        };
+
+        Ok(TypeAlias {
+            name,
+            type_params,
+            type_name,
+        }
+        .into())
+    }
+}
+
+impl From<TypeAlias> for AstNode {
+    fn from(value: TypeAlias) -> Self {
+        AstNode::TypeAlias(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::{Lexer, Position},
+        parser::{
+            ast::{Id, TypeName},
+            FromTokens, Note, ParseError,
+        },
+    };
+
+    use super::TypeAlias;
+
+    #[test]
+    fn parse_simple_alias() {
+        let mut tokens = Lexer::new("type Meters = i32")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(TypeAlias {
+                name: Id("Meters".into()),
+                type_params: vec![],
+                type_name: TypeName::Literal("i32".into())
+            }
+            .into()),
+            TypeAlias::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn parse_generic_alias() {
+        let mut tokens = Lexer::new("type Pair<T> = (T, T)")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Ok(TypeAlias {
+                name: Id("Pair".into()),
+                type_params: vec![Id("T".into())],
+                type_name: TypeName::Tuple(vec![
+                    TypeName::Literal("T".into()),
+                    TypeName::Literal("T".into())
+                ])
+            }
+            .into()),
+            TypeAlias::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn parse_alias_without_type_fails() {
+        let mut tokens = Lexer::new("type Meters = ;")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert_eq!(
+            Err(ParseError {
+                message: "could not parse type name".into(),
+                position: Some(Position::new(12, 1)),
+                notes: vec![Note {
+                    message: "expected a type after `=`".into(),
+                    position: Position::new(12, 1),
+                }],
+            }),
+            TypeAlias::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn parse_non_alias_fails() {
+        let mut tokens = Lexer::new("let x = 1;")
+            .lex()
+            .expect("something is wrong")
+            .into();
+
+        assert!(TypeAlias::parse(&mut tokens).is_err());
+    }
+}
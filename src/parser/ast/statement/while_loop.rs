@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    lexer::{Token, Tokens},
+    lazy_comb,
+    lexer::{Terminal, Token, Tokens},
     parser::{
         ast::{AstNode, Block, Expression},
         combinators::Comb,
@@ -7,37 +10,39 @@ use crate::{
     },
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WhileLoop {
     pub condition: Expression,
     pub block: Block,
 }
 
+impl WhileLoop {
+    /// `while (condition) { ... }`
+    pub(crate) fn grammar() -> &'static Comb<'static, Token, Terminal, AstNode> {
+        lazy_comb!(Comb::WHILE_KEYWORD >> Comb::LPAREN >> Comb::EXPR >> Comb::RPAREN >> Comb::BLOCK)
+    }
+}
+
 impl FromTokens<Token> for WhileLoop {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
-        let matcher =
-            Comb::WHILE_KEYWORD >> Comb::LPAREN >> Comb::EXPR >> Comb::RPAREN >> Comb::BLOCK;
+        let matcher = Self::grammar();
 
-        let result = matcher.parse(tokens)?;
+        let mut result = matcher.parse(tokens)?.into_iter();
 
-        let Some(AstNode::Expression(condition)) = result.first() else {
+        let Some(AstNode::Expression(condition)) = result.next() else {
             unreachable!()
         };
 
-        let Some(AstNode::Block(block)) = result.get(1) else {
+        let Some(AstNode::Block(block)) = result.next() else {
             unreachable!()
         };
 
-        Ok(WhileLoop {
-            condition: condition.clone(),
-            block: block.clone(),
-        }
-        .into())
+        Ok(WhileLoop { condition, block }.into())
     }
 }
 
 impl From<WhileLoop> for AstNode {
     fn from(value: WhileLoop) -> Self {
-        AstNode::WhileLoop(value)
+        AstNode::WhileLoop(Box::new(value))
     }
 }
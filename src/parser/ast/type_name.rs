@@ -1,3 +1,10 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::lazy_comb;
+use crate::lexer::Position;
+use crate::lexer::Symbol;
 use crate::lexer::Token;
 use crate::lexer::Tokens;
 use crate::parser::combinators::Comb;
@@ -6,20 +13,49 @@ use crate::parser::ParseError;
 
 use super::AstNode;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TypeName {
-    Literal(String),
+    /// `void` (or `()`), the type of expressions without a meaningful value.
+    Void,
+    /// A named type. Names are not resolved while parsing, so it might even refer to the type
+    /// which is currently being declared (e.g., `next: Node?` within `struct Node`).
+    Literal(Symbol),
+    /// A type with generic arguments, e.g., `Map<str, List<i32>>`.
+    Generic {
+        name: Symbol,
+        args: Vec<TypeName>,
+    },
+    /// `(params) -> return_type`, e.g., `(i32) -> void`. Without the arrow, the parenthesized
+    /// list would be a tuple.
     Fn {
         params: Vec<TypeName>,
         return_type: Box<TypeName>,
     },
     Tuple(Vec<TypeName>),
     Array(Box<TypeName>),
+    /// A value which might be absent, e.g., `Point?`.
+    Optional(Box<TypeName>),
     Reference(Box<TypeName>),
+    /// An associated type of another type, e.g., `Self::Item`.
+    Associated {
+        base: Box<TypeName>,
+        name: Symbol,
+    },
 }
 
 impl FromTokens<Token> for TypeName {
     fn parse(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
+        let AstNode::TypeName(element) = Self::parse_element(tokens)? else {
+            unreachable!()
+        };
+
+        Ok(Self::parse_suffixes(element, tokens).into())
+    }
+}
+
+impl TypeName {
+    /// Parse a type without any trailing `[]` or `?`.
+    fn parse_element(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         if let Ok(type_name) = Self::parse_literal(tokens) {
             return Ok(type_name);
         };
@@ -28,8 +64,12 @@ impl FromTokens<Token> for TypeName {
             return Ok(function);
         };
 
-        if let Ok(tuple) = Self::parse_tuple(tokens) {
-            return Ok(tuple);
+        if let Ok(AstNode::TypeName(tuple)) = Self::parse_tuple(tokens) {
+            return Ok(match tuple {
+                TypeName::Tuple(elements) if elements.is_empty() => TypeName::Void,
+                tuple => tuple,
+            }
+            .into());
         };
 
         if let Ok(array) = Self::parse_array(tokens) {
@@ -43,48 +83,153 @@ impl FromTokens<Token> for TypeName {
         Err(ParseError {
             message: "could not parse type name".into(),
             position: None,
+            notes: vec![],
         })
     }
-}
 
-impl TypeName {
+    /// Parse the (possibly multiple) `[]` and `?` following an element type from left to right,
+    /// e.g., `i32[][]` is an array of arrays of `i32` (just like `[[i32]]`) and `i32?[]` is an
+    /// array of optional `i32`.
+    fn parse_suffixes(mut element: TypeName, tokens: &mut Tokens<Token>) -> TypeName {
+        loop {
+            match (tokens.peek(), tokens.peek_at(1)) {
+                (Some(Token::LBracket { .. }), Some(Token::RBracket { .. })) => {
+                    tokens.next();
+                    tokens.next();
+
+                    element = TypeName::Array(Box::new(element));
+                }
+                (Some(Token::QuestionMark { .. }), _) => {
+                    tokens.next();
+
+                    element = TypeName::Optional(Box::new(element));
+                }
+                _ => return element,
+            }
+        }
+    }
     fn parse_literal(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let index = tokens.get_index();
 
-        let matcher = !Comb::ID;
+        let matcher = lazy_comb!(!Comb::ID);
 
-        let result = matcher.parse(tokens).map_err(|e| {
+        let result = matcher.parse(tokens).inspect_err(|_| {
             tokens.set_index(index);
-            e
         })?;
 
         let Some(AstNode::Id(type_name)) = result.first() else {
             return Err(ParseError {
                 message: "Could not parse type literal".into(),
                 position: None,
+                notes: vec![],
             });
         };
 
-        Ok(TypeName::Literal(type_name.0.clone()).into())
+        let name = type_name.0.clone();
+
+        if name.as_str() == "void" {
+            return Ok(TypeName::Void.into());
+        }
+
+        if !matches!(tokens.peek(), Some(Token::LessThan { .. })) {
+            return Ok(Self::parse_associated(TypeName::Literal(name), tokens).into());
+        }
+
+        let args = Self::parse_generic_arguments(tokens).inspect_err(|_| {
+            tokens.set_index(index);
+        })?;
+
+        Ok(TypeName::Generic { name, args }.into())
+    }
+
+    /// Parse the (possibly nested) associated types of `base`, e.g., the `::Item` of `Self::Item`.
+    fn parse_associated(mut base: TypeName, tokens: &mut Tokens<Token>) -> TypeName {
+        while let (Some(Token::DoubleColon { .. }), Some(Token::Id { value, .. })) =
+            (tokens.peek(), tokens.peek_at(1))
+        {
+            let name = value.clone();
+            tokens.next();
+            tokens.next();
+
+            base = TypeName::Associated {
+                base: Box::new(base),
+                name,
+            };
+        }
+
+        base
+    }
+
+    /// Parse `<T, ...>`. Since the lexer emits a closing `>>` of nested generics as a single
+    /// token, it is split into two `>` here.
+    pub(crate) fn parse_generic_arguments(
+        tokens: &mut Tokens<Token>,
+    ) -> Result<Vec<TypeName>, ParseError> {
+        Comb::LESS_THAN.parse(tokens)?;
+
+        let mut args = vec![];
+
+        loop {
+            let Some(AstNode::TypeName(arg)) = Comb::TYPE_NAME.parse(tokens)?.into_iter().next()
+            else {
+                unreachable!()
+            };
+            args.push(arg);
+
+            match tokens.peek() {
+                Some(Token::Comma { .. }) => {
+                    tokens.next();
+                }
+                Some(Token::GreaterThan { .. }) => {
+                    tokens.next();
+                    return Ok(args);
+                }
+                Some(Token::ShiftRight { position }) => {
+                    let Position { offset, .. } = *position;
+                    tokens.split_next(
+                        Token::GreaterThan {
+                            position: Position { offset, length: 1 },
+                        },
+                        Token::GreaterThan {
+                            position: Position {
+                                offset: offset + 1,
+                                length: 1,
+                            },
+                        },
+                    );
+                    tokens.next();
+                    return Ok(args);
+                }
+                Some(token) => {
+                    return Err(ParseError {
+                        message: format!(
+                            "Unexpected {token:?} while trying to parse generic arguments"
+                        ),
+                        position: Some(token.position()),
+                        notes: vec![],
+                    })
+                }
+                None => return Err(ParseError::eof("generic arguments")),
+            }
+        }
     }
 
     fn parse_tuple(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let index = tokens.get_index();
 
-        let matcher = Comb::LPAREN >> (Comb::TYPE_NAME % Comb::COMMA) >> Comb::RPAREN;
+        let matcher = lazy_comb!(Comb::LPAREN >> (Comb::TYPE_NAME % Comb::COMMA) >> Comb::RPAREN);
 
-        let result = matcher.parse(tokens).map_err(|e| {
+        let result = matcher.parse(tokens).inspect_err(|_| {
             tokens.set_index(index);
-            e
         })?;
 
         let mut elems = vec![];
 
-        for type_name in &result {
+        for type_name in result {
             let AstNode::TypeName(type_name) = type_name else {
                 unreachable!()
             };
-            elems.push(type_name.clone());
+            elems.push(type_name);
         }
 
         Ok(TypeName::Tuple(elems).into())
@@ -97,20 +242,19 @@ impl TypeName {
             unreachable!()
         };
 
-        let matcher = Comb::SMALL_RIGHT_ARROW >> Comb::TYPE_NAME;
+        let matcher = lazy_comb!(Comb::SMALL_RIGHT_ARROW >> Comb::TYPE_NAME);
 
-        let result = matcher.parse(tokens).map_err(|e| {
+        let result = matcher.parse(tokens).inspect_err(|_| {
             tokens.set_index(index);
-            e
         })?;
 
-        let Some(AstNode::TypeName(type_name)) = result.first() else {
+        let Some(AstNode::TypeName(type_name)) = result.into_iter().next() else {
             unreachable!()
         };
 
         Ok(TypeName::Fn {
             params,
-            return_type: Box::new(type_name.clone()),
+            return_type: Box::new(type_name),
         }
         .into())
     }
@@ -118,35 +262,74 @@ impl TypeName {
     fn parse_array(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let index = tokens.get_index();
 
-        let matcher = Comb::LBRACKET >> Comb::TYPE_NAME >> Comb::RBRACKET;
+        let matcher = lazy_comb!(Comb::LBRACKET >> Comb::TYPE_NAME >> Comb::RBRACKET);
 
-        let result = matcher.parse(tokens).map_err(|e| {
+        let result = matcher.parse(tokens).inspect_err(|_| {
             tokens.set_index(index);
-            e
         })?;
 
-        let Some(AstNode::TypeName(type_name)) = result.first() else {
+        let Some(AstNode::TypeName(type_name)) = result.into_iter().next() else {
             unreachable!()
         };
 
-        Ok(TypeName::Array(Box::new(type_name.clone())).into())
+        Ok(TypeName::Array(Box::new(type_name)).into())
     }
 
     fn parse_reference(tokens: &mut Tokens<Token>) -> Result<AstNode, ParseError> {
         let index = tokens.get_index();
 
-        let matcher = Comb::AMPERSAND >> Comb::TYPE_NAME;
+        let matcher = lazy_comb!(Comb::AMPERSAND >> Comb::TYPE_NAME);
 
-        let result = matcher.parse(tokens).map_err(|e| {
+        let result = matcher.parse(tokens).inspect_err(|_| {
             tokens.set_index(index);
-            e
         })?;
 
-        let Some(AstNode::TypeName(type_name)) = result.first() else {
+        let Some(AstNode::TypeName(type_name)) = result.into_iter().next() else {
             unreachable!()
         };
 
-        Ok(TypeName::Reference(Box::new(type_name.clone())).into())
+        Ok(TypeName::Reference(Box::new(type_name)).into())
+    }
+}
+
+impl Display for TypeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn list(f: &mut std::fmt::Formatter<'_>, type_names: &[TypeName]) -> std::fmt::Result {
+            for (index, type_name) in type_names.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{type_name}")?;
+            }
+            Ok(())
+        }
+
+        match self {
+            TypeName::Void => f.write_str("void"),
+            TypeName::Literal(name) => f.write_str(name.as_str()),
+            TypeName::Generic { name, args } => {
+                write!(f, "{name}<")?;
+                list(f, args)?;
+                f.write_str(">")
+            }
+            TypeName::Fn {
+                params,
+                return_type,
+            } => {
+                f.write_str("(")?;
+                list(f, params)?;
+                write!(f, ") -> {return_type}")
+            }
+            TypeName::Tuple(elements) => {
+                f.write_str("(")?;
+                list(f, elements)?;
+                f.write_str(")")
+            }
+            TypeName::Array(element) => write!(f, "[{element}]"),
+            TypeName::Optional(inner) => write!(f, "{inner}?"),
+            TypeName::Reference(inner) => write!(f, "&{inner}"),
+            TypeName::Associated { base, name } => write!(f, "{base}::{name}"),
+        }
     }
 }
 
@@ -158,7 +341,10 @@ impl From<TypeName> for AstNode {
 
 #[cfg(test)]
 mod tests {
-    use crate::{lexer::Lexer, parser::FromTokens};
+    use crate::{
+        lexer::{Lexer, Token},
+        parser::{ast::AstNode, FromTokens},
+    };
 
     use super::TypeName;
 
@@ -205,6 +391,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_function_with_params() {
+        let mut tokens = Lexer::new("(i32, i32) -> i32 (i32) -> (i32) -> void")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        assert_eq!(
+            Ok(TypeName::Fn {
+                params: vec![
+                    TypeName::Literal("i32".into()),
+                    TypeName::Literal("i32".into())
+                ],
+                return_type: Box::new(TypeName::Literal("i32".into()))
+            }
+            .into()),
+            TypeName::parse(&mut tokens)
+        );
+        // the arrow is right associative, i.e., a function returning a function
+        assert_eq!(
+            Ok(TypeName::Fn {
+                params: vec![TypeName::Literal("i32".into())],
+                return_type: Box::new(TypeName::Fn {
+                    params: vec![TypeName::Literal("i32".into())],
+                    return_type: Box::new(TypeName::Void)
+                })
+            }
+            .into()),
+            TypeName::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_function_needs_arrow() {
+        let mut tokens = Lexer::new("(i32) i32")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        // without an arrow, the parameters are just a tuple
+        assert_eq!(
+            Ok(TypeName::Tuple(vec![TypeName::Literal("i32".into())]).into()),
+            TypeName::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_void() {
+        let mut tokens = Lexer::new("void () () -> void")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        assert_eq!(Ok(TypeName::Void.into()), TypeName::parse(&mut tokens));
+        assert_eq!(Ok(TypeName::Void.into()), TypeName::parse(&mut tokens));
+        assert_eq!(
+            Ok(TypeName::Fn {
+                params: vec![],
+                return_type: Box::new(TypeName::Void)
+            }
+            .into()),
+            TypeName::parse(&mut tokens)
+        );
+    }
+
     #[test]
     fn test_parse_simple_reference() {
         let mut tokens = Lexer::new("&i32")
@@ -260,4 +511,170 @@ mod tests {
             result
         )
     }
+
+    #[test]
+    fn test_parse_generic() {
+        let mut tokens = Lexer::new("List<i32>")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        let result = TypeName::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(TypeName::Generic {
+                name: "List".into(),
+                args: vec![TypeName::Literal("i32".into())]
+            }
+            .into()),
+            result
+        )
+    }
+
+    #[test]
+    fn test_parse_nested_generics_with_shift_right() {
+        let mut tokens = Lexer::new("Map<str, List<Pair<i32, i32>>>")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        let result = TypeName::parse(&mut tokens);
+
+        assert_eq!(
+            Ok(TypeName::Generic {
+                name: "Map".into(),
+                args: vec![
+                    TypeName::Literal("str".into()),
+                    TypeName::Generic {
+                        name: "List".into(),
+                        args: vec![TypeName::Generic {
+                            name: "Pair".into(),
+                            args: vec![TypeName::Literal("i32".into()); 2]
+                        }]
+                    }
+                ]
+            }
+            .into()),
+            result
+        );
+        assert_eq!(None, tokens.peek());
+    }
+
+    #[test]
+    fn test_parse_unclosed_generic_fails() {
+        let mut tokens = Lexer::new("List<i32")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        assert!(TypeName::parse(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_associated() {
+        let mut tokens = Lexer::new("&Self::Iter::Item")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        let result = TypeName::parse(&mut tokens);
+        assert_eq!(
+            Ok(TypeName::Reference(Box::new(TypeName::Associated {
+                base: Box::new(TypeName::Associated {
+                    base: Box::new(TypeName::Literal("Self".into())),
+                    name: "Iter".into()
+                }),
+                name: "Item".into()
+            }))
+            .into()),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_multi_dimensional_array() {
+        let mut tokens = Lexer::new("i32[][] [(i32, bool)][]")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        assert_eq!(
+            Ok(
+                TypeName::Array(Box::new(TypeName::Array(Box::new(TypeName::Literal(
+                    "i32".into()
+                )))))
+                .into()
+            ),
+            TypeName::parse(&mut tokens)
+        );
+        assert_eq!(
+            Ok(
+                TypeName::Array(Box::new(TypeName::Array(Box::new(TypeName::Tuple(vec![
+                    TypeName::Literal("i32".into()),
+                    TypeName::Literal("bool".into())
+                ])))))
+                .into()
+            ),
+            TypeName::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_optional() {
+        let mut tokens = Lexer::new("Point? i32?[] (i32, bool)[]?")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        let optional = |type_name| TypeName::Optional(Box::new(type_name));
+        let array = |type_name| TypeName::Array(Box::new(type_name));
+
+        assert_eq!(
+            Ok(optional(TypeName::Literal("Point".into())).into()),
+            TypeName::parse(&mut tokens)
+        );
+        assert_eq!(
+            Ok(array(optional(TypeName::Literal("i32".into()))).into()),
+            TypeName::parse(&mut tokens)
+        );
+        assert_eq!(
+            Ok(optional(array(TypeName::Tuple(vec![
+                TypeName::Literal("i32".into()),
+                TypeName::Literal("bool".into())
+            ])))
+            .into()),
+            TypeName::parse(&mut tokens)
+        );
+    }
+
+    #[test]
+    fn test_parse_array_suffix_before_index() {
+        let mut tokens = Lexer::new("i32[0]")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        assert_eq!(
+            Ok(TypeName::Literal("i32".into()).into()),
+            TypeName::parse(&mut tokens)
+        );
+        assert!(matches!(tokens.peek(), Some(Token::LBracket { .. })));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut tokens = Lexer::new("(&i32, [Map<str, (i32, Self::Item)>], void) -> bool?")
+            .lex()
+            .expect("something went wrong")
+            .into();
+
+        let Ok(AstNode::TypeName(type_name)) = TypeName::parse(&mut tokens) else {
+            panic!("should parse");
+        };
+
+        assert_eq!(
+            "(&i32, [Map<str, (i32, Self::Item)>], void) -> bool?",
+            type_name.to_string()
+        );
+    }
 }
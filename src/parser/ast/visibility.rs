@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::lexer::{Token, Tokens};
+
+/// Whether an item (e.g., a function or a struct field) is accessible from outside of its module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Visibility {
+    #[default]
+    Private,
+    /// Marked with `pub`.
+    Public,
+}
+
+impl Visibility {
+    /// Consume a leading `pub` (if there is one).
+    pub(crate) fn parse(tokens: &mut Tokens<Token>) -> Visibility {
+        match tokens.peek() {
+            Some(Token::PubKeyword { .. }) => {
+                tokens.next();
+                Visibility::Public
+            }
+            _ => Visibility::Private,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_visibility() {
+        let mut tokens = Lexer::new("pub foo").lex().expect("should work").into();
+
+        assert_eq!(Visibility::Public, Visibility::parse(&mut tokens));
+        assert_eq!(Visibility::Private, Visibility::parse(&mut tokens));
+        assert!(matches!(tokens.peek(), Some(Token::Id { .. })));
+    }
+}
@@ -1,15 +1,17 @@
 use std::ops::{BitOr, BitXor, Not, Rem, Shr};
 
-use crate::lexer::{Terminal, Token, Tokens};
+use crate::lexer::{Positioned, Terminal, Token, Tokens, Trivia};
 
 use super::{
     ast::{
-        Array, Assignment, AstNode, Block, Constant, Declaration, Expression, Function, Id, If,
-        Initialisation, Lambda, Num, Parameter, Statement, StructDeclaration,
-        StructFieldDeclaration, StructFieldInitialisation, StructInitialisation, TypeName,
+        Array, Asm, Assignment, AstNode, Block, Bool, Comprehension, Conditional, Constant,
+        Declaration, EnumDeclaration, Expression, ExternBlock, Function, Id, If, ImplBlock, Import,
+        InfiniteLoop, Initialisation, Lambda, Match, MatchArm, Module, Num, Parameter, Pattern,
+        Property, RegexLiteral, Statement, StructDeclaration, StructFieldDeclaration,
+        StructFieldInitialisation, StructInitialisation, TraitDeclaration, TypeAlias, TypeName,
         WhileLoop,
     },
-    FromTokens, ParseError,
+    stats, FromTokens, ParseError,
 };
 
 #[derive(Clone)]
@@ -17,7 +19,9 @@ pub enum Comb<'a, Tok, Term, Node> {
     /// Combinator for parsing a non terminal symbol. Therefore, we utilize the parsing function of
     /// this respective non-terminal.
     Node {
-        parser: &'a dyn Fn(&mut Tokens<Tok>) -> Result<Node, ParseError>,
+        /// Name of the non-terminal (used for instrumentation).
+        name: &'static str,
+        parser: &'a (dyn Fn(&mut Tokens<Tok>) -> Result<Node, ParseError> + Sync),
     },
     /// Combinator for matching a terminal.
     Terminal { token: Term },
@@ -102,8 +106,9 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Node { .. } => f
+            Self::Node { name, .. } => f
                 .debug_struct("Node")
+                .field("name", name)
                 .field("parser", &"() -> {}".to_string())
                 .finish(),
             Self::Terminal { token } => f.debug_struct("Single").field("token", token).finish(),
@@ -142,10 +147,28 @@ macro_rules! terminal_comb {
 macro_rules! node_comb {
     ($name:ident, $struct:ident) => {
         pub const $name: Comb<'static, Token, Terminal, AstNode> = Comb::Node {
+            name: stringify!($struct),
             parser: &$struct::parse,
         };
     };
 }
+/// Build a combinator (tree) only once and get a static reference to it. Use this for combinators
+/// which are used in a parsing function, so they are not constructed on every invocation.
+#[macro_export]
+macro_rules! lazy_comb {
+    ($comb:expr) => {{
+        static COMB: std::sync::LazyLock<
+            $crate::parser::combinators::Comb<
+                'static,
+                $crate::lexer::Token,
+                $crate::lexer::Terminal,
+                $crate::parser::ast::AstNode,
+            >,
+        > = std::sync::LazyLock::new(|| $comb);
+        &*COMB
+    }};
+}
+
 impl<'a> Comb<'a, Token, Terminal, AstNode> {
     terminal_comb!(LET, Let);
 
@@ -155,6 +178,16 @@ impl<'a> Comb<'a, Token, Terminal, AstNode> {
 
     terminal_comb!(ASSIGN, Assign);
 
+    terminal_comb!(PLUS_ASSIGN, PlusAssign);
+
+    terminal_comb!(MINUS_ASSIGN, MinusAssign);
+
+    terminal_comb!(TIMES_ASSIGN, TimesAssign);
+
+    terminal_comb!(SLASH_ASSIGN, SlashAssign);
+
+    terminal_comb!(PERCENT_ASSIGN, PercentAssign);
+
     terminal_comb!(LPAREN, LParen);
 
     terminal_comb!(RPAREN, RParen);
@@ -175,12 +208,32 @@ impl<'a> Comb<'a, Token, Terminal, AstNode> {
 
     terminal_comb!(WHILE_KEYWORD, WhileKeyword);
 
+    terminal_comb!(LOOP_KEYWORD, LoopKeyword);
+
     terminal_comb!(RETURN_KEYWORD, ReturnKeyword);
 
+    terminal_comb!(YIELD_KEYWORD, YieldKeyword);
+
+    terminal_comb!(BREAK_KEYWORD, BreakKeyword);
+
+    terminal_comb!(CONTINUE_KEYWORD, ContinueKeyword);
+
     terminal_comb!(MINUS, Minus);
 
+    terminal_comb!(TIMES, Times);
+
+    terminal_comb!(LESS_THAN, LessThan);
+
+    terminal_comb!(GREATER_THAN, GreaterThan);
+
     terminal_comb!(EXCLAMATION_MARK, ExclamationMark);
 
+    terminal_comb!(QUESTION_MARK, QuestionMark);
+
+    terminal_comb!(TILDE, Tilde);
+
+    terminal_comb!(AS_KEYWORD, AsKeyword);
+
     terminal_comb!(COLON, Colon);
 
     terminal_comb!(COMMA, Comma);
@@ -201,12 +254,49 @@ impl<'a> Comb<'a, Token, Terminal, AstNode> {
 
     terminal_comb!(STRUCT_KEYWORD, StructKeyword);
 
+    terminal_comb!(ENUM_KEYWORD, EnumKeyword);
+
+    terminal_comb!(USE_KEYWORD, UseKeyword);
+
+    terminal_comb!(DOUBLE_COLON, DoubleColon);
+
+    terminal_comb!(MATCH_KEYWORD, MatchKeyword);
+
+    terminal_comb!(IMPL_KEYWORD, ImplKeyword);
+
+    terminal_comb!(TRAIT_KEYWORD, TraitKeyword);
+
+    terminal_comb!(EXTERN_KEYWORD, ExternKeyword);
+
+    terminal_comb!(MOD_KEYWORD, ModKeyword);
+
+    terminal_comb!(COMPTIME_KEYWORD, ComptimeKeyword);
+
+    terminal_comb!(HASH_IF, HashIf);
+
+    terminal_comb!(HASH_ELSE, HashElse);
+
+    terminal_comb!(FOR_KEYWORD, ForKeyword);
+
+    terminal_comb!(IN_KEYWORD, InKeyword);
+
     node_comb!(ID, Id);
 
     node_comb!(NUM, Num);
 
+    node_comb!(BOOL, Bool);
+
+    node_comb!(REGEX_LITERAL, RegexLiteral);
+
     node_comb!(EXPR, Expression);
 
+    /// An element of an array literal or an argument of a call, i.e., an expression which might
+    /// be spread (e.g., `...rest`).
+    pub const ELEMENT: Comb<'static, Token, Terminal, AstNode> = Comb::Node {
+        name: "Element",
+        parser: &Expression::parse_element,
+    };
+
     node_comb!(STATEMENT, Statement);
 
     node_comb!(INITIALISATION, Initialisation);
@@ -221,6 +311,16 @@ impl<'a> Comb<'a, Token, Terminal, AstNode> {
 
     node_comb!(WHILE_LOOP, WhileLoop);
 
+    node_comb!(INFINITE_LOOP, InfiniteLoop);
+
+    node_comb!(CONDITIONAL, Conditional);
+
+    node_comb!(IMPL_BLOCK, ImplBlock);
+
+    node_comb!(TRAIT_DECLARATION, TraitDeclaration);
+
+    node_comb!(MODULE, Module);
+
     node_comb!(BLOCK, Block);
 
     node_comb!(ARRAY, Array);
@@ -235,65 +335,102 @@ impl<'a> Comb<'a, Token, Terminal, AstNode> {
 
     node_comb!(STRUCT_DECLARATION, StructDeclaration);
 
+    node_comb!(ENUM_DECLARATION, EnumDeclaration);
+
+    node_comb!(TYPE_ALIAS, TypeAlias);
+
+    node_comb!(EXTERN_BLOCK, ExternBlock);
+
     node_comb!(STRUCT_FIELD_DECLARATION, StructFieldDeclaration);
 
     node_comb!(STRUCT_INITILISATION, StructInitialisation);
 
     node_comb!(STRUCT_FIELD_INITIALISATION, StructFieldInitialisation);
+
+    node_comb!(IMPORT, Import);
+
+    node_comb!(PATTERN, Pattern);
+
+    node_comb!(MATCH, Match);
+
+    node_comb!(MATCH_ARM, MatchArm);
+
+    node_comb!(PROPERTY, Property);
+
+    node_comb!(COMPREHENSION, Comprehension);
+
+    node_comb!(ASM, Asm);
 }
 
 impl<'a, Tok, Term, Node> Comb<'a, Tok, Term, Node>
 where
-    Tok: Clone + std::fmt::Debug,
+    Tok: std::fmt::Debug + Trivia + Positioned,
     Term: PartialEq<Tok> + std::fmt::Debug,
 {
     pub fn parse(&self, tokens: &mut Tokens<Tok>) -> Result<Vec<Node>, ParseError> {
         let mut matched = vec![];
+        self.parse_into(tokens, &mut matched)?;
+        Ok(matched)
+    }
+
+    /// Parse the tokens and append all matched nodes to `matched`. In contrast to [`Comb::parse`],
+    /// this does not allocate a new vector for every (nested) combinator.
+    ///
+    /// Note: On error, `matched` might contain nodes of the partial match.
+    pub fn parse_into(
+        &self,
+        tokens: &mut Tokens<Tok>,
+        matched: &mut Vec<Node>,
+    ) -> Result<(), ParseError> {
         match self {
             Comb::Terminal { token } => {
                 let Some(t) = tokens.next() else {
                     return Err(ParseError {
                         message: "Reached EOF!".into(),
                         position: None,
+                        notes: vec![],
                     });
                 };
 
                 // try to parse the terminal
-                if *token != t {
+                if *token != *t {
                     return Err(ParseError {
                         message: format!("Unexpected {:?} while trying to parse {:?}", t, token),
-                        position: None,
+                        position: Some(t.position()),
+                        notes: vec![],
                     });
                 }
             }
             Comb::Sequence { current, next } => {
-                let mut current_matches = current.parse(tokens)?;
-                matched.append(&mut current_matches);
-
-                let mut next_matches = next.parse(tokens)?;
-                matched.append(&mut next_matches);
+                current.parse_into(tokens, matched)?;
+                next.parse_into(tokens, matched)?;
             }
             Comb::Either { left, right } => {
                 let current_index = tokens.get_index();
+                let current_len = matched.len();
 
-                if let Ok(mut left_matches) = left.parse(tokens) {
-                    matched.append(&mut left_matches);
-                } else {
+                if left.parse_into(tokens, matched).is_err() {
+                    if tokens.get_index() != current_index {
+                        stats::backtrack();
+                    }
                     tokens.set_index(current_index);
-                    let mut right_matches = right.parse(tokens)?;
-                    matched.append(&mut right_matches);
+                    matched.truncate(current_len);
+                    right.parse_into(tokens, matched)?;
                 }
             }
-            Comb::Node { parser } => {
-                let matches = parser(tokens)?;
-                matched.push(matches);
+            Comb::Node { name, parser } => {
+                matched.push(stats::rule(name, || parser(tokens))?);
             }
             Comb::Optional { inner } => {
                 let current_index = tokens.get_index();
-                if let Ok(mut result) = inner.parse(tokens) {
-                    matched.append(&mut result);
-                } else {
+                let current_len = matched.len();
+
+                if inner.parse_into(tokens, matched).is_err() {
+                    if tokens.get_index() != current_index {
+                        stats::backtrack();
+                    }
                     tokens.set_index(current_index);
+                    matched.truncate(current_len);
                 }
             }
             Comb::Repitition { inner, amount } => {
@@ -301,22 +438,26 @@ where
                 if let Some(amount) = amount {
                     // match exactly the specified amount of tokens
                     for _ in 0..*amount {
-                        let mut result = inner.parse(tokens)?;
-                        matched.append(&mut result);
+                        inner.parse_into(tokens, matched)?;
                     }
                 } else {
                     // match an arbitrary amount of tokens
                     let mut current_index = tokens.get_index();
-                    while let Ok(mut result) = inner.parse(tokens) {
-                        matched.append(&mut result);
+                    let mut current_len = matched.len();
+                    while inner.parse_into(tokens, matched).is_ok() {
                         current_index = tokens.get_index();
+                        current_len = matched.len();
+                    }
+                    if tokens.get_index() != current_index {
+                        stats::backtrack();
                     }
                     tokens.set_index(current_index);
+                    matched.truncate(current_len);
                 }
             }
         }
 
-        Ok(matched)
+        Ok(())
     }
 }
 
@@ -387,6 +528,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::lexer::Position;
+
     use super::*;
 
     #[test]
@@ -472,7 +615,10 @@ mod tests {
     #[test]
     fn test_parse_optional_matching_terminal() {
         let a = !Comb::LET;
-        let mut tokens = vec![Token::Let { position: 0 }].into();
+        let mut tokens = vec![Token::Let {
+            position: Position::default(),
+        }]
+        .into();
         let result = a.parse(&mut tokens);
 
         assert_eq!(Ok(vec![]), result);
@@ -482,7 +628,10 @@ mod tests {
     #[test]
     fn test_parse_optional_not_matching_terminal() {
         let a = !Comb::LET;
-        let mut tokens = vec![Token::Assign { position: 0 }].into();
+        let mut tokens = vec![Token::Assign {
+            position: Position::default(),
+        }]
+        .into();
         let result = a.parse(&mut tokens);
 
         assert_eq!(Ok(vec![]), result);
@@ -494,7 +643,7 @@ mod tests {
         let a = !Comb::NUM;
         let mut tokens = vec![Token::Integer {
             value: 42,
-            position: 0,
+            position: Position::default(),
         }]
         .into();
         let result = a.parse(&mut tokens);
@@ -508,7 +657,7 @@ mod tests {
         let a = !Comb::NUM;
         let mut tokens = vec![Token::Id {
             value: "some_id".into(),
-            position: 0,
+            position: Position::default(),
         }]
         .into();
         let result = a.parse(&mut tokens);
@@ -539,11 +688,21 @@ mod tests {
     fn test_parse_repition_simple_matching() {
         let a = Comb::LET ^ 5;
         let mut tokens = vec![
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
         ]
         .into();
         let result = a.parse(&mut tokens);
@@ -555,11 +714,21 @@ mod tests {
     fn test_parse_repition_simple_not_matching() {
         let a = Comb::LET ^ 5;
         let mut tokens = vec![
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
-            Token::Assign { position: 0 },
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Assign {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
         ]
         .into();
         let result = a.parse(&mut tokens);
@@ -570,11 +739,21 @@ mod tests {
     fn test_parse_repition_simple_wildcard() {
         let a = Comb::LET ^ ();
         let mut tokens = vec![
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
-            Token::Assign { position: 0 },
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Assign {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
         ]
         .into();
         let result = a.parse(&mut tokens);
@@ -588,15 +767,15 @@ mod tests {
         let mut tokens = vec![
             Token::Integer {
                 value: 42,
-                position: 0,
+                position: Position::default(),
             },
             Token::Integer {
                 value: 1337,
-                position: 0,
+                position: Position::default(),
             },
             Token::Integer {
                 value: 17,
-                position: 0,
+                position: Position::default(),
             },
         ]
         .into();
@@ -619,18 +798,22 @@ mod tests {
         let mut tokens = vec![
             Token::Integer {
                 value: 42,
-                position: 0,
+                position: Position::default(),
             },
             Token::Integer {
                 value: 1337,
-                position: 0,
+                position: Position::default(),
             },
             Token::Integer {
                 value: 17,
-                position: 0,
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Let {
+                position: Position::default(),
             },
-            Token::Let { position: 0 },
-            Token::Let { position: 0 },
         ]
         .into();
         let result = a.parse(&mut tokens);
@@ -649,7 +832,10 @@ mod tests {
     #[test]
     fn test_parse_terminal_simple() {
         let a = Comb::LET;
-        let mut tokens = vec![Token::Let { position: 0 }].into();
+        let mut tokens = vec![Token::Let {
+            position: Position::default(),
+        }]
+        .into();
         let result = a.parse(&mut tokens);
 
         assert_eq!(Ok(vec![]), result);
@@ -661,7 +847,7 @@ mod tests {
         let a = Comb::NUM;
         let mut tokens = vec![Token::Integer {
             value: 42,
-            position: 0,
+            position: Position::default(),
         }]
         .into();
         let result = a.parse(&mut tokens);
@@ -674,10 +860,12 @@ mod tests {
     fn test_parse_shr() {
         let matcher = Comb::LET >> Comb::NUM;
         let mut tokens = vec![
-            Token::Let { position: 0 },
+            Token::Let {
+                position: Position::default(),
+            },
             Token::Integer {
                 value: 42,
-                position: 0,
+                position: Position::default(),
             },
         ]
         .into();
@@ -691,7 +879,7 @@ mod tests {
         let matcher = Comb::ID | Comb::NUM;
         let mut tokens = vec![Token::Integer {
             value: 42,
-            position: 0,
+            position: Position::default(),
         }]
         .into();
         let result = matcher.parse(&mut tokens);
@@ -701,7 +889,7 @@ mod tests {
 
         let mut tokens = vec![Token::Id {
             value: "some_id".into(),
-            position: 0,
+            position: Position::default(),
         }]
         .into();
         let result = matcher.parse(&mut tokens);
@@ -714,12 +902,76 @@ mod tests {
         let a = Comb::LET;
         let mut tokens = vec![Token::Integer {
             value: 42,
-            position: 0,
+            position: Position::new(3, 2),
         }]
         .into();
         let result = a.parse(&mut tokens);
 
-        assert!(result.is_err());
+        assert_eq!(
+            Some(Position::new(3, 2)),
+            result.expect_err("should fail").position
+        );
         assert_eq!(tokens.get_index(), 1);
     }
+
+    #[test]
+    fn test_parse_into_appends() {
+        let matcher = Comb::LET >> Comb::NUM;
+        let mut tokens = vec![
+            Token::Let {
+                position: Position::default(),
+            },
+            Token::Integer {
+                value: 42,
+                position: Position::default(),
+            },
+        ]
+        .into();
+        let mut matched = vec![AstNode::Id(Id("before".into()))];
+        let result = matcher.parse_into(&mut tokens, &mut matched);
+
+        assert_eq!(Ok(()), result);
+        assert_eq!(
+            vec![
+                AstNode::Id(Id("before".into())),
+                AstNode::Num(Num::Integer(42))
+            ],
+            matched
+        );
+    }
+
+    #[test]
+    fn test_parse_bitor_discards_partial_match() {
+        let matcher = (Comb::NUM >> Comb::SEMI) | (Comb::NUM >> Comb::ASSIGN);
+        let mut tokens = vec![
+            Token::Integer {
+                value: 42,
+                position: Position::default(),
+            },
+            Token::Assign {
+                position: Position::default(),
+            },
+        ]
+        .into();
+        let result = matcher.parse(&mut tokens);
+
+        assert_eq!(Ok(vec![AstNode::Num(Num::Integer(42))]), result);
+        assert_eq!(tokens.get_index(), 2);
+    }
+
+    #[test]
+    fn test_lazy_comb_is_built_once() {
+        fn matcher() -> &'static Comb<'static, Token, Terminal, AstNode> {
+            lazy_comb!(Comb::LET >> Comb::SEMI)
+        }
+
+        assert!(std::ptr::eq(matcher(), matcher()));
+        assert_eq!(
+            &Comb::Sequence {
+                current: Box::new(Comb::LET),
+                next: Box::new(Comb::SEMI)
+            },
+            matcher()
+        );
+    }
 }
@@ -0,0 +1,243 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::lexer::{Position, Token};
+
+/// Edition of the language. Newer editions may reserve additional keywords, so code written for
+/// an older edition keeps working (with deprecation warnings) when it is parsed in that edition.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum LanguageEdition {
+    Edition2023,
+    #[default]
+    Edition2024,
+}
+
+/// Keywords which are only reserved since the given edition.
+const INTRODUCED_KEYWORDS: &[(LanguageEdition, &[&str])] = &[(
+    LanguageEdition::Edition2024,
+    &[
+        "match", "for", "in", "impl", "trait", "comptime", "mod", "pub", "as", "loop", "enum",
+        "extern", "yield", "true", "false", "break", "continue", "use",
+    ],
+)];
+
+/// Keywords which are literals (instead of names) since they got reserved, so there is no
+/// sensible rename to suggest.
+const LITERAL_KEYWORDS: &[&str] = &["true", "false"];
+
+/// Usage of syntax which is invalid in a newer edition, together with a suggested rewrite (if
+/// there is one).
+///
+/// The position is only printed by the loader, which knows the line and column of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub message: String,
+    pub position: Position,
+    pub suggestion: Option<String>,
+}
+
+impl Display for Deprecation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "warning: {}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", use `{suggestion}` instead")?;
+        }
+        Ok(())
+    }
+}
+
+impl LanguageEdition {
+    pub const LATEST: LanguageEdition = LanguageEdition::Edition2024;
+
+    /// The edition in which the given keyword got reserved (if it was introduced after the first
+    /// edition).
+    fn introduction_of(keyword: &str) -> Option<LanguageEdition> {
+        INTRODUCED_KEYWORDS
+            .iter()
+            .find(|(_, keywords)| keywords.contains(&keyword))
+            .map(|(edition, _)| *edition)
+    }
+
+    /// Adjust the lexed tokens to this edition: Keywords which are not yet reserved in this
+    /// edition are turned back into identifiers. Since they will not be valid names in newer
+    /// editions, a deprecation is emitted for each of them.
+    pub fn apply(self, mut tokens: Vec<Token>) -> (Vec<Token>, Vec<Deprecation>) {
+        let deprecations = self.apply_to(&mut tokens);
+        (tokens, deprecations)
+    }
+
+    /// Like [`LanguageEdition::apply`], but adjust the tokens in place.
+    pub fn apply_to(self, tokens: &mut [Token]) -> Vec<Deprecation> {
+        let mut deprecations = vec![];
+
+        for token in tokens {
+            let Some(keyword) = token.keyword() else {
+                continue;
+            };
+
+            match Self::introduction_of(keyword) {
+                Some(introduced) if introduced > self => {
+                    let position = token.position();
+                    deprecations.push(match LITERAL_KEYWORDS.contains(&keyword) {
+                        true => Deprecation {
+                            message: format!(
+                                "`{keyword}` is a literal since edition {introduced} and can not \
+                                be used as a name, choose a different name"
+                            ),
+                            position,
+                            suggestion: None,
+                        },
+                        false => Deprecation {
+                            message: format!("`{keyword}` is a keyword since edition {introduced}"),
+                            position,
+                            suggestion: Some(format!("{keyword}_")),
+                        },
+                    });
+
+                    *token = Token::Id {
+                        value: keyword.into(),
+                        position,
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        deprecations
+    }
+}
+
+impl Display for LanguageEdition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LanguageEdition::Edition2023 => "2023",
+            LanguageEdition::Edition2024 => "2024",
+        })
+    }
+}
+
+impl FromStr for LanguageEdition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "2023" => Ok(LanguageEdition::Edition2023),
+            "2024" => Ok(LanguageEdition::Edition2024),
+            _ => Err(format!("unknown edition '{s}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::Lexer;
+
+    use super::*;
+
+    #[test]
+    fn test_old_edition_allows_new_keywords_as_names() {
+        let tokens = Lexer::new("let match = 1;").lex().expect("should work");
+
+        let (tokens, deprecations) = LanguageEdition::Edition2023.apply(tokens);
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [Token::Let { .. }, Token::Id { value, .. }, ..] if value.as_str() == "match"
+        ));
+        assert_eq!(
+            vec![Deprecation {
+                message: "`match` is a keyword since edition 2024".into(),
+                position: Position::new(4, 5),
+                suggestion: Some("match_".into())
+            }],
+            deprecations
+        );
+    }
+
+    #[test]
+    fn test_old_edition_allows_later_keywords_as_names() {
+        for keyword in ["true", "false", "break", "continue", "use"] {
+            let tokens = Lexer::new(&format!("let {keyword} = 1;"))
+                .lex()
+                .expect("should work");
+
+            let (tokens, deprecations) = LanguageEdition::Edition2023.apply(tokens);
+
+            assert!(matches!(
+                tokens.as_slice(),
+                [Token::Let { .. }, Token::Id { value, .. }, ..] if value.as_str() == keyword
+            ));
+            assert_eq!(1, deprecations.len());
+        }
+    }
+
+    #[test]
+    fn test_literal_keywords_have_no_suggestion() {
+        let tokens = Lexer::new("let true = 1;").lex().expect("should work");
+
+        let (_, deprecations) = LanguageEdition::Edition2023.apply(tokens);
+
+        assert_eq!(
+            vec![Deprecation {
+                message: "`true` is a literal since edition 2024 and can not be used as a name, \
+                    choose a different name"
+                    .into(),
+                position: Position::new(4, 4),
+                suggestion: None
+            }],
+            deprecations
+        );
+        assert_eq!(
+            "warning: `true` is a literal since edition 2024 and can not be used as a name, \
+            choose a different name",
+            deprecations[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_deprecation() {
+        let tokens = Lexer::new("let match = 1;").lex().expect("should work");
+
+        let (_, deprecations) = LanguageEdition::Edition2023.apply(tokens);
+
+        assert_eq!(
+            "warning: `match` is a keyword since edition 2024, use `match_` instead",
+            deprecations[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_latest_edition_keeps_keywords() {
+        let tokens = Lexer::new("let match = 1;").lex().expect("should work");
+
+        let (tokens, deprecations) = LanguageEdition::LATEST.apply(tokens);
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [Token::Let { .. }, Token::MatchKeyword { .. }, ..]
+        ));
+        assert!(deprecations.is_empty());
+    }
+
+    #[test]
+    fn test_original_keywords_are_always_reserved() {
+        let tokens = Lexer::new("let let = 1;").lex().expect("should work");
+
+        let (tokens, deprecations) = LanguageEdition::Edition2023.apply(tokens);
+
+        assert!(matches!(
+            tokens.as_slice(),
+            [Token::Let { .. }, Token::Let { .. }, ..]
+        ));
+        assert!(deprecations.is_empty());
+    }
+
+    #[test]
+    fn test_parse_edition() {
+        assert_eq!(Ok(LanguageEdition::Edition2023), "2023".parse());
+        assert!("1999".parse::<LanguageEdition>().is_err());
+    }
+}
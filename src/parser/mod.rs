@@ -1,16 +1,34 @@
-use std::{error::Error, fmt::Display};
+use std::{collections::HashMap, error::Error, fmt::Display};
 
-mod ast;
+pub mod ast;
 pub mod combinators;
+pub mod edition;
+pub mod railroad;
+pub mod stats;
 
-use crate::lexer::{Token, Tokens};
+use serde::{Deserialize, Serialize};
 
-use self::{ast::AstNode, combinators::Comb};
+use crate::lexer::{Position, Span, Symbol, Token, Tokens};
+
+use self::{
+    ast::{AstNode, Statement},
+    combinators::Comb,
+    edition::{Deprecation, LanguageEdition},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseError {
     pub message: String,
-    pub position: Option<usize>,
+    pub position: Option<Position>,
+    /// Further positions related to this error, e.g., the first definition of a duplicate name.
+    pub notes: Vec<Note>,
+}
+
+/// A secondary position attached to a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    pub message: String,
+    pub position: Position,
 }
 
 impl ParseError {
@@ -18,14 +36,39 @@ impl ParseError {
         ParseError {
             message: format!("hit EOF while parsing {item}"),
             position: None,
+            notes: vec![],
+        }
+    }
+}
+
+/// Ensure that no name occurs twice (e.g., within the parameters of a function). Otherwise, an
+/// error pointing at the duplicate (with a note pointing at the first occurrence) is returned.
+pub(crate) fn ensure_unique(
+    kind: &str,
+    names: impl IntoIterator<Item = (Symbol, Position)>,
+) -> Result<(), ParseError> {
+    let mut seen = HashMap::new();
+
+    for (name, position) in names {
+        if let Some(first) = seen.insert(name.clone(), position) {
+            return Err(ParseError {
+                message: format!("duplicate {kind} '{name}'"),
+                position: Some(position),
+                notes: vec![Note {
+                    message: format!("'{name}' is first defined here"),
+                    position: first,
+                }],
+            });
         }
     }
+
+    Ok(())
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(pos) = self.position {
-            f.write_fmt(format_args!("{} ({})", self.message, pos))
+            f.write_fmt(format_args!("{} ({})", self.message, pos.offset))
         } else {
             f.write_str(&self.message)
         }
@@ -38,17 +81,119 @@ pub trait FromTokens<T> {
     fn parse(tokens: &mut Tokens<T>) -> Result<AstNode, ParseError>;
 }
 
-pub fn parse(tokens: &mut Tokens<Token>) -> Result<Vec<AstNode>, Box<dyn Error>> {
+/// Settings which affect how tokens are parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ParseSettings {
+    /// The edition the source is written in.
+    pub edition: LanguageEdition,
+}
+
+/// The top-level statements (along with their spans) of a parse and all usages of syntax, which
+/// is deprecated in newer editions.
+pub type ParsedItems = (Vec<(Statement, Span)>, Vec<Deprecation>);
+
+/// Parse all top-level statements according to the given settings. Deprecations are dropped, use
+/// [`parse_items`] to get them.
+pub fn parse(
+    tokens: &mut Tokens<Token>,
+    settings: ParseSettings,
+) -> Result<Vec<AstNode>, ParseError> {
+    let (statements, _) = parse_items(tokens, settings)?;
+
+    Ok(statements
+        .into_iter()
+        .map(|(statement, _)| statement.into())
+        .collect())
+}
+
+/// Parse all top-level statements according to the given settings and additionally return the
+/// span of every statement (i.e., of all tokens it was parsed from).
+pub fn parse_items(
+    tokens: &mut Tokens<Token>,
+    settings: ParseSettings,
+) -> Result<ParsedItems, ParseError> {
+    let deprecations = settings.edition.apply_to(tokens.rest_mut());
+
     let mut statements = vec![];
 
+    let mut matched = vec![];
+
     let matcher = Comb::STATEMENT;
-    while tokens.peek().is_some() {
-        let result = matcher.parse(tokens)?;
-        let [AstNode::Statement(statement)] = result.as_slice() else {
+    while let Some(position) = tokens.peek_comment().or(tokens.peek()).map(Token::position) {
+        let start = tokens.get_index();
+        // errors without a position are attributed to the start of the failing statement
+        matcher
+            .parse_into(tokens, &mut matched)
+            .map_err(|error| ParseError {
+                position: error.position.or(Some(position)),
+                ..error
+            })?;
+        let Some(AstNode::Statement(statement)) = matched.pop() else {
             unreachable!()
         };
-        statements.push(statement.clone().into());
+        let span = tokens.span_since(start).unwrap_or(position.span());
+        statements.push((statement, span));
     }
 
-    Ok(statements)
+    Ok((statements, deprecations))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{Lexer, Position, Span};
+
+    use super::{
+        edition::{Deprecation, LanguageEdition},
+        parse_items, ParseSettings,
+    };
+
+    #[test]
+    fn test_parse_items_spans() {
+        let source = "// c\nlet x = foo(1);\n\nfn f(): i32 {\n    x\n}\n";
+        let tokens = Lexer::new(source).lex().expect("should lex");
+        let (items, _) =
+            parse_items(&mut tokens.into(), ParseSettings::default()).expect("should parse");
+
+        let texts = items
+            .iter()
+            .map(|(_, span)| span.text(source))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec!["// c", "let x = foo(1);", "fn f(): i32 {\n    x\n}"],
+            texts
+        );
+        assert_eq!(Span { start: 5, end: 20 }, items[1].1);
+    }
+
+    #[test]
+    fn test_parse_items_in_edition() {
+        let tokens = Lexer::new("let match = 1;").lex().expect("should lex");
+        let settings = ParseSettings {
+            edition: LanguageEdition::Edition2023,
+        };
+
+        let (items, deprecations) =
+            parse_items(&mut tokens.into(), settings).expect("should parse");
+
+        assert_eq!(1, items.len());
+        assert_eq!(
+            vec![Deprecation {
+                message: "`match` is a keyword since edition 2024".into(),
+                position: Position::new(4, 5),
+                suggestion: Some("match_".into())
+            }],
+            deprecations
+        );
+    }
+
+    #[test]
+    fn test_parse_items_error_position() {
+        let tokens = Lexer::new("let x = f(1, 2,);").lex().expect("should lex");
+
+        let Err(error) = parse_items(&mut tokens.into(), ParseSettings::default()) else {
+            panic!("expected parse error");
+        };
+
+        assert_eq!(Some(Position::new(14, 1)), error.position);
+    }
 }
@@ -0,0 +1,429 @@
+use crate::lexer::{Terminal, Token};
+
+use super::{
+    ast::{
+        Assignment, AstNode, Block, Comprehension, Declaration, Import, InfiniteLoop, Match,
+        MatchArm, StructFieldInitialisation, StructInitialisation, WhileLoop,
+    },
+    combinators::Comb,
+};
+
+/// Width of a single character within a box (the diagrams use a monospace font).
+const CHAR_WIDTH: usize = 8;
+/// Horizontal padding between the text of a box and its border.
+const PADDING: usize = 10;
+/// Half of the height of a box.
+const HALF_BOX: usize = 12;
+/// Length of the line between two items of a sequence.
+const GAP: usize = 12;
+/// Horizontal space taken by the rails which split and join branches (or loops).
+const RAIL: usize = 20;
+/// Vertical space between two branches (or between an item and its loop).
+const VERTICAL_GAP: usize = 10;
+/// Space around the whole diagram.
+const MARGIN: usize = 20;
+
+/// A railroad (syntax) diagram of a grammar rule, derived from its combinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagram {
+    /// A terminal, drawn as a rounded box with its source text.
+    Terminal(String),
+    /// A reference to another rule, drawn as a box with its name.
+    NonTerminal(String),
+    Sequence(Vec<Diagram>),
+    /// Branches, where the first one is drawn on the main track.
+    Choice(Vec<Diagram>),
+    Optional(Box<Diagram>),
+    /// At least one occurrence of the inner diagram, with a loop back to its start.
+    OneOrMore(Box<Diagram>),
+}
+
+/// Horizontal and vertical extent of a diagram. The track enters on the left and leaves on the
+/// right `up` pixels below the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Extent {
+    width: usize,
+    up: usize,
+    down: usize,
+}
+
+impl<'a> From<&Comb<'a, Token, Terminal, AstNode>> for Diagram {
+    fn from(value: &Comb<'a, Token, Terminal, AstNode>) -> Self {
+        match value {
+            Comb::Node { name, .. } => Diagram::NonTerminal(name.to_string()),
+            Comb::Terminal { token } => Diagram::Terminal(token.text().to_string()),
+            Comb::Sequence { current, next } => {
+                let mut items = vec![];
+                for diagram in [current.as_ref().into(), next.as_ref().into()] {
+                    match diagram {
+                        Diagram::Sequence(nested) => items.extend(nested),
+                        diagram => items.push(diagram),
+                    }
+                }
+                Diagram::Sequence(items)
+            }
+            Comb::Either { left, right } => {
+                let mut branches = vec![];
+                for diagram in [left.as_ref().into(), right.as_ref().into()] {
+                    match diagram {
+                        Diagram::Choice(nested) => branches.extend(nested),
+                        diagram => branches.push(diagram),
+                    }
+                }
+                Diagram::Choice(branches)
+            }
+            Comb::Optional { inner } => match Diagram::from(inner.as_ref()) {
+                optional @ Diagram::Optional(_) => optional,
+                diagram => Diagram::Optional(Box::new(diagram)),
+            },
+            Comb::Repitition {
+                inner,
+                amount: None,
+            } => Diagram::Optional(Box::new(Diagram::OneOrMore(Box::new(
+                inner.as_ref().into(),
+            )))),
+            Comb::Repitition {
+                inner,
+                amount: Some(amount),
+            } => Diagram::Sequence(vec![inner.as_ref().into(); *amount]),
+        }
+    }
+}
+
+impl Diagram {
+    fn extent(&self) -> Extent {
+        match self {
+            Diagram::Terminal(text) | Diagram::NonTerminal(text) => Extent {
+                width: text.chars().count() * CHAR_WIDTH + 2 * PADDING,
+                up: HALF_BOX,
+                down: HALF_BOX,
+            },
+            Diagram::Sequence(items) => {
+                let extents = items.iter().map(Diagram::extent).collect::<Vec<_>>();
+                Extent {
+                    width: extents.iter().map(|extent| extent.width).sum::<usize>()
+                        + GAP * items.len().saturating_sub(1),
+                    up: extents.iter().map(|extent| extent.up).max().unwrap_or(0),
+                    down: extents.iter().map(|extent| extent.down).max().unwrap_or(0),
+                }
+            }
+            Diagram::Choice(branches) => {
+                let extents = branches.iter().map(Diagram::extent).collect::<Vec<_>>();
+                let offsets = Self::branch_offsets(&extents);
+                Extent {
+                    width: extents.iter().map(|extent| extent.width).max().unwrap_or(0) + 2 * RAIL,
+                    up: extents.first().map_or(0, |extent| extent.up),
+                    down: offsets
+                        .last()
+                        .zip(extents.last())
+                        .map_or(0, |(offset, extent)| offset + extent.down),
+                }
+            }
+            Diagram::Optional(inner) => {
+                let Extent { width, up, down } = inner.extent();
+                Extent {
+                    width: width + 2 * RAIL,
+                    up: 0,
+                    down: VERTICAL_GAP + up + down,
+                }
+            }
+            Diagram::OneOrMore(inner) => {
+                let Extent { width, up, down } = inner.extent();
+                Extent {
+                    width: width + 2 * RAIL,
+                    up,
+                    down: down + VERTICAL_GAP,
+                }
+            }
+        }
+    }
+
+    /// Vertical offsets of the tracks of all branches relative to the track of the first one.
+    fn branch_offsets(extents: &[Extent]) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(extents.len());
+        let mut offset = 0;
+
+        for (index, extent) in extents.iter().enumerate() {
+            if index > 0 {
+                offset += extents[index - 1].down + VERTICAL_GAP + extent.up;
+            }
+            offsets.push(offset);
+        }
+
+        offsets
+    }
+
+    /// Draw this diagram with its track starting at `(x, y)`.
+    fn draw(&self, x: usize, y: usize, out: &mut String) {
+        let Extent { width, .. } = self.extent();
+
+        match self {
+            Diagram::Terminal(text) => {
+                out.push_str(&format!(
+                    r#"<g class="terminal"><rect x="{x}" y="{}" width="{width}" height="{}" rx="{HALF_BOX}"/><text x="{}" y="{y}">{}</text></g>"#,
+                    y - HALF_BOX,
+                    2 * HALF_BOX,
+                    x + width / 2,
+                    escape(text)
+                ));
+            }
+            Diagram::NonTerminal(name) => {
+                out.push_str(&format!(
+                    r##"<a href="#{name}"><g class="nonterminal"><rect x="{x}" y="{}" width="{width}" height="{}"/><text x="{}" y="{y}">{}</text></g></a>"##,
+                    y - HALF_BOX,
+                    2 * HALF_BOX,
+                    x + width / 2,
+                    escape(name)
+                ));
+            }
+            Diagram::Sequence(items) => {
+                let mut x = x;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        line(x, y, x + GAP, out);
+                        x += GAP;
+                    }
+                    item.draw(x, y, out);
+                    x += item.extent().width;
+                }
+            }
+            Diagram::Choice(branches) => {
+                let extents = branches.iter().map(Diagram::extent).collect::<Vec<_>>();
+                let offsets = Self::branch_offsets(&extents);
+                let end = x + width;
+
+                for ((branch, extent), offset) in branches.iter().zip(&extents).zip(offsets) {
+                    let track = y + offset;
+                    curve(x, y, x + RAIL, track, out);
+                    branch.draw(x + RAIL, track, out);
+                    line(x + RAIL + extent.width, track, end - RAIL, out);
+                    curve(end - RAIL, track, end, y, out);
+                }
+            }
+            Diagram::Optional(inner) => {
+                Diagram::Choice(vec![Diagram::Sequence(vec![]), inner.as_ref().clone()])
+                    .draw(x, y, out);
+            }
+            Diagram::OneOrMore(inner) => {
+                let Extent {
+                    width: inner_width,
+                    down,
+                    ..
+                } = inner.extent();
+                let end = x + width;
+                let back = y + down + VERTICAL_GAP;
+
+                line(x, y, x + RAIL, out);
+                inner.draw(x + RAIL, y, out);
+                line(x + RAIL + inner_width, y, end, out);
+                out.push_str(&format!(
+                    r#"<path d="M{} {y} C{end} {y} {end} {back} {} {back} H{} C{x} {back} {x} {y} {} {y}"/>"#,
+                    end - RAIL,
+                    end - RAIL,
+                    x + RAIL,
+                    x + RAIL
+                ));
+            }
+        }
+    }
+
+    /// Collect the names of all rules referenced by this diagram (in order of their first
+    /// occurrence).
+    fn references<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Diagram::Terminal(_) => {}
+            Diagram::NonTerminal(name) => {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+            Diagram::Sequence(items) | Diagram::Choice(items) => {
+                items.iter().for_each(|item| item.references(names))
+            }
+            Diagram::Optional(inner) | Diagram::OneOrMore(inner) => inner.references(names),
+        }
+    }
+
+    /// Render this diagram as a standalone SVG image.
+    pub fn to_svg(&self) -> String {
+        let Extent { width, up, down } = self.extent();
+        let y = MARGIN + up;
+
+        let mut out = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" class="railroad" width="{}" height="{}">"#,
+            width + 2 * MARGIN,
+            up + down + 2 * MARGIN
+        );
+
+        // short lines marking the start and the end of the rule
+        line(MARGIN / 2, y, MARGIN, &mut out);
+        self.draw(MARGIN, y, &mut out);
+        line(MARGIN + width, y, MARGIN + width + MARGIN / 2, &mut out);
+
+        out.push_str("</svg>");
+        out
+    }
+}
+
+fn line(from: usize, y: usize, to: usize, out: &mut String) {
+    if from != to {
+        out.push_str(&format!(r#"<path d="M{from} {y} H{to}"/>"#));
+    }
+}
+
+fn curve(x1: usize, y1: usize, x2: usize, y2: usize, out: &mut String) {
+    if y1 == y2 {
+        line(x1, y1, x2, out);
+    } else {
+        let middle = (x1 + x2) / 2;
+        out.push_str(&format!(
+            r#"<path d="M{x1} {y1} C{middle} {y1} {middle} {y2} {x2} {y2}"/>"#
+        ));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Diagrams of all rules, which are completely described by a combinator.
+pub fn rules() -> Vec<(&'static str, Diagram)> {
+    vec![
+        ("Block", Block::grammar().into()),
+        ("Declaration", Declaration::grammar().into()),
+        ("Assignment", Assignment::grammar().into()),
+        ("WhileLoop", WhileLoop::grammar().into()),
+        ("InfiniteLoop", InfiniteLoop::grammar().into()),
+        ("Import", Import::grammar().into()),
+        ("Match", Match::grammar().into()),
+        ("MatchArm", MatchArm::grammar().into()),
+        ("Comprehension", Comprehension::grammar().into()),
+        (
+            "StructInitialisation",
+            StructInitialisation::grammar().into(),
+        ),
+        (
+            "StructFieldInitialisation",
+            StructFieldInitialisation::grammar().into(),
+        ),
+    ]
+}
+
+/// Render the given rules as a single HTML document (with one diagram per rule). References to
+/// other rules link to their diagram. Referenced rules, which are not given (since they are parsed
+/// by hand-written code), get a section without a diagram, so that every link has a target.
+pub fn document(rules: &[(&str, Diagram)]) -> String {
+    let mut out = String::from(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Syntax</title>
+<style>
+svg.railroad path { stroke: #333; stroke-width: 2; fill: none; }
+svg.railroad rect { stroke: #333; stroke-width: 2; }
+svg.railroad .terminal rect { fill: #fff6d0; }
+svg.railroad .nonterminal rect { fill: #d8ecff; }
+svg.railroad text { font: 13px monospace; text-anchor: middle; dominant-baseline: central; }
+</style>
+</head>
+<body>
+"#,
+    );
+
+    for (name, diagram) in rules {
+        out.push_str(&format!(
+            "<section id=\"{name}\">\n<h2>{name}</h2>\n{}\n</section>\n",
+            diagram.to_svg()
+        ));
+    }
+
+    let mut references = vec![];
+    for (_, diagram) in rules {
+        diagram.references(&mut references);
+    }
+
+    for name in references {
+        if rules.iter().all(|(rule, _)| *rule != name) {
+            out.push_str(&format!(
+                "<section id=\"{name}\">\n<h2>{name}</h2>\n<p>Parsed by hand-written code.</p>\n</section>\n"
+            ));
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagram_from_combinator() {
+        let comb = Comb::USE_KEYWORD >> (Comb::ID % Comb::DOUBLE_COLON);
+
+        assert_eq!(
+            Diagram::Sequence(vec![
+                Diagram::Terminal("use".into()),
+                Diagram::Optional(Box::new(Diagram::Sequence(vec![
+                    Diagram::NonTerminal("Id".into()),
+                    Diagram::Optional(Box::new(Diagram::OneOrMore(Box::new(Diagram::Sequence(
+                        vec![
+                            Diagram::Terminal("::".into()),
+                            Diagram::NonTerminal("Id".into())
+                        ]
+                    )))))
+                ])))
+            ]),
+            Diagram::from(&comb)
+        );
+    }
+
+    #[test]
+    fn test_diagram_flattens_choices() {
+        let comb = Comb::LET | Comb::CONST_KEYWORD | Comb::MUT;
+
+        assert_eq!(
+            Diagram::Choice(vec![
+                Diagram::Terminal("let".into()),
+                Diagram::Terminal("const".into()),
+                Diagram::Terminal("mut".into())
+            ]),
+            Diagram::from(&comb)
+        );
+    }
+
+    #[test]
+    fn test_svg_escapes_terminals() {
+        let svg = Diagram::from(&(Comb::LESS_THAN >> Comb::EXPR)).to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(svg.contains(r##"<a href="#Expression">"##));
+    }
+
+    #[test]
+    fn test_document_contains_all_rules() {
+        let rules = rules();
+        let html = document(&rules);
+
+        for (name, _) in &rules {
+            assert!(html.contains(&format!("<section id=\"{name}\">")));
+        }
+        assert!(html.contains(">while</text>"));
+    }
+
+    #[test]
+    fn test_document_links_have_targets() {
+        let html = document(&rules());
+
+        for name in ["Expression", "Statement", "Pattern", "Id", "Block"] {
+            assert!(html.contains(&format!(r##"<a href="#{name}">"##)));
+            assert_eq!(1, html.matches(&format!("<section id=\"{name}\">")).count());
+        }
+    }
+}
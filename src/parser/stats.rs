@@ -0,0 +1,278 @@
+//! Opt-in instrumentation of the parser.
+//!
+//! Statistics are only recorded while running inside of [`collect`]. Outside of it, the overhead
+//! for every rule invocation is a single check of a thread local flag.
+//!
+//! Files parsed concurrently by the loader (see [`crate::loader::parse_files`]) are recorded as
+//! well, if the loader is invoked within [`collect`].
+//!
+//! Memory statistics additionally require the [`CountingAllocator`] to be installed as global
+//! allocator (e.g., in a binary using this crate). The binary of this crate only installs it with
+//! the `counting-allocator` feature:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Statistics of a single grammar rule (i.e., a non-terminal).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleStats {
+    /// How often the rule was invoked.
+    pub invocations: usize,
+    /// How often the rule failed to parse (and the parser had to try something else).
+    pub failures: usize,
+    /// How often the parser backtracked within this rule, i.e., had to rewind tokens consumed by
+    /// an alternative, an optional or a repetition which did not match in the end.
+    pub backtracks: usize,
+    /// Time spent in this rule (including all nested rules).
+    pub time: Duration,
+}
+
+/// Statistics collected for a parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    pub rules: HashMap<&'static str, RuleStats>,
+    /// Total time of the parse.
+    pub time: Duration,
+    /// Peak of memory allocated during the parse (in bytes), if the [`CountingAllocator`] is
+    /// installed.
+    pub peak_memory: Option<usize>,
+}
+
+impl ParseStats {
+    /// Add the rule statistics of another parse (e.g., of a file parsed on another thread).
+    fn merge(&mut self, other: ParseStats) {
+        for (name, stats) in other.rules {
+            let rule = self.rules.entry(name).or_default();
+            rule.invocations += stats.invocations;
+            rule.failures += stats.failures;
+            rule.backtracks += stats.backtracks;
+            rule.time += stats.time;
+        }
+    }
+}
+
+impl Display for ParseStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rules = self.rules.iter().collect::<Vec<_>>();
+        rules.sort_by(|(a_name, a), (b_name, b)| b.time.cmp(&a.time).then(a_name.cmp(b_name)));
+
+        writeln!(
+            f,
+            "{:<28} {:>12} {:>10} {:>11} {:>12}",
+            "rule", "invocations", "failures", "backtracks", "time"
+        )?;
+        for (name, stats) in rules {
+            writeln!(
+                f,
+                "{:<28} {:>12} {:>10} {:>11} {:>12?}",
+                name, stats.invocations, stats.failures, stats.backtracks, stats.time
+            )?;
+        }
+
+        writeln!(f, "total time: {:?}", self.time)?;
+        match self.peak_memory {
+            Some(peak) => writeln!(f, "peak memory: {peak} bytes"),
+            None => writeln!(f, "peak memory: unknown (counting allocator not installed)"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    stats: ParseStats,
+    /// Rules which are currently being parsed (innermost last).
+    stack: Vec<&'static str>,
+}
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static RECORDER: RefCell<Recorder> = RefCell::new(Recorder::default());
+}
+
+/// Run the given function (e.g., a parse) and collect statistics for all rules invoked on this
+/// thread in the meantime.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, ParseStats) {
+    let was_enabled = ENABLED.replace(true);
+    let previous = RECORDER.take();
+
+    let memory_baseline = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(memory_baseline, Ordering::Relaxed);
+
+    let start = Instant::now();
+    let result = f();
+    let time = start.elapsed();
+
+    let mut stats = RECORDER.replace(previous).stats;
+    ENABLED.set(was_enabled);
+
+    stats.time = time;
+    stats.peak_memory = INSTALLED
+        .load(Ordering::Relaxed)
+        .then(|| PEAK.load(Ordering::Relaxed).saturating_sub(memory_baseline));
+
+    (result, stats)
+}
+
+pub(crate) fn enabled() -> bool {
+    ENABLED.get()
+}
+
+/// Run the given function on another thread (which was spawned while statistics are collected on
+/// the current thread) and return its result along with the statistics it recorded.
+/// Only the rules are recorded, since time and memory are already tracked by the outer [`collect`].
+pub(crate) fn collect_on_thread<R>(f: impl FnOnce() -> R) -> (R, ParseStats) {
+    let was_enabled = ENABLED.replace(true);
+    let previous = RECORDER.take();
+
+    let result = f();
+
+    let stats = RECORDER.replace(previous).stats;
+    ENABLED.set(was_enabled);
+
+    (result, stats)
+}
+
+/// Add statistics recorded on another thread (see [`collect_on_thread`]) to the current one.
+pub(crate) fn record(stats: ParseStats) {
+    if !enabled() {
+        return;
+    }
+
+    RECORDER.with_borrow_mut(|recorder| recorder.stats.merge(stats));
+}
+
+/// Record the invocation of the given rule.
+pub(crate) fn rule<T, E>(name: &'static str, f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    if !enabled() {
+        return f();
+    }
+
+    RECORDER.with_borrow_mut(|recorder| recorder.stack.push(name));
+
+    let start = Instant::now();
+    let result = f();
+    let time = start.elapsed();
+
+    RECORDER.with_borrow_mut(|recorder| {
+        recorder.stack.pop();
+
+        let stats = recorder.stats.rules.entry(name).or_default();
+        stats.invocations += 1;
+        stats.failures += usize::from(result.is_err());
+        // recursive invocations are already included in the time of the outermost one
+        if !recorder.stack.contains(&name) {
+            stats.time += time;
+        }
+    });
+
+    result
+}
+
+/// Record a backtrack within the rule which is currently parsed.
+pub(crate) fn backtrack() {
+    if !enabled() {
+        return;
+    }
+
+    RECORDER.with_borrow_mut(|recorder| {
+        let name = recorder.stack.last().copied().unwrap_or("<root>");
+        recorder.stats.rules.entry(name).or_default().backtracks += 1;
+    });
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator which keeps track of the currently allocated memory, so [`collect`] can report
+/// the peak memory of a parse.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::{parse, stats::collect, ParseSettings},
+    };
+
+    #[test]
+    fn test_collect_counts_rules() {
+        let mut tokens = Lexer::new("let a = 42;\nlet b = a + 1;")
+            .lex()
+            .expect("should lex")
+            .into();
+
+        let (result, stats) = collect(|| parse(&mut tokens, ParseSettings::default()));
+
+        assert!(result.is_ok());
+        assert_eq!(2, stats.rules["Statement"].invocations);
+        assert_eq!(2, stats.rules["Initialisation"].invocations);
+        assert_eq!(0, stats.rules["Initialisation"].failures);
+        // the operands of `a + 1` are parsed within the same invocation
+        assert_eq!(2, stats.rules["Expression"].invocations);
+    }
+
+    #[test]
+    fn test_collect_counts_backtracks() {
+        // the path is parsed again as the name of a struct initialisation
+        let mut tokens = Lexer::new("let p = Shape::Rect { w: 1 };")
+            .lex()
+            .expect("should lex")
+            .into();
+
+        let (_, stats) = collect(|| parse(&mut tokens, ParseSettings::default()));
+
+        assert!(stats.rules["Expression"].backtracks > 0);
+    }
+
+    #[test]
+    fn test_unmatched_alternatives_are_no_backtracks() {
+        // the repetition of statements within the block ends without consuming any token
+        let mut tokens = Lexer::new("while (foo) { bar(); }")
+            .lex()
+            .expect("should lex")
+            .into();
+
+        let (_, stats) = collect(|| parse(&mut tokens, ParseSettings::default()));
+
+        assert!(stats.rules.values().all(|rule| rule.backtracks == 0));
+    }
+
+    #[test]
+    fn test_no_stats_outside_of_collect() {
+        let mut tokens = Lexer::new("let a = 42;").lex().expect("should lex").into();
+        parse(&mut tokens, ParseSettings::default()).expect("should parse");
+
+        let (_, stats) = collect(|| ());
+
+        assert!(stats.rules.is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::Display;
+
+use crate::{
+    loader::Workspace,
+    parser::ast::{Function, Statement, TypeName},
+};
+
+use super::Diagnostic;
+
+/// The expected signature of the entry point of an executable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoint {
+    pub name: String,
+    pub parameters: Vec<TypeName>,
+    pub return_type: TypeName,
+}
+
+impl Default for EntryPoint {
+    /// `fn main(): void`
+    fn default() -> Self {
+        Self {
+            name: "main".into(),
+            parameters: vec![],
+            return_type: TypeName::Void,
+        }
+    }
+}
+
+impl Display for EntryPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fn {}(", self.name)?;
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{parameter}")?;
+        }
+        write!(f, "): {}", self.return_type)
+    }
+}
+
+impl EntryPoint {
+    fn matches(&self, function: &Function) -> bool {
+        function.return_type == self.return_type
+            && function.parameters.len() == self.parameters.len()
+            && function
+                .parameters
+                .iter()
+                .zip(&self.parameters)
+                .all(|(parameter, expected)| parameter.type_name.as_ref() == Some(expected))
+    }
+}
+
+/// Check that the workspace defines exactly one top-level function with the name of the entry
+/// point and that it has the expected signature.
+pub fn check_entry_point(workspace: &Workspace, entry_point: &EntryPoint) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut definitions = vec![];
+
+    for program in workspace.programs() {
+        for (item, statement) in program.statements.iter().enumerate() {
+            let Statement::Function(function) = statement else {
+                continue;
+            };
+
+            if function
+                .id
+                .as_ref()
+                .is_some_and(|id| id.0.as_str() == entry_point.name)
+            {
+                definitions.push((program, item, function));
+            }
+        }
+    }
+
+    match definitions.as_slice() {
+        [] => diagnostics.push(Diagnostic {
+            message: format!("missing entry point `{entry_point}`"),
+            path: workspace.entry().map(|program| program.file.path.clone()),
+            location: None,
+        }),
+        [_] => {}
+        [_, duplicates @ ..] => {
+            for (program, item, _) in duplicates {
+                diagnostics.push(Diagnostic::at_item(
+                    format!(
+                        "duplicate entry point `{}`, it is already defined",
+                        entry_point.name
+                    ),
+                    program,
+                    *item,
+                ));
+            }
+        }
+    }
+
+    for (program, item, function) in &definitions {
+        if !entry_point.matches(function) {
+            diagnostics.push(Diagnostic::at_item(
+                format!("entry point must have the signature `{entry_point}`"),
+                program,
+                *item,
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        loader::{parse_program, LineCol, Workspace},
+        parser::ParseSettings,
+    };
+
+    use super::*;
+
+    fn workspace(sources: &[&str]) -> Workspace {
+        let mut workspace = Workspace::default();
+        for (index, source) in sources.iter().enumerate() {
+            let program = parse_program(
+                format!("file{index}.why"),
+                source.to_string(),
+                ParseSettings::default(),
+            )
+            .expect("should parse");
+            workspace.insert(program);
+        }
+        workspace
+    }
+
+    #[test]
+    fn test_valid_entry_point() {
+        let workspace = workspace(&["fn main(): void {}"]);
+
+        assert!(check_entry_point(&workspace, &EntryPoint::default()).is_empty());
+    }
+
+    #[test]
+    fn test_unit_return_type_is_void() {
+        let workspace = workspace(&["fn main(): () {}"]);
+
+        assert!(check_entry_point(&workspace, &EntryPoint::default()).is_empty());
+    }
+
+    #[test]
+    fn test_missing_entry_point() {
+        let workspace = workspace(&["fn foo(): void {}"]);
+
+        let diagnostics = check_entry_point(&workspace, &EntryPoint::default());
+
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(
+            "file0.why: missing entry point `fn main(): void`",
+            diagnostics[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_entry_point() {
+        let workspace = workspace(&["fn main(): void {}", "let a = 1;\nfn main(): void {}"]);
+
+        let diagnostics = check_entry_point(&workspace, &EntryPoint::default());
+
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(
+            Some(LineCol { line: 2, column: 1 }),
+            diagnostics[0].location
+        );
+        assert!(diagnostics[0].message.contains("duplicate"));
+    }
+
+    #[test]
+    fn test_entry_point_with_wrong_signature() {
+        let workspace = workspace(&["fn main(x: i32): i32 { x }"]);
+
+        let diagnostics = check_entry_point(&workspace, &EntryPoint::default());
+
+        assert_eq!(1, diagnostics.len());
+        assert!(diagnostics[0].message.contains("signature"));
+    }
+
+    #[test]
+    fn test_configured_signature() {
+        let workspace = workspace(&["fn start(args: [str]): i32 { 0 }"]);
+        let entry_point = EntryPoint {
+            name: "start".into(),
+            parameters: vec![TypeName::Array(Box::new(TypeName::Literal("str".into())))],
+            return_type: TypeName::Literal("i32".into()),
+        };
+
+        assert!(check_entry_point(&workspace, &entry_point).is_empty());
+        assert_eq!("fn start([str]): i32", entry_point.to_string());
+    }
+}
@@ -0,0 +1,48 @@
+//! Checks on parsed programs which go beyond the grammar of the language.
+
+mod entry_point;
+
+pub use self::entry_point::*;
+
+use std::{fmt::Display, path::PathBuf};
+
+use crate::loader::{LineCol, Program};
+
+/// A problem found by a semantic check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    /// The file the problem was found in (if it is tied to a single file).
+    pub path: Option<PathBuf>,
+    pub location: Option<LineCol>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic pointing at the start of the top-level item with the given index.
+    pub fn at_item(message: impl Into<String>, program: &Program, item: usize) -> Self {
+        let location = program.items.get(item).map(|range| {
+            let source = &program.file.source()[range.clone()];
+            // the range of an item might start with whitespace
+            let leading = source.len() - source.trim_start().len();
+            program.file.line_col(range.start + leading)
+        });
+
+        Self {
+            message: message.into(),
+            path: Some(program.file.path.clone()),
+            location,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.path, self.location) {
+            (Some(path), Some(location)) => {
+                write!(f, "{}:{location}: {}", path.display(), self.message)
+            }
+            (Some(path), None) => write!(f, "{}: {}", path.display(), self.message),
+            (None, _) => f.write_str(&self.message),
+        }
+    }
+}
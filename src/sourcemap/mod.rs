@@ -0,0 +1,292 @@
+mod vlq;
+
+use std::ops::Range;
+
+use crate::loader::{FileId, Workspace};
+
+/// Mapping of a range of generated output to the range of original source it was generated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub generated: Range<usize>,
+    pub file: FileId,
+    pub original: Range<usize>,
+}
+
+/// Collection of mappings from generated output back to the original sources.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// Find the (innermost) mapping containing the given byte offset of the generated output.
+    pub fn lookup(&self, generated: usize) -> Option<&Mapping> {
+        self.mappings
+            .iter()
+            .filter(|mapping| mapping.generated.contains(&generated))
+            .min_by_key(|mapping| mapping.generated.len())
+    }
+
+    /// Render this source map in the (JSON based) source map format v3, so it can be consumed by
+    /// external tools. The paths of the original files are taken from the given workspace.
+    pub fn to_json(&self, workspace: &Workspace, file: &str, output: &str) -> String {
+        let mut mappings = self.mappings.clone();
+        mappings.sort_by_key(|mapping| (mapping.generated.start, mapping.generated.end));
+
+        let mut sources: Vec<FileId> = vec![];
+        for mapping in &mappings {
+            if !sources.contains(&mapping.file) {
+                sources.push(mapping.file);
+            }
+        }
+
+        // a segment starts at every boundary of a mapping, where the innermost mapping changes
+        // (e.g., the outer mapping is restored after a nested one ended)
+        let mut boundaries = mappings
+            .iter()
+            .flat_map(|mapping| [mapping.generated.start, mapping.generated.end])
+            .filter(|offset| *offset < output.len())
+            .collect::<Vec<_>>();
+        boundaries.sort();
+        boundaries.dedup();
+
+        let mut segments: Vec<(usize, Option<&Mapping>)> = vec![];
+        for offset in boundaries {
+            let mapping = self.lookup(offset);
+            if segments
+                .last()
+                .map_or(mapping.is_some(), |(_, last)| *last != mapping)
+            {
+                segments.push((offset, mapping));
+            }
+        }
+
+        let mut encoded = String::new();
+        let mut line = 0;
+        let mut previous_column = 0;
+        let mut previous_source = 0;
+        let mut previous_line = 0;
+        let mut previous_original_column = 0;
+        let mut first_in_line = true;
+
+        for (offset, mapping) in segments {
+            let (generated_line, generated_column) = line_col(output, offset);
+
+            while line < generated_line {
+                encoded.push(';');
+                line += 1;
+                previous_column = 0;
+                first_in_line = true;
+            }
+
+            if !first_in_line {
+                encoded.push(',');
+            }
+            first_in_line = false;
+
+            vlq::encode(generated_column as i64 - previous_column, &mut encoded);
+            previous_column = generated_column as i64;
+
+            let Some(mapping) = mapping else {
+                continue;
+            };
+
+            let source = sources
+                .iter()
+                .position(|id| *id == mapping.file)
+                .expect("source should be collected") as i64;
+            let original = workspace
+                .get(mapping.file)
+                .map(|program| line_col(program.file.source(), mapping.original.start))
+                .unwrap_or((0, 0));
+
+            vlq::encode(source - previous_source, &mut encoded);
+            vlq::encode(original.0 as i64 - previous_line, &mut encoded);
+            vlq::encode(original.1 as i64 - previous_original_column, &mut encoded);
+
+            previous_source = source;
+            previous_line = original.0 as i64;
+            previous_original_column = original.1 as i64;
+        }
+
+        let sources = sources
+            .iter()
+            .map(|id| {
+                let path = workspace
+                    .get(*id)
+                    .map(|program| program.file.path.display().to_string())
+                    .unwrap_or_default();
+                format!("\"{}\"", escape(&path))
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"version\":3,\"file\":\"{}\",\"sources\":[{sources}],\"names\":[],\"mappings\":\"{encoded}\"}}",
+            escape(file)
+        )
+    }
+}
+
+/// Writer for generated code which keeps track of where each piece of output originated from.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMapWriter {
+    output: String,
+    map: SourceMap,
+}
+
+impl SourceMapWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write output which does not correspond to any original source.
+    pub fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    /// Write output which was generated from the given range of the original file.
+    pub fn write_mapped(&mut self, text: &str, file: FileId, original: Range<usize>) {
+        let start = self.output.len();
+        self.output.push_str(text);
+        self.map.mappings.push(Mapping {
+            generated: start..self.output.len(),
+            file,
+            original,
+        });
+    }
+
+    /// Run the given closure and map all output it writes to the given range of the original
+    /// file. Mappings created within the closure take precedence on lookup.
+    pub fn mapped(&mut self, file: FileId, original: Range<usize>, f: impl FnOnce(&mut Self)) {
+        let start = self.output.len();
+        f(self);
+        self.map.mappings.push(Mapping {
+            generated: start..self.output.len(),
+            file,
+            original,
+        });
+    }
+
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    pub fn finish(self) -> (String, SourceMap) {
+        (self.output, self.map)
+    }
+}
+
+/// Compute the (zero based) line and column (in UTF-16 code units) of a byte offset.
+fn line_col(text: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(text.len());
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let column = before[line_start..].encode_utf16().count();
+
+    (line, column)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        loader::{parse_program, FileId, Workspace},
+        parser::ParseSettings,
+    };
+
+    use super::*;
+
+    fn workspace(source: &str) -> Workspace {
+        let mut workspace = Workspace::default();
+        workspace.insert(
+            parse_program("main.why", source.into(), ParseSettings::default())
+                .expect("should parse"),
+        );
+        workspace
+    }
+
+    #[test]
+    fn test_lookup() {
+        let mut writer = SourceMapWriter::new();
+        writer.write("// generated\n");
+        writer.mapped(FileId(0), 0..10, |writer| {
+            writer.write("let ");
+            writer.write_mapped("a", FileId(0), 4..5);
+            writer.write(" = 1;");
+        });
+
+        let (output, map) = writer.finish();
+
+        assert_eq!("// generated\nlet a = 1;", output);
+        assert_eq!(None, map.lookup(3));
+        assert_eq!(Some(0..10), map.lookup(13).map(|m| m.original.clone()));
+        assert_eq!(Some(4..5), map.lookup(17).map(|m| m.original.clone()));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let workspace = workspace("let a = 1;\nlet b = 2;\n");
+
+        let mut writer = SourceMapWriter::new();
+        writer.write_mapped("let b = 2;", FileId(0), 11..21);
+        writer.write("\n");
+        writer.write_mapped("let a = 1;", FileId(0), 0..10);
+
+        let (output, map) = writer.finish();
+        let json = map.to_json(&workspace, "out.why", &output);
+
+        assert_eq!(
+            "{\"version\":3,\"file\":\"out.why\",\"sources\":[\"main.why\"],\"names\":[],\"mappings\":\"AACA,U;AADA\"}",
+            json
+        );
+    }
+
+    #[test]
+    fn test_to_json_nested() {
+        let workspace = workspace("let a = 1;\n");
+
+        let mut writer = SourceMapWriter::new();
+        writer.mapped(FileId(0), 0..10, |writer| {
+            writer.write("let ");
+            writer.write_mapped("a", FileId(0), 4..5);
+            writer.write(" = 1;");
+        });
+        writer.write_mapped("", FileId(0), 10..10);
+
+        let (output, map) = writer.finish();
+        let json = map.to_json(&workspace, "out.why", &output);
+
+        // the outer mapping is restored after `a` (and the empty mapping at the end is skipped)
+        assert_eq!(
+            "{\"version\":3,\"file\":\"out.why\",\"sources\":[\"main.why\"],\"names\":[],\"mappings\":\"AAAA,IAAI,CAAJ\"}",
+            json
+        );
+    }
+
+    #[test]
+    fn test_line_col() {
+        assert_eq!((0, 0), line_col("abc\ndef", 0));
+        assert_eq!((0, 3), line_col("abc\ndef", 3));
+        assert_eq!((1, 1), line_col("abc\ndef", 5));
+        assert_eq!((1, 1), line_col("äbc\nöef", 7));
+    }
+}
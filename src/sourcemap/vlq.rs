@@ -0,0 +1,52 @@
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Append the base64 VLQ encoding (as used by source maps) of the given value.
+pub fn encode(value: i64, output: &mut String) {
+    let mut value = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+
+    loop {
+        let mut digit = value & 0b11111;
+        value >>= 5;
+
+        if value > 0 {
+            digit |= 0b100000;
+        }
+
+        output.push(BASE64[digit as usize] as char);
+
+        if value == 0 {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode;
+
+    fn encoded(value: i64) -> String {
+        let mut output = String::new();
+        encode(value, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_encode_small_values() {
+        assert_eq!("A", encoded(0));
+        assert_eq!("C", encoded(1));
+        assert_eq!("D", encoded(-1));
+        assert_eq!("e", encoded(15));
+    }
+
+    #[test]
+    fn test_encode_large_values() {
+        assert_eq!("gB", encoded(16));
+        assert_eq!("hB", encoded(-16));
+        assert_eq!("2H", encoded(123));
+        assert_eq!("ggggI", encoded(1 << 22));
+    }
+}